@@ -0,0 +1,87 @@
+use std::sync::Arc;
+
+use crate::clipboard::{
+    Clipboard, ClipboardFallback, ClipboardProvider, MemoryClipboard, Osc52Clipboard,
+    SystemClipboard,
+};
+
+impl Clipboard {
+    /// Create a clipboard without providers (copying always fails)
+    pub fn new() -> Self {
+        Self {
+            providers: Vec::new(),
+            fallback: ClipboardFallback::default(),
+        }
+    }
+
+    /// Pick providers for the current environment
+    ///
+    /// Over SSH, or on Linux without a display server, OSC 52 is tried before
+    /// the system clipboard; otherwise the system clipboard comes first.
+    pub fn detect() -> Self {
+        let prefer_osc52 = prefers_osc52(|name| std::env::var_os(name).is_some());
+        let clipboard = Self::new();
+        if prefer_osc52 {
+            clipboard
+                .with_provider(Osc52Clipboard::new())
+                .with_provider(SystemClipboard::new())
+        } else {
+            clipboard
+                .with_provider(SystemClipboard::new())
+                .with_provider(Osc52Clipboard::new())
+        }
+    }
+
+    /// Create a clipboard that only uses the system clipboard
+    pub fn system() -> Self {
+        Self::new().with_provider(SystemClipboard::new())
+    }
+
+    /// Create a clipboard that only writes OSC 52 to stdout
+    pub fn osc52() -> Self {
+        Self::new().with_provider(Osc52Clipboard::new())
+    }
+
+    /// Create a clipboard backed by `memory` (for tests)
+    pub fn memory(memory: MemoryClipboard) -> Self {
+        Self::new().with_provider(memory)
+    }
+
+    /// Append a provider (builder pattern)
+    pub fn with_provider(mut self, provider: impl ClipboardProvider + 'static) -> Self {
+        self.providers.push(Arc::new(provider));
+        self
+    }
+
+    /// Set how providers are combined (builder pattern)
+    pub fn with_fallback(mut self, fallback: ClipboardFallback) -> Self {
+        self.fallback = fallback;
+        self
+    }
+}
+
+/// Check whether OSC 52 should be preferred, given which variables are set
+fn prefers_osc52(is_set: impl Fn(&str) -> bool) -> bool {
+    let ssh = is_set("SSH_CONNECTION") || is_set("SSH_TTY");
+    let headless = cfg!(all(unix, not(target_os = "macos")))
+        && !is_set("DISPLAY")
+        && !is_set("WAYLAND_DISPLAY");
+    ssh || headless
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prefers_osc52_over_ssh() {
+        assert!(prefers_osc52(|name| name == "SSH_TTY" || name == "DISPLAY"));
+        assert!(prefers_osc52(|name| name == "SSH_CONNECTION"));
+    }
+
+    #[test]
+    fn test_prefers_system_with_display() {
+        assert!(!prefers_osc52(|name| name == "DISPLAY"));
+        assert!(!prefers_osc52(|name| name == "WAYLAND_DISPLAY"));
+    }
+}
@@ -0,0 +1,143 @@
+use std::sync::{OnceLock, RwLock};
+
+use anyhow::{anyhow, Result};
+
+use crate::clipboard::{Clipboard, ClipboardFallback};
+
+/// Clipboard used by copy paths that were not given one
+static GLOBAL: OnceLock<RwLock<Clipboard>> = OnceLock::new();
+
+impl Clipboard {
+    /// Get the process-wide clipboard (detected on first use)
+    ///
+    /// Widgets without their own clipboard copy through this one.
+    pub fn global() -> Clipboard {
+        GLOBAL
+            .get_or_init(|| RwLock::new(Clipboard::detect()))
+            .read()
+            .map(|clipboard| clipboard.clone())
+            .unwrap_or_default()
+    }
+
+    /// Replace the process-wide clipboard
+    pub fn set_global(clipboard: Clipboard) {
+        let global = GLOBAL.get_or_init(|| RwLock::new(Clipboard::new()));
+        if let Ok(mut global) = global.write() {
+            *global = clipboard;
+        }
+    }
+
+    /// Copy text, following the fallback rule
+    ///
+    /// Returns the last provider error if no provider succeeded.
+    pub fn set_text(&self, text: &str) -> Result<()> {
+        let mut copied = false;
+        let mut last_error = None;
+        for provider in &self.providers {
+            match provider.set_text(text) {
+                Ok(()) => {
+                    copied = true;
+                    if self.fallback == ClipboardFallback::FirstSuccess {
+                        break;
+                    }
+                }
+                Err(e) => last_error = Some(e.context(format!("{} clipboard", provider.name()))),
+            }
+        }
+
+        match (copied, last_error) {
+            (true, _) => Ok(()),
+            (false, Some(e)) => Err(e),
+            (false, None) => Err(anyhow!("no clipboard provider configured")),
+        }
+    }
+
+    /// Read text from the first provider that supports reading
+    pub fn get_text(&self) -> Result<String> {
+        let mut last_error = anyhow!("no clipboard provider configured");
+        for provider in &self.providers {
+            match provider.get_text() {
+                Ok(text) => return Ok(text),
+                Err(e) => last_error = e.context(format!("{} clipboard", provider.name())),
+            }
+        }
+        Err(last_error)
+    }
+
+    /// Get the provider names in the order they are tried
+    pub fn provider_names(&self) -> Vec<&str> {
+        self.providers
+            .iter()
+            .map(|provider| provider.name())
+            .collect()
+    }
+
+    /// Get how providers are combined
+    pub fn fallback(&self) -> ClipboardFallback {
+        self.fallback
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clipboard::{ClipboardProvider, MemoryClipboard};
+
+    /// Provider that always fails
+    struct Broken;
+
+    impl ClipboardProvider for Broken {
+        fn name(&self) -> &str {
+            "broken"
+        }
+
+        fn set_text(&self, _text: &str) -> Result<()> {
+            Err(anyhow!("unavailable"))
+        }
+
+        fn get_text(&self) -> Result<String> {
+            Err(anyhow!("unavailable"))
+        }
+    }
+
+    #[test]
+    fn test_first_success_falls_back() {
+        let first = MemoryClipboard::new();
+        let second = MemoryClipboard::new();
+        let clipboard = Clipboard::new()
+            .with_provider(Broken)
+            .with_provider(first.clone())
+            .with_provider(second.clone());
+
+        clipboard.set_text("hello").unwrap();
+        assert_eq!(first.text().as_deref(), Some("hello"));
+        assert_eq!(second.text(), None);
+        assert_eq!(clipboard.get_text().unwrap(), "hello");
+        assert_eq!(clipboard.provider_names(), ["broken", "memory", "memory"]);
+    }
+
+    #[test]
+    fn test_all_copies_everywhere() {
+        let first = MemoryClipboard::new();
+        let second = MemoryClipboard::new();
+        let clipboard = Clipboard::new()
+            .with_provider(first.clone())
+            .with_provider(Broken)
+            .with_provider(second.clone())
+            .with_fallback(ClipboardFallback::All);
+
+        clipboard.set_text("both").unwrap();
+        assert_eq!(first.text().as_deref(), Some("both"));
+        assert_eq!(second.text().as_deref(), Some("both"));
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(Clipboard::new().set_text("x").is_err());
+        let err = Clipboard::new()
+            .with_provider(Broken)
+            .set_text("x")
+            .unwrap_err();
+        assert!(format!("{:#}", err).contains("broken clipboard"));
+    }
+}
@@ -0,0 +1,48 @@
+//! Clipboard component
+//!
+//! A clipboard shared by every copy path in the toolkit, backed by an ordered
+//! list of providers: the system clipboard (arboard), OSC 52 escape sequences
+//! written to the host terminal (works over SSH), and an in-memory clipboard
+//! for tests.
+
+pub mod constructors;
+pub mod methods;
+pub mod providers;
+pub mod traits;
+
+pub use providers::{osc52_sequence, MemoryClipboard, Osc52Clipboard, SystemClipboard};
+
+use std::sync::Arc;
+
+/// A place copied text can be sent to
+pub trait ClipboardProvider: Send + Sync {
+    /// Short name for diagnostics (e.g. "system", "osc52")
+    fn name(&self) -> &str;
+
+    /// Replace the clipboard contents
+    fn set_text(&self, text: &str) -> anyhow::Result<()>;
+
+    /// Read the clipboard contents, if the provider supports it
+    fn get_text(&self) -> anyhow::Result<String>;
+}
+
+/// How a `Clipboard` uses its providers when copying
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ClipboardFallback {
+    /// Try providers in order and stop at the first that succeeds
+    #[default]
+    FirstSuccess,
+    /// Copy with every provider; succeeds if any of them does
+    All,
+}
+
+/// Clipboard that copies through an ordered list of providers
+///
+/// Cloning is cheap: clones share the same providers.
+#[derive(Clone)]
+pub struct Clipboard {
+    /// Providers in the order they are tried
+    providers: Vec<Arc<dyn ClipboardProvider>>,
+    /// How providers are combined
+    fallback: ClipboardFallback,
+}
@@ -0,0 +1,41 @@
+use std::sync::{Arc, Mutex};
+
+use anyhow::{anyhow, Result};
+
+use crate::clipboard::ClipboardProvider;
+
+/// In-memory clipboard, mainly for tests
+///
+/// Clones share their contents, so a test can keep one to inspect what a
+/// widget copied.
+#[derive(Clone, Debug, Default)]
+pub struct MemoryClipboard {
+    text: Arc<Mutex<Option<String>>>,
+}
+
+impl MemoryClipboard {
+    /// Create an empty clipboard
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the copied text, if anything was copied
+    pub fn text(&self) -> Option<String> {
+        self.text.lock().unwrap().clone()
+    }
+}
+
+impl ClipboardProvider for MemoryClipboard {
+    fn name(&self) -> &str {
+        "memory"
+    }
+
+    fn set_text(&self, text: &str) -> Result<()> {
+        *self.text.lock().unwrap() = Some(text.to_string());
+        Ok(())
+    }
+
+    fn get_text(&self) -> Result<String> {
+        self.text().ok_or_else(|| anyhow!("clipboard is empty"))
+    }
+}
@@ -0,0 +1,9 @@
+//! Clipboard providers
+
+mod memory;
+mod osc52;
+mod system;
+
+pub use memory::MemoryClipboard;
+pub use osc52::{osc52_sequence, Osc52Clipboard};
+pub use system::SystemClipboard;
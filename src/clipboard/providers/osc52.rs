@@ -0,0 +1,102 @@
+use std::io::Write;
+use std::sync::Mutex;
+
+use anyhow::{bail, Result};
+use base64::Engine;
+
+use crate::clipboard::ClipboardProvider;
+
+/// Copies by writing an OSC 52 escape sequence to the host terminal
+///
+/// Works over SSH and inside tmux (with `set-clipboard on`), as long as the
+/// terminal emulator supports OSC 52. The clipboard cannot be read back.
+pub struct Osc52Clipboard {
+    writer: Mutex<Box<dyn Write + Send>>,
+    tmux: bool,
+}
+
+impl Osc52Clipboard {
+    /// Write to stdout, wrapping for tmux when `$TMUX` is set
+    pub fn new() -> Self {
+        Self {
+            writer: Mutex::new(Box::new(std::io::stdout())),
+            tmux: std::env::var_os("TMUX").is_some(),
+        }
+    }
+
+    /// Write sequences to `writer` instead of stdout (builder pattern)
+    pub fn with_writer(mut self, writer: impl Write + Send + 'static) -> Self {
+        self.writer = Mutex::new(Box::new(writer));
+        self
+    }
+
+    /// Set whether to wrap sequences in tmux passthrough (builder pattern)
+    pub fn with_tmux_passthrough(mut self, tmux: bool) -> Self {
+        self.tmux = tmux;
+        self
+    }
+}
+
+impl Default for Osc52Clipboard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ClipboardProvider for Osc52Clipboard {
+    fn name(&self) -> &str {
+        "osc52"
+    }
+
+    fn set_text(&self, text: &str) -> Result<()> {
+        let mut writer = self.writer.lock().unwrap();
+        writer.write_all(osc52_sequence(text, self.tmux).as_bytes())?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    fn get_text(&self) -> Result<String> {
+        bail!("OSC 52 clipboard cannot be read")
+    }
+}
+
+/// Build the OSC 52 sequence that sets the clipboard to `text`
+///
+/// With `tmux`, the sequence is wrapped in a DCS passthrough so tmux forwards
+/// it to the outer terminal.
+pub fn osc52_sequence(text: &str, tmux: bool) -> String {
+    let encoded = base64::engine::general_purpose::STANDARD.encode(text);
+    let sequence = format!("\x1b]52;c;{}\x07", encoded);
+    if tmux {
+        format!("\x1bPtmux;{}\x1b\\", sequence.replace('\x1b', "\x1b\x1b"))
+    } else {
+        sequence
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::SharedBuf;
+
+    #[test]
+    fn test_osc52_sequence() {
+        assert_eq!(osc52_sequence("hello", false), "\x1b]52;c;aGVsbG8=\x07");
+        assert_eq!(
+            osc52_sequence("hello", true),
+            "\x1bPtmux;\x1b\x1b]52;c;aGVsbG8=\x07\x1b\\"
+        );
+    }
+
+    #[test]
+    fn test_writes_to_writer() {
+        let buf = SharedBuf::default();
+        let clipboard = Osc52Clipboard::new()
+            .with_writer(buf.clone())
+            .with_tmux_passthrough(false);
+
+        clipboard.set_text("hi").unwrap();
+        assert_eq!(buf.bytes(), b"\x1b]52;c;aGk=\x07");
+        assert!(clipboard.get_text().is_err());
+    }
+}
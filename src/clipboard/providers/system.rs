@@ -0,0 +1,52 @@
+use std::sync::Mutex;
+
+use anyhow::Result;
+
+use crate::clipboard::ClipboardProvider;
+
+/// The system clipboard, through arboard
+///
+/// The connection is opened on first use and kept, since on X11 the copied
+/// text is only served while it is alive.
+#[derive(Default)]
+pub struct SystemClipboard {
+    clipboard: Mutex<Option<arboard::Clipboard>>,
+}
+
+impl SystemClipboard {
+    /// Create a provider; nothing is opened until the first copy
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn with_clipboard<T>(
+        &self,
+        f: impl FnOnce(&mut arboard::Clipboard) -> Result<T, arboard::Error>,
+    ) -> Result<T> {
+        let mut guard = self.clipboard.lock().unwrap();
+        let clipboard = match guard.as_mut() {
+            Some(clipboard) => clipboard,
+            None => guard.insert(arboard::Clipboard::new()?),
+        };
+        let result = f(clipboard);
+        if result.is_err() {
+            // Reconnect next time, e.g. after the display server restarted
+            *guard = None;
+        }
+        Ok(result?)
+    }
+}
+
+impl ClipboardProvider for SystemClipboard {
+    fn name(&self) -> &str {
+        "system"
+    }
+
+    fn set_text(&self, text: &str) -> Result<()> {
+        self.with_clipboard(|clipboard| clipboard.set_text(text))
+    }
+
+    fn get_text(&self) -> Result<String> {
+        self.with_clipboard(|clipboard| clipboard.get_text())
+    }
+}
@@ -0,0 +1,16 @@
+use crate::clipboard::Clipboard;
+
+impl Default for Clipboard {
+    fn default() -> Self {
+        Self::detect()
+    }
+}
+
+impl std::fmt::Debug for Clipboard {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Clipboard")
+            .field("providers", &self.provider_names())
+            .field("fallback", &self.fallback)
+            .finish()
+    }
+}
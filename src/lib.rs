@@ -28,6 +28,7 @@
 //! | `menu` | Yes | Menu bar component |
 //! | `statusbar` | Yes | Status bar components |
 //! | `hotkey` | Yes | Hotkey footer and modal |
+//! | `clipboard` | Yes | Clipboard with system, OSC 52 and in-memory providers |
 //! | `terminal` | No | Terminal emulator (TermTui) |
 //! | `fuzzy` | No | Fuzzy finder component |
 //! | `master-layout` | No | Full application layout framework |
@@ -105,6 +106,10 @@ pub mod hotkey_footer;
 #[cfg_attr(docsrs, doc(cfg(feature = "hotkey")))]
 pub mod hotkey_modal;
 
+#[cfg(feature = "clipboard")]
+#[cfg_attr(docsrs, doc(cfg(feature = "clipboard")))]
+pub mod clipboard;
+
 #[cfg(feature = "markdown")]
 #[cfg_attr(docsrs, doc(cfg(feature = "markdown")))]
 pub mod markdown_renderer;
@@ -125,6 +130,9 @@ pub mod file_system_tree;
 #[cfg_attr(docsrs, doc(cfg(feature = "master-layout")))]
 pub mod master_layout;
 
+#[cfg(all(test, feature = "clipboard"))]
+mod test_utils;
+
 // Re-export commonly used types - always available
 pub use button::render_title_with_buttons::render_title_with_buttons;
 pub use button::Button;
@@ -171,6 +179,9 @@ pub use hotkey_footer::{HotkeyFooter, HotkeyFooterBuilder, HotkeyItem};
 #[cfg(feature = "hotkey")]
 pub use hotkey_modal::{functions::render_hotkey_modal, Hotkey, HotkeyModalConfig, HotkeySection};
 
+#[cfg(feature = "clipboard")]
+pub use clipboard::{Clipboard, ClipboardFallback, ClipboardProvider};
+
 #[cfg(feature = "markdown")]
 pub use markdown_renderer::{
     copy_selection_to_clipboard, handle_mouse_event, handle_mouse_event_with_double_click,
@@ -3,6 +3,7 @@
 use ratatui::layout::Rect;
 use ratatui::text::Line;
 
+use crate::clipboard::Clipboard;
 use crate::markdown_renderer::scroll_manager::MarkdownScrollManager;
 
 use super::helpers::is_in_area;
@@ -110,12 +111,8 @@ pub fn handle_mouse_event_with_selection(
                 selection.frozen_width = width;
                 // Auto-copy to clipboard
                 if let Some(text) = selection.get_selected_text() {
-                    if !text.is_empty() {
-                        if let Ok(mut clipboard) = arboard::Clipboard::new() {
-                            if clipboard.set_text(&text).is_ok() {
-                                return SelectionMouseResult::copied();
-                            }
-                        }
+                    if !text.is_empty() && Clipboard::global().set_text(&text).is_ok() {
+                        return SelectionMouseResult::copied();
                     }
                 }
             }
@@ -133,7 +130,7 @@ pub fn handle_mouse_event_with_selection(
     }
 }
 
-/// Copy selected text to clipboard.
+/// Copy selected text to the global clipboard (see `Clipboard::global`).
 ///
 /// # Arguments
 ///
@@ -144,12 +141,8 @@ pub fn handle_mouse_event_with_selection(
 /// `true` if text was copied successfully.
 pub fn copy_selection_to_clipboard(selection: &SelectionState) -> bool {
     if let Some(text) = selection.get_selected_text() {
-        if !text.is_empty() {
-            if let Ok(mut clipboard) = arboard::Clipboard::new() {
-                if clipboard.set_text(&text).is_ok() {
-                    return true;
-                }
-            }
+        if !text.is_empty() && Clipboard::global().set_text(&text).is_ok() {
+            return true;
         }
     }
     false
@@ -2,6 +2,8 @@
 
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 
+use crate::clipboard::Clipboard;
+
 use super::super::super::markdown_event::MarkdownEvent;
 use super::super::{MarkdownWidget, MarkdownWidgetMode};
 
@@ -31,14 +33,10 @@ impl<'a> MarkdownWidget<'a> {
         // Copy selection with 'y' (vim-style)
         if key.code == KeyCode::Char('y') && self.selection.has_selection() {
             if let Some(text) = self.selection.get_selected_text() {
-                if !text.is_empty() {
-                    if let Ok(mut clipboard) = arboard::Clipboard::new() {
-                        if clipboard.set_text(&text).is_ok() {
-                            self.selection.exit();
-                            self.mode = MarkdownWidgetMode::Normal;
-                            return MarkdownEvent::Copied { text };
-                        }
-                    }
+                if !text.is_empty() && Clipboard::global().set_text(&text).is_ok() {
+                    self.selection.exit();
+                    self.mode = MarkdownWidgetMode::Normal;
+                    return MarkdownEvent::Copied { text };
                 }
             }
         }
@@ -49,14 +47,10 @@ impl<'a> MarkdownWidget<'a> {
             && key.modifiers.contains(KeyModifiers::SHIFT)
         {
             if let Some(text) = self.selection.get_selected_text() {
-                if !text.is_empty() {
-                    if let Ok(mut clipboard) = arboard::Clipboard::new() {
-                        if clipboard.set_text(&text).is_ok() {
-                            self.selection.exit();
-                            self.mode = MarkdownWidgetMode::Normal;
-                            return MarkdownEvent::Copied { text };
-                        }
-                    }
+                if !text.is_empty() && Clipboard::global().set_text(&text).is_ok() {
+                    self.selection.exit();
+                    self.mode = MarkdownWidgetMode::Normal;
+                    return MarkdownEvent::Copied { text };
                 }
             }
         }
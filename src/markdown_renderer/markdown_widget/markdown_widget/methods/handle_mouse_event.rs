@@ -3,6 +3,7 @@
 use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
 use ratatui::layout::Rect;
 
+use crate::clipboard::Clipboard;
 use crate::markdown_renderer::render_styled_line;
 
 use super::super::super::helpers::{is_in_area, should_render_line};
@@ -104,14 +105,10 @@ impl<'a> MarkdownWidget<'a> {
 
                     // Auto-copy to clipboard
                     if let Some(text) = self.selection.get_selected_text() {
-                        if !text.is_empty() {
-                            if let Ok(mut clipboard) = arboard::Clipboard::new() {
-                                if clipboard.set_text(&text).is_ok() {
-                                    return MarkdownEvent::Copied {
-                                        text: text.clone(),
-                                    };
-                                }
-                            }
+                        if !text.is_empty() && Clipboard::global().set_text(&text).is_ok() {
+                            return MarkdownEvent::Copied {
+                                text: text.clone(),
+                            };
                         }
                     }
                 }
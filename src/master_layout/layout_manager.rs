@@ -365,6 +365,7 @@ impl MasterLayout {
         match event {
             Event::Key(key) => self.handle_key_event(key),
             Event::Mouse(mouse) => self.handle_mouse_event(mouse),
+            Event::Paste(text) => self.handle_paste_event(&text),
             Event::Resize(_, _) => EventResult::Consumed,
             _ => EventResult::NotHandled,
         }
@@ -452,6 +453,38 @@ impl MasterLayout {
         }
     }
 
+    /// Handle paste events
+    ///
+    /// Pastes go to the focused pane, or to the selected pane when auto-focus
+    /// routes input to it directly. Layout Mode otherwise ignores pastes.
+    fn handle_paste_event(&mut self, text: &str) -> EventResult {
+        let target = match self.mode.clone() {
+            InteractionMode::Focus { focused_pane } => Some(focused_pane),
+            InteractionMode::Layout { selected_pane } if self.auto_focus => {
+                selected_pane.filter(|&pane_id| {
+                    !self
+                        .active_tab()
+                        .and_then(|tab| tab.pane_container().get_pane(pane_id))
+                        .map(|pane| pane.requires_focus_mode())
+                        .unwrap_or(false)
+                })
+            }
+            InteractionMode::Layout { .. } => None,
+        };
+
+        if let Some(pane_id) = target {
+            if let Some(tab) = self.active_tab_mut() {
+                if let Some(pane) = tab.pane_container_mut().get_pane_mut(pane_id) {
+                    if pane.handle_paste(text) {
+                        return EventResult::Consumed;
+                    }
+                }
+            }
+        }
+
+        EventResult::NotHandled
+    }
+
     /// Handle keyboard events in Layout Mode
     fn handle_layout_mode_key(&mut self, key: KeyEvent) -> EventResult {
         // Clear selection (Esc)
@@ -761,15 +794,24 @@ impl MasterLayout {
         let nav_area = chunks[0];
         let tab_area = chunks[1];
 
-        // Render navigation bar as menu bar with offset
-        self.nav_bar
-            .render_with_offset(frame, nav_area, self.nav_bar_offset);
-
-        // Render active tab
+        // Render active tab first, so badges cleared by viewing its panes
+        // are already gone from the nav bar
         let mode = self.mode.clone();
         if let Some(tab) = self.active_tab_mut() {
             tab.render(frame, tab_area, &mode);
         }
+
+        // Show pane badges next to tab names
+        for (item, tab) in self.nav_bar.items.iter_mut().zip(&self.tabs) {
+            item.name = match tab.badge() {
+                Some(badge) => format!("{} {}", tab.name(), badge),
+                None => tab.name().to_string(),
+            };
+        }
+
+        // Render navigation bar as menu bar with offset
+        self.nav_bar
+            .render_with_offset(frame, nav_area, self.nav_bar_offset);
     }
 }
 
@@ -844,6 +886,10 @@ mod tests {
         fn requires_focus_mode(&self) -> bool {
             self.requires_focus
         }
+
+        fn handle_paste(&mut self, _text: &str) -> bool {
+            true
+        }
     }
 
     fn create_test_layout() -> MasterLayout {
@@ -2426,4 +2472,92 @@ mod tests {
 
         assert_eq!(result, EventResult::Quit);
     }
+
+    #[test]
+    fn test_paste_ignored_in_layout_mode() {
+        let mut layout = create_test_layout();
+
+        let result = layout.handle_event(Event::Paste("ls\n".to_string()));
+        assert_eq!(result, EventResult::NotHandled);
+    }
+
+    #[test]
+    fn test_paste_routes_to_focused_pane() {
+        let mut layout = create_test_layout();
+        let pane_id = layout
+            .active_tab()
+            .unwrap()
+            .pane_container()
+            .get_pane_by_index(0)
+            .unwrap()
+            .id();
+        layout.enter_focus_mode(pane_id);
+
+        let result = layout.handle_event(Event::Paste("ls\n".to_string()));
+        assert_eq!(result, EventResult::Consumed);
+        assert!(layout.mode().is_focus());
+    }
+
+    #[test]
+    fn test_paste_with_auto_focus() {
+        let (layout, chat_id, tree_id) = create_mixed_focus_layout();
+        let mut layout = layout.with_auto_focus(true);
+
+        // Chat requires focus mode, so a paste while merely selected is ignored
+        assert_eq!(layout.mode().selected_pane(), Some(chat_id));
+        let result = layout.handle_event(Event::Paste("hello".to_string()));
+        assert_eq!(result, EventResult::NotHandled);
+
+        // Tree auto-passthroughs, so the paste goes straight to it
+        layout.select_next_pane();
+        assert_eq!(layout.mode().selected_pane(), Some(tree_id));
+        let result = layout.handle_event(Event::Paste("hello".to_string()));
+        assert_eq!(result, EventResult::Consumed);
+    }
+
+    struct BadgeContent;
+
+    impl Widget for BadgeContent {
+        fn render(self, _area: Rect, _buf: &mut Buffer) {}
+    }
+
+    impl PaneContent for BadgeContent {
+        fn handle_key(&mut self, _key: KeyEvent) -> bool {
+            false
+        }
+
+        fn handle_mouse(&mut self, _mouse: MouseEvent) -> bool {
+            false
+        }
+
+        fn title(&self) -> String {
+            "Build".to_string()
+        }
+
+        fn render_content(&mut self, _area: Rect, _frame: &mut ratatui::Frame) {}
+
+        fn badge(&self) -> Option<String> {
+            Some("!".to_string())
+        }
+    }
+
+    #[test]
+    fn test_nav_bar_shows_tab_badges() {
+        let mut layout = MasterLayout::new();
+        layout.add_tab(Tab::new("Main"));
+        let mut tab = Tab::new("Jobs");
+        tab.add_pane(Pane::new(PaneId::new("build"), Box::new(BadgeContent)));
+        layout.add_tab(tab);
+
+        let backend = TestBackend::new(80, 24);
+        let mut terminal = Terminal::new(backend).unwrap();
+        terminal
+            .draw(|frame| {
+                layout.render(frame);
+            })
+            .unwrap();
+
+        assert_eq!(layout.nav_bar.items[0].name, "Main");
+        assert_eq!(layout.nav_bar.items[1].name, "Jobs !");
+    }
 }
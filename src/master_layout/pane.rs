@@ -34,6 +34,12 @@ pub trait PaneContent {
         true
     }
 
+    /// Handle pasted text (crossterm `Event::Paste`) when pane is focused
+    /// Returns true if the paste was consumed, false otherwise
+    fn handle_paste(&mut self, _text: &str) -> bool {
+        false // Default: pane doesn't accept pastes
+    }
+
     /// Start text selection at the given coordinates (relative to content area)
     fn start_selection(&mut self, _x: u16, _y: u16) {
         // Default: no-op (pane doesn't support selection)
@@ -102,6 +108,14 @@ pub trait PaneContent {
         "●"
     }
 
+    /// Badge shown after the title, e.g. alert flags from a terminal
+    ///
+    /// Tabs show the badges of their panes in the navigation bar.
+    /// Default: no badge
+    fn badge(&self) -> Option<String> {
+        None
+    }
+
     /// Get custom border style for this pane
     /// Default implementation uses standard focus/selection colors
     fn border_style(&self, is_selected: bool, is_focused: bool) -> Style {
@@ -227,6 +241,11 @@ impl Pane {
         self.content.title()
     }
 
+    /// Get the pane's badge, if any
+    pub fn badge(&self) -> Option<String> {
+        self.content.badge()
+    }
+
     /// Check if pane is focusable
     pub fn is_focusable(&self) -> bool {
         self.content.is_focusable()
@@ -251,6 +270,11 @@ impl Pane {
         self.content.handle_mouse(mouse)
     }
 
+    /// Handle pasted text
+    pub fn handle_paste(&mut self, text: &str) -> bool {
+        self.content.handle_paste(text)
+    }
+
     /// Start text selection at the given coordinates
     pub fn start_selection(&mut self, x: u16, y: u16) {
         self.content.start_selection(x, y);
@@ -304,11 +328,11 @@ impl Pane {
             && y < self.area.y + self.area.height
     }
 
-    /// Build the title line with optional icon
+    /// Build the title line with optional icon and badge
     fn build_title(&self, is_selected: bool, is_focused: bool) -> Line<'static> {
         let base_title = self.content.title_with_indicator(is_selected, is_focused);
 
-        if let Some(ref icon) = self.icon {
+        let mut title = if let Some(ref icon) = self.icon {
             let icon_str = icon.clone();
             let title_str = base_title;
             Line::from(vec![
@@ -320,7 +344,18 @@ impl Pane {
             ])
         } else {
             Line::from(format!(" {} ", base_title))
+        };
+
+        if let Some(badge) = self.content.badge() {
+            title.push_span(Span::styled(
+                badge,
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            ));
+            title.push_span(Span::raw(" "));
         }
+        title
     }
 
     /// Get the padded area (after applying padding)
@@ -417,6 +452,7 @@ mod tests {
         requires_focus: bool,
         last_key: Option<KeyEvent>,
         last_mouse: Option<MouseEvent>,
+        badge: Option<String>,
     }
 
     impl MockPaneContent {
@@ -428,6 +464,7 @@ mod tests {
                 requires_focus: false,
                 last_key: None,
                 last_mouse: None,
+                badge: None,
             }
         }
 
@@ -439,6 +476,7 @@ mod tests {
                 requires_focus: false,
                 last_key: None,
                 last_mouse: None,
+                badge: None,
             }
         }
 
@@ -450,6 +488,7 @@ mod tests {
                 requires_focus: true,
                 last_key: None,
                 last_mouse: None,
+                badge: None,
             }
         }
     }
@@ -490,6 +529,10 @@ mod tests {
         fn requires_focus_mode(&self) -> bool {
             self.requires_focus
         }
+
+        fn badge(&self) -> Option<String> {
+            self.badge.clone()
+        }
     }
 
     #[test]
@@ -790,6 +833,24 @@ mod tests {
         assert!(!title_text.contains("📁"));
     }
 
+    #[test]
+    fn test_build_title_with_badge() {
+        let mut content = MockPaneContent::new("Build");
+        content.badge = Some("!".to_string());
+        let pane = Pane::new(PaneId::new("build"), Box::new(content));
+
+        let title = pane.build_title(false, false);
+        let title_text = title
+            .spans
+            .iter()
+            .map(|span| span.content.as_ref())
+            .collect::<String>();
+
+        assert_eq!(title_text, " Build ! ");
+        assert_eq!(pane.badge().as_deref(), Some("!"));
+        assert_eq!(title.spans[1].style.fg, Some(Color::Yellow));
+    }
+
     #[test]
     fn test_pane_set_focused() {
         let pane_id = PaneId::new("test");
@@ -80,6 +80,25 @@ impl Tab {
         self.pane_container.pane_count()
     }
 
+    /// Get the combined badges of the panes in this tab
+    ///
+    /// Each badge character appears once, in the order first seen.
+    pub fn badge(&self) -> Option<String> {
+        let mut badge = String::new();
+        for index in 0..self.pane_container.pane_count() {
+            let pane_badge = self
+                .pane_container
+                .get_pane_by_index(index)
+                .and_then(|pane| pane.badge());
+            for c in pane_badge.unwrap_or_default().chars() {
+                if !badge.contains(c) {
+                    badge.push(c);
+                }
+            }
+        }
+        (!badge.is_empty()).then_some(badge)
+    }
+
     /// Render the tab (panes + footer)
     pub fn render(&mut self, frame: &mut ratatui::Frame, area: Rect, mode: &InteractionMode) {
         // Calculate layout: panes take most space, footer takes 1 row
@@ -116,14 +135,21 @@ mod tests {
     // Mock PaneContent for testing
     struct MockContent {
         title: String,
+        badge: Option<String>,
     }
 
     impl MockContent {
         fn new(title: &str) -> Self {
             Self {
                 title: title.to_string(),
+                badge: None,
             }
         }
+
+        fn with_badge(mut self, badge: &str) -> Self {
+            self.badge = Some(badge.to_string());
+            self
+        }
     }
 
     impl Widget for MockContent {
@@ -143,6 +169,9 @@ mod tests {
         fn render_content(&mut self, _area: Rect, _frame: &mut ratatui::Frame) {
             // Mock implementation - do nothing
         }
+        fn badge(&self) -> Option<String> {
+            self.badge.clone()
+        }
     }
 
     #[test]
@@ -216,6 +245,27 @@ mod tests {
         assert_eq!(tab.pane_count(), 2);
     }
 
+    #[test]
+    fn test_badge_merges_panes() {
+        let mut tab = Tab::new("Test Tab");
+        assert_eq!(tab.badge(), None);
+
+        tab.add_pane(Pane::new(
+            PaneId::new("p1"),
+            Box::new(MockContent::new("Pane 1").with_badge("!#")),
+        ));
+        tab.add_pane(Pane::new(
+            PaneId::new("p2"),
+            Box::new(MockContent::new("Pane 2")),
+        ));
+        tab.add_pane(Pane::new(
+            PaneId::new("p3"),
+            Box::new(MockContent::new("Pane 3").with_badge("~!")),
+        ));
+
+        assert_eq!(tab.badge().as_deref(), Some("!#~"));
+    }
+
     #[test]
     fn test_footer_access() {
         let mut tab = Tab::new("Test Tab");
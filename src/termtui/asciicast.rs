@@ -0,0 +1,624 @@
+//! Asciicast recording and playback
+//!
+//! Recordings are written as [asciicast v2](https://docs.asciinema.org/manual/asciicast/v2/):
+//! a JSON header line followed by one `[time, code, data]` line per event.
+//! Playback also accepts v3 files, whose event times are intervals.
+
+use crate::termtui::parser::Parser;
+use anyhow::{anyhow, bail, Context, Result};
+use serde_json::{json, Value};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Kind of a recorded event
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AsciicastEventKind {
+    /// Output written by the program (`o`)
+    Output,
+    /// Input sent to the program (`i`)
+    Input,
+    /// Terminal resize to `COLSxROWS` (`r`)
+    Resize,
+    /// Marker with an optional label (`m`)
+    Marker,
+}
+
+impl AsciicastEventKind {
+    /// Get the event code used in the file
+    pub fn code(self) -> &'static str {
+        match self {
+            AsciicastEventKind::Output => "o",
+            AsciicastEventKind::Input => "i",
+            AsciicastEventKind::Resize => "r",
+            AsciicastEventKind::Marker => "m",
+        }
+    }
+
+    /// Parse an event code, or `None` for codes playback does not use
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code {
+            "o" => Some(AsciicastEventKind::Output),
+            "i" => Some(AsciicastEventKind::Input),
+            "r" => Some(AsciicastEventKind::Resize),
+            "m" => Some(AsciicastEventKind::Marker),
+            _ => None,
+        }
+    }
+}
+
+/// A recorded event
+#[derive(Clone, Debug, PartialEq)]
+pub struct AsciicastEvent {
+    /// Time since the start of the recording
+    pub time: Duration,
+    /// Event kind
+    pub kind: AsciicastEventKind,
+    /// Event data (output text, `COLSxROWS`, marker label)
+    pub data: String,
+}
+
+impl AsciicastEvent {
+    /// Parse the size of a resize event
+    pub fn resize_size(&self) -> Option<(u16, u16)> {
+        if self.kind != AsciicastEventKind::Resize {
+            return None;
+        }
+        let (cols, rows) = self.data.split_once('x')?;
+        Some((cols.parse().ok()?, rows.parse().ok()?))
+    }
+}
+
+/// A parsed recording
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AsciicastRecording {
+    /// Initial terminal width
+    pub width: u16,
+    /// Initial terminal height
+    pub height: u16,
+    /// Recording title from the header
+    pub title: Option<String>,
+    /// Events in time order
+    pub events: Vec<AsciicastEvent>,
+}
+
+impl AsciicastRecording {
+    /// Parse a recording from the contents of a `.cast` file
+    pub fn parse(contents: &str) -> Result<Self> {
+        let mut lines = contents
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty());
+
+        let (_, header) = lines.next().ok_or_else(|| anyhow!("empty recording"))?;
+        let header: Value = serde_json::from_str(header).context("invalid header")?;
+        let version = header["version"].as_u64().unwrap_or(0);
+        let (width, height) = match version {
+            2 => (&header["width"], &header["height"]),
+            3 => (&header["term"]["cols"], &header["term"]["rows"]),
+            _ => bail!("unsupported asciicast version {}", version),
+        };
+        let dimension = |value: &Value| -> Result<u16> {
+            value
+                .as_u64()
+                .and_then(|n| u16::try_from(n).ok())
+                .ok_or_else(|| anyhow!("invalid terminal size in header"))
+        };
+
+        let mut recording = Self {
+            width: dimension(width)?,
+            height: dimension(height)?,
+            title: header["title"].as_str().map(str::to_string),
+            events: Vec::new(),
+        };
+
+        let mut time = 0.0;
+        for (idx, line) in lines {
+            // v3 lines may be comments
+            if line.starts_with('#') {
+                continue;
+            }
+            let event: Value = serde_json::from_str(line)
+                .with_context(|| format!("invalid event on line {}", idx + 1))?;
+            let (Some(at), Some(code), Some(data)) =
+                (event[0].as_f64(), event[1].as_str(), event[2].as_str())
+            else {
+                bail!("invalid event on line {}", idx + 1);
+            };
+
+            // v2 times are absolute, v3 times are intervals
+            time = if version == 3 { time + at } else { at };
+            let Ok(event_time) = Duration::try_from_secs_f64(time.max(0.0)) else {
+                bail!("invalid event time on line {}", idx + 1);
+            };
+            if let Some(kind) = AsciicastEventKind::from_code(code) {
+                recording.events.push(AsciicastEvent {
+                    time: event_time,
+                    kind,
+                    data: data.to_string(),
+                });
+            }
+        }
+
+        Ok(recording)
+    }
+
+    /// Load a recording from a `.cast` file
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        Self::parse(&contents)
+    }
+
+    /// Get the time of the last event
+    pub fn duration(&self) -> Duration {
+        self.events
+            .last()
+            .map(|event| event.time)
+            .unwrap_or_default()
+    }
+}
+
+/// Writes terminal output and resizes to an asciicast v2 stream
+pub struct AsciicastRecorder {
+    writer: Box<dyn Write + Send>,
+    start: Instant,
+    /// Trailing bytes of an incomplete UTF-8 sequence
+    pending: Vec<u8>,
+}
+
+impl AsciicastRecorder {
+    /// Start a recording of a `cols`x`rows` terminal, writing the header
+    ///
+    /// `term` is the child's `TERM`, recorded in the header's `env` when known.
+    pub fn new(
+        writer: impl Write + Send + 'static,
+        cols: u16,
+        rows: u16,
+        term: Option<&str>,
+    ) -> Result<Self> {
+        let mut writer: Box<dyn Write + Send> = Box::new(writer);
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default();
+        let mut header = json!({
+            "version": 2,
+            "width": cols,
+            "height": rows,
+            "timestamp": timestamp,
+        });
+        if let Some(term) = term {
+            header["env"] = json!({ "TERM": term });
+        }
+        writeln!(writer, "{}", header)?;
+        writer.flush()?;
+
+        Ok(Self {
+            writer,
+            start: Instant::now(),
+            pending: Vec::new(),
+        })
+    }
+
+    /// Start a recording into a file, replacing it if it exists
+    pub fn create(
+        path: impl AsRef<Path>,
+        cols: u16,
+        rows: u16,
+        term: Option<&str>,
+    ) -> Result<Self> {
+        let path = path.as_ref();
+        let file =
+            File::create(path).with_context(|| format!("failed to create {}", path.display()))?;
+        Self::new(BufWriter::new(file), cols, rows, term)
+    }
+
+    /// Get the time since the recording started
+    pub fn elapsed(&self) -> Duration {
+        self.start.elapsed()
+    }
+
+    /// Record program output
+    ///
+    /// A UTF-8 sequence split across calls is held back until it completes.
+    pub fn output(&mut self, bytes: &[u8]) -> Result<()> {
+        self.pending.extend_from_slice(bytes);
+        let (text, rest) = split_utf8(&self.pending);
+        self.pending = rest;
+        if text.is_empty() {
+            return Ok(());
+        }
+        self.write_event(AsciicastEventKind::Output, &text)
+    }
+
+    /// Record a terminal resize
+    pub fn resize(&mut self, cols: u16, rows: u16) -> Result<()> {
+        self.write_event(AsciicastEventKind::Resize, &format!("{}x{}", cols, rows))
+    }
+
+    /// Record a marker (shown as a chapter by asciinema players)
+    pub fn marker(&mut self, label: &str) -> Result<()> {
+        self.write_event(AsciicastEventKind::Marker, label)
+    }
+
+    /// Flush buffered events
+    pub fn flush(&mut self) -> Result<()> {
+        self.writer.flush()?;
+        Ok(())
+    }
+
+    fn write_event(&mut self, kind: AsciicastEventKind, data: &str) -> Result<()> {
+        let time = (self.elapsed().as_secs_f64() * 1e6).round() / 1e6;
+        writeln!(self.writer, "{}", json!([time, kind.code(), data]))?;
+        // Keep the file usable if the application exits without stopping
+        self.writer.flush()?;
+        Ok(())
+    }
+}
+
+impl Drop for AsciicastRecorder {
+    fn drop(&mut self) {
+        let _ = self.writer.flush();
+    }
+}
+
+/// Decode UTF-8, returning the text and an incomplete trailing sequence
+///
+/// Invalid bytes are replaced with U+FFFD.
+fn split_utf8(bytes: &[u8]) -> (String, Vec<u8>) {
+    let mut text = String::new();
+    let mut rest = bytes;
+    loop {
+        match std::str::from_utf8(rest) {
+            Ok(valid) => {
+                text.push_str(valid);
+                return (text, Vec::new());
+            }
+            Err(e) => {
+                let (valid, after) = rest.split_at(e.valid_up_to());
+                // Checked by `valid_up_to`
+                text.push_str(std::str::from_utf8(valid).unwrap_or_default());
+                match e.error_len() {
+                    Some(len) => {
+                        text.push(char::REPLACEMENT_CHARACTER);
+                        rest = &after[len..];
+                    }
+                    None => return (text, after.to_vec()),
+                }
+            }
+        }
+    }
+}
+
+/// Plays a recording into a `Parser`
+///
+/// The player only tracks time: feed it elapsed time with `advance` and it
+/// applies every event up to the new position.
+#[derive(Clone, Debug)]
+pub struct AsciicastPlayer {
+    recording: AsciicastRecording,
+    position: Duration,
+    /// Index of the first event not yet applied
+    next: usize,
+    speed: f64,
+    paused: bool,
+    scrollback: usize,
+}
+
+impl AsciicastPlayer {
+    /// Create a player positioned at the start of the recording
+    pub fn new(recording: AsciicastRecording) -> Self {
+        Self {
+            recording,
+            position: Duration::ZERO,
+            next: 0,
+            speed: 1.0,
+            paused: false,
+            scrollback: 10000,
+        }
+    }
+
+    /// Set the scrollback kept by `new_parser` and seeking (builder pattern)
+    pub fn with_scrollback(mut self, scrollback: usize) -> Self {
+        self.scrollback = scrollback;
+        self
+    }
+
+    /// Create a parser sized for the start of the recording
+    pub fn new_parser(&self) -> Parser {
+        Parser::new(
+            self.recording.height.max(1) as usize,
+            self.recording.width.max(1) as usize,
+            self.scrollback,
+        )
+    }
+
+    /// Get the recording being played
+    pub fn recording(&self) -> &AsciicastRecording {
+        &self.recording
+    }
+
+    /// Get the current playback position
+    pub fn position(&self) -> Duration {
+        self.position
+    }
+
+    /// Get the length of the recording
+    pub fn duration(&self) -> Duration {
+        self.recording.duration()
+    }
+
+    /// Check if every event has been played
+    pub fn is_finished(&self) -> bool {
+        self.next >= self.recording.events.len()
+    }
+
+    /// Check if playback is paused
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Resume playback
+    pub fn play(&mut self) {
+        self.paused = false;
+    }
+
+    /// Pause playback
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    /// Toggle between playing and paused
+    pub fn toggle_pause(&mut self) {
+        self.paused = !self.paused;
+    }
+
+    /// Get the playback speed multiplier
+    pub fn speed(&self) -> f64 {
+        self.speed
+    }
+
+    /// Set the playback speed multiplier (e.g. `2.0` for double speed)
+    pub fn set_speed(&mut self, speed: f64) {
+        if speed.is_finite() && speed > 0.0 {
+            self.speed = speed;
+        }
+    }
+
+    /// Advance by `elapsed` wall-clock time, scaled by the speed
+    ///
+    /// Does nothing while paused.
+    pub fn advance(&mut self, elapsed: Duration, parser: &mut Parser) {
+        if self.paused {
+            return;
+        }
+        let position = self.position + elapsed.mul_f64(self.speed);
+        self.position = position.min(self.duration());
+        self.apply_events(parser);
+    }
+
+    /// Jump to `position`
+    ///
+    /// Seeking backwards resets the parser and replays from the start.
+    pub fn seek(&mut self, position: Duration, parser: &mut Parser) {
+        let position = position.min(self.duration());
+        if position < self.position {
+            let palette = parser.screen().palette().clone();
+            *parser = self.new_parser();
+            parser.set_palette(palette);
+            self.next = 0;
+        }
+        self.position = position;
+        self.apply_events(parser);
+    }
+
+    /// Apply the events at or before the current position
+    fn apply_events(&mut self, parser: &mut Parser) {
+        while let Some(event) = self.recording.events.get(self.next) {
+            if event.time > self.position {
+                break;
+            }
+            match event.kind {
+                AsciicastEventKind::Output => parser.process(event.data.as_bytes()),
+                AsciicastEventKind::Resize => {
+                    if let Some((cols, rows)) = event.resize_size() {
+                        if cols > 0 && rows > 0 {
+                            parser.resize(rows as usize, cols as usize);
+                        }
+                    }
+                }
+                AsciicastEventKind::Input | AsciicastEventKind::Marker => {}
+            }
+            self.next += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::SharedBuf;
+
+    const CAST: &str = r#"{"version": 2, "width": 10, "height": 3, "title": "demo"}
+[0.5, "o", "hello"]
+[1.0, "i", "x"]
+[1.5, "r", "20x4"]
+[2.0, "o", "\r\nworld"]
+"#;
+
+    fn first_line(parser: &Parser) -> String {
+        parser
+            .screen()
+            .contents()
+            .lines()
+            .next()
+            .unwrap_or("")
+            .to_string()
+    }
+
+    #[test]
+    fn test_parse_v2() {
+        let recording = AsciicastRecording::parse(CAST).unwrap();
+        assert_eq!((recording.width, recording.height), (10, 3));
+        assert_eq!(recording.title.as_deref(), Some("demo"));
+        assert_eq!(recording.events.len(), 4);
+        assert_eq!(recording.events[2].resize_size(), Some((20, 4)));
+        assert_eq!(recording.duration(), Duration::from_secs(2));
+    }
+
+    #[test]
+    fn test_parse_v3_intervals() {
+        let cast = "{\"version\": 3, \"term\": {\"cols\": 8, \"rows\": 2}}\n\
+                    # comment\n\
+                    [0.5, \"o\", \"a\"]\n\
+                    [0.25, \"o\", \"b\"]\n\
+                    [0.25, \"x\", \"0\"]\n";
+        let recording = AsciicastRecording::parse(cast).unwrap();
+        assert_eq!((recording.width, recording.height), (8, 2));
+        // Exit events are not kept
+        assert_eq!(recording.events.len(), 2);
+        assert_eq!(recording.events[1].time, Duration::from_millis(750));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(AsciicastRecording::parse("").is_err());
+        assert!(AsciicastRecording::parse("{\"version\": 1}").is_err());
+        assert!(AsciicastRecording::parse(
+            "{\"version\": 2, \"width\": 1, \"height\": 1}\n[\"bad\"]"
+        )
+        .is_err());
+
+        // Times too large for a Duration are rejected, not a panic
+        let error = AsciicastRecording::parse(
+            "{\"version\": 2, \"width\": 1, \"height\": 1}\n[1e30, \"o\", \"x\"]",
+        )
+        .unwrap_err();
+        assert_eq!(error.to_string(), "invalid event time on line 2");
+        // v3 intervals can overflow when summed
+        let error = AsciicastRecording::parse(
+            "{\"version\": 3, \"term\": {\"cols\": 1, \"rows\": 1}}\n\
+             [1e19, \"o\", \"x\"]\n[1e19, \"o\", \"x\"]",
+        )
+        .unwrap_err();
+        assert_eq!(error.to_string(), "invalid event time on line 3");
+    }
+
+    #[test]
+    fn test_recorder_round_trip() {
+        let buf = SharedBuf::default();
+        let mut recorder = AsciicastRecorder::new(buf.clone(), 80, 24, Some("xterm")).unwrap();
+        recorder.output(b"\x1b[31mred\x1b[0m").unwrap();
+        // "é" split across two reads
+        recorder.output(b"caf\xc3").unwrap();
+        recorder.output(b"\xa9").unwrap();
+        recorder.resize(100, 30).unwrap();
+        recorder.marker("done").unwrap();
+        drop(recorder);
+
+        let contents = String::from_utf8(buf.bytes()).unwrap();
+        let header: Value = serde_json::from_str(contents.lines().next().unwrap()).unwrap();
+        assert_eq!(header["env"], json!({ "TERM": "xterm" }));
+        let recording = AsciicastRecording::parse(&contents).unwrap();
+        assert_eq!((recording.width, recording.height), (80, 24));
+
+        let events: Vec<_> = recording
+            .events
+            .iter()
+            .map(|event| (event.kind, event.data.as_str()))
+            .collect();
+        assert_eq!(
+            events,
+            [
+                (AsciicastEventKind::Output, "\x1b[31mred\x1b[0m"),
+                (AsciicastEventKind::Output, "caf"),
+                (AsciicastEventKind::Output, "é"),
+                (AsciicastEventKind::Resize, "100x30"),
+                (AsciicastEventKind::Marker, "done"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_recorder_without_term_has_no_env() {
+        let buf = SharedBuf::default();
+        drop(AsciicastRecorder::new(buf.clone(), 80, 24, None).unwrap());
+
+        let header: Value = serde_json::from_slice(&buf.bytes()).unwrap();
+        assert!(header.get("env").is_none());
+        assert_eq!(header["width"], 80);
+    }
+
+    #[test]
+    fn test_split_utf8() {
+        assert_eq!(split_utf8(b"abc"), ("abc".to_string(), Vec::new()));
+        assert_eq!(
+            split_utf8(b"a\xe4\xb8"),
+            ("a".to_string(), vec![0xe4, 0xb8])
+        );
+        assert_eq!(
+            split_utf8(b"a\xffb"),
+            ("a\u{fffd}b".to_string(), Vec::new())
+        );
+    }
+
+    #[test]
+    fn test_player_advance_and_speed() {
+        let mut player = AsciicastPlayer::new(AsciicastRecording::parse(CAST).unwrap());
+        let mut parser = player.new_parser();
+
+        player.advance(Duration::from_millis(400), &mut parser);
+        assert_eq!(first_line(&parser), "");
+
+        player.advance(Duration::from_millis(100), &mut parser);
+        assert_eq!(first_line(&parser), "hello");
+
+        player.set_speed(2.0);
+        player.advance(Duration::from_millis(500), &mut parser);
+        assert_eq!(player.position(), Duration::from_millis(1500));
+        assert_eq!(parser.size().cols, 20);
+        assert_eq!(parser.size().rows, 4);
+        assert!(!player.is_finished());
+
+        player.advance(Duration::from_secs(10), &mut parser);
+        assert!(player.is_finished());
+        assert_eq!(player.position(), player.duration());
+        assert!(parser.screen().contents().contains("world"));
+    }
+
+    #[test]
+    fn test_player_pause() {
+        let mut player = AsciicastPlayer::new(AsciicastRecording::parse(CAST).unwrap());
+        let mut parser = player.new_parser();
+
+        player.pause();
+        player.advance(Duration::from_secs(1), &mut parser);
+        assert_eq!(player.position(), Duration::ZERO);
+
+        player.toggle_pause();
+        player.advance(Duration::from_secs(1), &mut parser);
+        assert_eq!(first_line(&parser), "hello");
+
+        player.set_speed(0.0);
+        assert_eq!(player.speed(), 1.0);
+    }
+
+    #[test]
+    fn test_player_seek() {
+        let mut player = AsciicastPlayer::new(AsciicastRecording::parse(CAST).unwrap());
+        let mut parser = player.new_parser();
+
+        player.seek(Duration::from_secs(3), &mut parser);
+        assert!(parser.screen().contents().contains("world"));
+        assert_eq!(parser.size().cols, 20);
+
+        // Seeking back replays from the start
+        player.seek(Duration::from_millis(600), &mut parser);
+        assert_eq!(first_line(&parser), "hello");
+        assert!(!parser.screen().contents().contains("world"));
+        assert_eq!(parser.size().cols, 10);
+    }
+}
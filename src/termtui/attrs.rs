@@ -58,12 +58,48 @@ impl From<ColorSpec> for Color {
     }
 }
 
+/// Underline style (SGR 4, 4:n, 21, 24)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum UnderlineStyle {
+    /// Not underlined
+    #[default]
+    None,
+    /// Single line
+    Single,
+    /// Double line
+    Double,
+    /// Curly line (used for diagnostics)
+    Curly,
+    /// Dotted line
+    Dotted,
+    /// Dashed line
+    Dashed,
+}
+
+impl From<termwiz::cell::Underline> for UnderlineStyle {
+    fn from(underline: termwiz::cell::Underline) -> Self {
+        use termwiz::cell::Underline;
+        match underline {
+            Underline::None => UnderlineStyle::None,
+            Underline::Single => UnderlineStyle::Single,
+            Underline::Double => UnderlineStyle::Double,
+            Underline::Curly => UnderlineStyle::Curly,
+            Underline::Dotted => UnderlineStyle::Dotted,
+            Underline::Dashed => UnderlineStyle::Dashed,
+        }
+    }
+}
+
 // Text mode bit flags
-const TEXT_MODE_BOLD: u8 = 1 << 0;
-const TEXT_MODE_ITALIC: u8 = 1 << 1;
-const TEXT_MODE_UNDERLINE: u8 = 1 << 2;
-const TEXT_MODE_INVERSE: u8 = 1 << 3;
-const TEXT_MODE_STRIKETHROUGH: u8 = 1 << 4;
+const TEXT_MODE_BOLD: u16 = 1 << 0;
+const TEXT_MODE_ITALIC: u16 = 1 << 1;
+const TEXT_MODE_INVERSE: u16 = 1 << 3;
+const TEXT_MODE_STRIKETHROUGH: u16 = 1 << 4;
+const TEXT_MODE_DIM: u16 = 1 << 5;
+const TEXT_MODE_BLINK: u16 = 1 << 6;
+const TEXT_MODE_RAPID_BLINK: u16 = 1 << 7;
+const TEXT_MODE_HIDDEN: u16 = 1 << 8;
+const TEXT_MODE_OVERLINE: u16 = 1 << 9;
 
 /// Terminal cell attributes
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
@@ -72,8 +108,12 @@ pub struct Attrs {
     pub fg: Color,
     /// Background color
     pub bg: Color,
+    /// Underline color (SGR 58); `Default` follows the foreground
+    pub underline_color: Color,
+    /// Underline style
+    underline_style: UnderlineStyle,
     /// Text mode flags
-    mode: u8,
+    mode: u16,
 }
 
 impl Attrs {
@@ -82,6 +122,15 @@ impl Attrs {
         Self::default()
     }
 
+    /// Set or clear a mode flag
+    fn set_mode(&mut self, flag: u16, on: bool) {
+        if on {
+            self.mode |= flag;
+        } else {
+            self.mode &= !flag;
+        }
+    }
+
     /// Check if bold
     pub fn bold(&self) -> bool {
         self.mode & TEXT_MODE_BOLD != 0
@@ -89,11 +138,17 @@ impl Attrs {
 
     /// Set bold
     pub fn set_bold(&mut self, on: bool) {
-        if on {
-            self.mode |= TEXT_MODE_BOLD;
-        } else {
-            self.mode &= !TEXT_MODE_BOLD;
-        }
+        self.set_mode(TEXT_MODE_BOLD, on);
+    }
+
+    /// Check if dim (faint)
+    pub fn dim(&self) -> bool {
+        self.mode & TEXT_MODE_DIM != 0
+    }
+
+    /// Set dim
+    pub fn set_dim(&mut self, on: bool) {
+        self.set_mode(TEXT_MODE_DIM, on);
     }
 
     /// Check if italic
@@ -103,25 +158,71 @@ impl Attrs {
 
     /// Set italic
     pub fn set_italic(&mut self, on: bool) {
-        if on {
-            self.mode |= TEXT_MODE_ITALIC;
-        } else {
-            self.mode &= !TEXT_MODE_ITALIC;
-        }
+        self.set_mode(TEXT_MODE_ITALIC, on);
     }
 
-    /// Check if underline
+    /// Check if underlined (in any style)
     pub fn underline(&self) -> bool {
-        self.mode & TEXT_MODE_UNDERLINE != 0
+        self.underline_style != UnderlineStyle::None
     }
 
-    /// Set underline
+    /// Set a single underline, or remove the underline
     pub fn set_underline(&mut self, on: bool) {
-        if on {
-            self.mode |= TEXT_MODE_UNDERLINE;
+        self.underline_style = if on {
+            UnderlineStyle::Single
         } else {
-            self.mode &= !TEXT_MODE_UNDERLINE;
-        }
+            UnderlineStyle::None
+        };
+    }
+
+    /// Get the underline style
+    pub fn underline_style(&self) -> UnderlineStyle {
+        self.underline_style
+    }
+
+    /// Set the underline style
+    pub fn set_underline_style(&mut self, style: UnderlineStyle) {
+        self.underline_style = style;
+    }
+
+    /// Check if blinking (slow or rapid)
+    pub fn blink(&self) -> bool {
+        self.mode & (TEXT_MODE_BLINK | TEXT_MODE_RAPID_BLINK) != 0
+    }
+
+    /// Set slow blink
+    pub fn set_blink(&mut self, on: bool) {
+        self.set_mode(TEXT_MODE_BLINK, on);
+    }
+
+    /// Check if blinking rapidly
+    pub fn rapid_blink(&self) -> bool {
+        self.mode & TEXT_MODE_RAPID_BLINK != 0
+    }
+
+    /// Set rapid blink
+    pub fn set_rapid_blink(&mut self, on: bool) {
+        self.set_mode(TEXT_MODE_RAPID_BLINK, on);
+    }
+
+    /// Check if hidden (invisible)
+    pub fn hidden(&self) -> bool {
+        self.mode & TEXT_MODE_HIDDEN != 0
+    }
+
+    /// Set hidden
+    pub fn set_hidden(&mut self, on: bool) {
+        self.set_mode(TEXT_MODE_HIDDEN, on);
+    }
+
+    /// Check if overlined
+    pub fn overline(&self) -> bool {
+        self.mode & TEXT_MODE_OVERLINE != 0
+    }
+
+    /// Set overline
+    pub fn set_overline(&mut self, on: bool) {
+        self.set_mode(TEXT_MODE_OVERLINE, on);
     }
 
     /// Check if inverse (swap fg/bg)
@@ -131,11 +232,7 @@ impl Attrs {
 
     /// Set inverse
     pub fn set_inverse(&mut self, on: bool) {
-        if on {
-            self.mode |= TEXT_MODE_INVERSE;
-        } else {
-            self.mode &= !TEXT_MODE_INVERSE;
-        }
+        self.set_mode(TEXT_MODE_INVERSE, on);
     }
 
     /// Check if strikethrough
@@ -145,11 +242,7 @@ impl Attrs {
 
     /// Set strikethrough
     pub fn set_strikethrough(&mut self, on: bool) {
-        if on {
-            self.mode |= TEXT_MODE_STRIKETHROUGH;
-        } else {
-            self.mode &= !TEXT_MODE_STRIKETHROUGH;
-        }
+        self.set_mode(TEXT_MODE_STRIKETHROUGH, on);
     }
 
     /// Reset all attributes to default
@@ -158,6 +251,9 @@ impl Attrs {
     }
 
     /// Convert to ratatui style
+    ///
+    /// Every underline style renders as `UNDERLINED` and overline has no
+    /// ratatui equivalent, so it is only kept on the attributes.
     pub fn to_ratatui(&self) -> Style {
         let mut style = Style::default();
 
@@ -180,11 +276,25 @@ impl Attrs {
         if self.bold() {
             modifiers |= Modifier::BOLD;
         }
+        if self.dim() {
+            modifiers |= Modifier::DIM;
+        }
         if self.italic() {
             modifiers |= Modifier::ITALIC;
         }
         if self.underline() {
             modifiers |= Modifier::UNDERLINED;
+            if let Some(color) = self.underline_color.to_ratatui() {
+                style = style.underline_color(color);
+            }
+        }
+        if self.rapid_blink() {
+            modifiers |= Modifier::RAPID_BLINK;
+        } else if self.blink() {
+            modifiers |= Modifier::SLOW_BLINK;
+        }
+        if self.hidden() {
+            modifiers |= Modifier::HIDDEN;
         }
         if self.strikethrough() {
             modifiers |= Modifier::CROSSED_OUT;
@@ -255,4 +365,29 @@ mod tests {
         assert!(!attrs.bold());
         assert!(!attrs.italic());
     }
+
+    #[test]
+    fn test_attrs_to_ratatui_modifiers() {
+        let mut attrs = Attrs::new();
+        attrs.set_dim(true);
+        attrs.set_blink(true);
+        attrs.set_hidden(true);
+        attrs.set_underline_style(UnderlineStyle::Curly);
+        attrs.underline_color = Color::Rgb(255, 0, 0);
+
+        let style = attrs.to_ratatui();
+        assert!(style.add_modifier.contains(
+            Modifier::DIM | Modifier::SLOW_BLINK | Modifier::HIDDEN | Modifier::UNDERLINED
+        ));
+        assert_eq!(style.underline_color, Some(RatatuiColor::Rgb(255, 0, 0)));
+
+        attrs.set_rapid_blink(true);
+        let style = attrs.to_ratatui();
+        assert!(style.add_modifier.contains(Modifier::RAPID_BLINK));
+        assert!(!style.add_modifier.contains(Modifier::SLOW_BLINK));
+
+        // The underline color only applies to underlined text
+        attrs.set_underline(false);
+        assert_eq!(attrs.to_ratatui().underline_color, None);
+    }
 }
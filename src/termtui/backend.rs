@@ -0,0 +1,314 @@
+//! Byte-stream backends that feed a terminal
+//!
+//! A backend provides the output stream shown in the terminal, the input
+//! stream keys are written to, and optionally resizing and a process status.
+
+use crate::termtui::process::ProcessStatus;
+use crate::termtui::size::Size;
+use anyhow::{anyhow, Result};
+use portable_pty::{native_pty_system, Child, CommandBuilder, MasterPty, PtySize};
+use std::io::{Read, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver};
+use std::sync::Arc;
+
+/// Source of terminal output and sink for terminal input
+///
+/// `TermTui` takes the reader and writer once when the backend is attached,
+/// reads output on a background thread, and keeps the backend for resizes
+/// and status checks.
+pub trait TermTuiBackend: Send {
+    /// Take the stream of output to display
+    fn take_reader(&mut self) -> Result<Box<dyn Read + Send>>;
+
+    /// Take the stream that input is written to
+    fn take_writer(&mut self) -> Result<Box<dyn Write + Send>>;
+
+    /// Tell the other end the terminal was resized
+    fn resize(&mut self, _size: Size) -> Result<()> {
+        Ok(())
+    }
+
+    /// Check whether the other end has finished, without blocking
+    ///
+    /// Returns `None` while it is still running.
+    fn try_wait(&mut self) -> Result<Option<ProcessStatus>>;
+
+    /// Stop the other end and wait for it to finish
+    fn kill(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Get the id of the process behind the backend, if any
+    fn process_id(&self) -> Option<u32> {
+        None
+    }
+}
+
+/// A command running in a local pseudo-terminal
+pub struct PtyBackend {
+    master: Box<dyn MasterPty + Send>,
+    child: Box<dyn Child + Send + Sync>,
+}
+
+impl PtyBackend {
+    /// Spawn `cmd` in a new PTY of the given size
+    pub fn spawn(cmd: CommandBuilder, size: Size) -> Result<Self> {
+        let pair = native_pty_system().openpty(PtySize {
+            rows: size.rows,
+            cols: size.cols,
+            pixel_width: 0,
+            pixel_height: 0,
+        })?;
+        let child = pair.slave.spawn_command(cmd)?;
+        Ok(Self {
+            master: pair.master,
+            child,
+        })
+    }
+}
+
+impl TermTuiBackend for PtyBackend {
+    fn take_reader(&mut self) -> Result<Box<dyn Read + Send>> {
+        self.master.try_clone_reader()
+    }
+
+    fn take_writer(&mut self) -> Result<Box<dyn Write + Send>> {
+        self.master.take_writer()
+    }
+
+    fn resize(&mut self, size: Size) -> Result<()> {
+        self.master.resize(PtySize {
+            rows: size.rows,
+            cols: size.cols,
+            pixel_width: 0,
+            pixel_height: 0,
+        })
+    }
+
+    fn try_wait(&mut self) -> Result<Option<ProcessStatus>> {
+        Ok(self.child.try_wait()?.map(ProcessStatus::from))
+    }
+
+    /// On Unix the process gets `SIGHUP` and a short grace period before `SIGKILL`
+    fn kill(&mut self) -> Result<()> {
+        self.child.kill()?;
+        self.child.wait()?;
+        Ok(())
+    }
+
+    fn process_id(&self) -> Option<u32> {
+        self.child.process_id()
+    }
+}
+
+/// Any `Read`/`Write` pair, e.g. a socket, a serial port or in-memory buffers
+///
+/// There is no process: the status becomes `Exited(0)` once the reader
+/// reaches end of file.
+pub struct StreamBackend {
+    reader: Option<Box<dyn Read + Send>>,
+    writer: Option<Box<dyn Write + Send>>,
+    closed: Arc<AtomicBool>,
+}
+
+impl StreamBackend {
+    /// Create a backend that displays `reader` and writes input to `writer`
+    pub fn new(reader: impl Read + Send + 'static, writer: impl Write + Send + 'static) -> Self {
+        Self {
+            reader: Some(Box::new(reader)),
+            writer: Some(Box::new(writer)),
+            closed: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Create a backend for output only; input is discarded
+    pub fn read_only(reader: impl Read + Send + 'static) -> Self {
+        Self::new(reader, std::io::sink())
+    }
+}
+
+impl TermTuiBackend for StreamBackend {
+    fn take_reader(&mut self) -> Result<Box<dyn Read + Send>> {
+        let reader = self
+            .reader
+            .take()
+            .ok_or_else(|| anyhow!("reader already taken"))?;
+        Ok(Box::new(EofReader {
+            inner: reader,
+            closed: Arc::clone(&self.closed),
+        }))
+    }
+
+    fn take_writer(&mut self) -> Result<Box<dyn Write + Send>> {
+        self.writer
+            .take()
+            .ok_or_else(|| anyhow!("writer already taken"))
+    }
+
+    fn try_wait(&mut self) -> Result<Option<ProcessStatus>> {
+        Ok(self
+            .closed
+            .load(Ordering::Acquire)
+            .then_some(ProcessStatus::Exited(0)))
+    }
+}
+
+/// Reader that records when the stream ends
+struct EofReader {
+    inner: Box<dyn Read + Send>,
+    closed: Arc<AtomicBool>,
+}
+
+impl Read for EofReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let result = self.inner.read(buf);
+        match result {
+            Ok(0) => self.closed.store(true, Ordering::Release),
+            Err(ref e) if e.kind() != std::io::ErrorKind::Interrupted => {
+                self.closed.store(true, Ordering::Release)
+            }
+            _ => {}
+        }
+        result
+    }
+}
+
+/// A `std::process::Child` spawned with piped stdio
+///
+/// Output from stdout and stderr is interleaved as it arrives. Programs see
+/// pipes rather than a terminal, so many disable colors and line editing.
+pub struct ChildBackend {
+    child: std::process::Child,
+}
+
+impl ChildBackend {
+    /// Wrap a child whose stdout (and optionally stdin and stderr) is piped
+    pub fn new(child: std::process::Child) -> Self {
+        Self { child }
+    }
+}
+
+impl TermTuiBackend for ChildBackend {
+    fn take_reader(&mut self) -> Result<Box<dyn Read + Send>> {
+        let stdout = self
+            .child
+            .stdout
+            .take()
+            .ok_or_else(|| anyhow!("child stdout is not piped"))?;
+        match self.child.stderr.take() {
+            Some(stderr) => Ok(Box::new(MergedReader::new(vec![
+                Box::new(stdout),
+                Box::new(stderr),
+            ]))),
+            None => Ok(Box::new(stdout)),
+        }
+    }
+
+    fn take_writer(&mut self) -> Result<Box<dyn Write + Send>> {
+        match self.child.stdin.take() {
+            Some(stdin) => Ok(Box::new(stdin)),
+            None => Ok(Box::new(std::io::sink())),
+        }
+    }
+
+    fn try_wait(&mut self) -> Result<Option<ProcessStatus>> {
+        Ok(self.child.try_wait()?.map(ProcessStatus::from))
+    }
+
+    fn kill(&mut self) -> Result<()> {
+        self.child.kill()?;
+        self.child.wait()?;
+        Ok(())
+    }
+
+    fn process_id(&self) -> Option<u32> {
+        Some(self.child.id())
+    }
+}
+
+/// Interleaves several readers, each drained by its own thread
+struct MergedReader {
+    chunks: Receiver<Vec<u8>>,
+    chunk: Vec<u8>,
+    offset: usize,
+}
+
+impl MergedReader {
+    fn new(readers: Vec<Box<dyn Read + Send>>) -> Self {
+        let (tx, rx) = mpsc::channel();
+        for mut reader in readers {
+            let tx = tx.clone();
+            std::thread::spawn(move || {
+                let mut buf = vec![0u8; 8192];
+                loop {
+                    match reader.read(&mut buf) {
+                        Ok(0) => break,
+                        Ok(n) => {
+                            if tx.send(buf[..n].to_vec()).is_err() {
+                                break;
+                            }
+                        }
+                        Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {}
+                        Err(_) => break,
+                    }
+                }
+            });
+        }
+        Self {
+            chunks: rx,
+            chunk: Vec::new(),
+            offset: 0,
+        }
+    }
+}
+
+impl Read for MergedReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if self.offset >= self.chunk.len() {
+            // Every sender is gone once all readers reached end of file
+            match self.chunks.recv() {
+                Ok(chunk) => {
+                    self.chunk = chunk;
+                    self.offset = 0;
+                }
+                Err(_) => return Ok(0),
+            }
+        }
+        let n = buf.len().min(self.chunk.len() - self.offset);
+        buf[..n].copy_from_slice(&self.chunk[self.offset..self.offset + n]);
+        self.offset += n;
+        Ok(n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_stream_backend_reports_eof() {
+        let mut backend = StreamBackend::read_only(Cursor::new(b"hello".to_vec()));
+        let mut reader = backend.take_reader().unwrap();
+        assert!(backend.take_reader().is_err());
+        assert_eq!(backend.try_wait().unwrap(), None);
+
+        let mut output = String::new();
+        reader.read_to_string(&mut output).unwrap();
+        assert_eq!(output, "hello");
+        assert_eq!(backend.try_wait().unwrap(), Some(ProcessStatus::Exited(0)));
+    }
+
+    #[test]
+    fn test_merged_reader() {
+        let mut reader = MergedReader::new(vec![
+            Box::new(Cursor::new(b"one".to_vec())),
+            Box::new(Cursor::new(b"two".to_vec())),
+        ]);
+        let mut output = String::new();
+        reader.read_to_string(&mut output).unwrap();
+        assert_eq!(output.len(), 6);
+        assert!(output.contains("one") && output.contains("two"));
+    }
+}
@@ -1,16 +1,21 @@
 //! Terminal cell representation
 
 use crate::termtui::attrs::Attrs;
+use crate::termtui::hyperlink::Hyperlink;
 use ratatui::buffer::Cell as RatatuiCell;
-use unicode_width::UnicodeWidthChar;
+use ratatui::style::Modifier;
+use std::sync::Arc;
+use unicode_width::UnicodeWidthStr;
 
 /// A single terminal cell
 #[derive(Clone, Debug, Default)]
 pub struct Cell {
-    /// Cell text (may contain combining characters)
+    /// Cell text: one grapheme cluster (empty for a wide continuation)
     text: String,
     /// Cell attributes
     attrs: Attrs,
+    /// OSC 8 hyperlink covering this cell
+    hyperlink: Option<Arc<Hyperlink>>,
 }
 
 impl Cell {
@@ -19,6 +24,7 @@ impl Cell {
         Self {
             text: " ".to_string(),
             attrs: Attrs::default(),
+            hyperlink: None,
         }
     }
 
@@ -27,6 +33,7 @@ impl Cell {
         Self {
             text: " ".to_string(),
             attrs,
+            hyperlink: None,
         }
     }
 
@@ -40,6 +47,12 @@ impl Cell {
         self.text = text.into();
     }
 
+    /// Set the cell text to a single character, reusing the allocation
+    pub fn set_char(&mut self, c: char) {
+        self.text.clear();
+        self.text.push(c);
+    }
+
     /// Get the cell attributes
     pub fn attrs(&self) -> &Attrs {
         &self.attrs
@@ -55,13 +68,19 @@ impl Cell {
         self.attrs = attrs;
     }
 
-    /// Get the display width of this cell
+    /// Get the hyperlink covering this cell
+    pub fn hyperlink(&self) -> Option<&Arc<Hyperlink>> {
+        self.hyperlink.as_ref()
+    }
+
+    /// Set the hyperlink covering this cell
+    pub fn set_hyperlink(&mut self, hyperlink: Option<Arc<Hyperlink>>) {
+        self.hyperlink = hyperlink;
+    }
+
+    /// Get the display width of this cell's grapheme cluster (1 or 2)
     pub fn width(&self) -> usize {
-        self.text
-            .chars()
-            .next()
-            .and_then(|c| c.width())
-            .unwrap_or(1)
+        self.text.width().clamp(1, 2)
     }
 
     /// Check if this cell is a wide character continuation
@@ -77,23 +96,38 @@ impl Cell {
 
     /// Clear the cell (reset to space with default attrs)
     pub fn clear(&mut self) {
-        self.text = " ".to_string();
+        self.set_char(' ');
         self.attrs = Attrs::default();
+        self.hyperlink = None;
     }
 
     /// Clear the cell but keep attributes
     pub fn clear_keep_attrs(&mut self) {
-        self.text = " ".to_string();
+        self.set_char(' ');
+        self.hyperlink = None;
+    }
+
+    /// Get the ratatui style for this cell (hyperlinks are underlined)
+    pub fn style(&self) -> ratatui::style::Style {
+        let style = self.attrs.to_ratatui();
+        if self.hyperlink.is_some() {
+            style.add_modifier(Modifier::UNDERLINED)
+        } else {
+            style
+        }
     }
 
     /// Convert to ratatui cell
     pub fn to_ratatui(&self) -> RatatuiCell {
         let mut cell = RatatuiCell::default();
 
-        // Get the first character or space
-        let ch = self.text.chars().next().unwrap_or(' ');
-        cell.set_char(ch);
-        cell.set_style(self.attrs.to_ratatui());
+        // Continuations render as a space
+        if self.text.is_empty() {
+            cell.set_char(' ');
+        } else {
+            cell.set_symbol(&self.text);
+        }
+        cell.set_style(self.style());
 
         cell
     }
@@ -101,7 +135,7 @@ impl Cell {
 
 impl PartialEq for Cell {
     fn eq(&self, other: &Self) -> bool {
-        self.text == other.text && self.attrs == other.attrs
+        self.text == other.text && self.attrs == other.attrs && self.hyperlink == other.hyperlink
     }
 }
 
@@ -132,6 +166,17 @@ mod tests {
         assert_eq!(cell.width(), 2);
     }
 
+    #[test]
+    fn test_cell_grapheme_cluster() {
+        let mut cell = Cell::new();
+        cell.set_text("e\u{301}");
+        assert_eq!(cell.width(), 1);
+        assert_eq!(cell.to_ratatui().symbol(), "e\u{301}");
+
+        cell.set_text("\u{1F44D}\u{1F3FD}");
+        assert_eq!(cell.width(), 2);
+    }
+
     #[test]
     fn test_cell_clear() {
         let mut cell = Cell::new();
@@ -152,4 +197,16 @@ mod tests {
         let ratatui_cell = cell.to_ratatui();
         assert_eq!(ratatui_cell.symbol(), "A");
     }
+
+    #[test]
+    fn test_cell_hyperlink() {
+        let mut cell = Cell::new();
+        cell.set_text("A");
+        cell.set_hyperlink(Some(Arc::new(Hyperlink::new("https://example.com"))));
+        assert_eq!(cell.hyperlink().unwrap().uri(), "https://example.com");
+        assert!(cell.style().add_modifier.contains(Modifier::UNDERLINED));
+
+        cell.clear();
+        assert!(cell.hyperlink().is_none());
+    }
 }
@@ -0,0 +1,83 @@
+//! Character sets designated to G0/G1 (`ESC ( x` / `ESC ) x`)
+
+/// A character set that can be designated to G0 or G1
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Charset {
+    /// US ASCII (`B`)
+    #[default]
+    Ascii,
+    /// United Kingdom (`A`): `#` prints as a pound sign
+    Uk,
+    /// DEC special graphics (`0`), used for line drawing
+    DecLineDrawing,
+}
+
+impl Charset {
+    /// Map a printed character through this character set
+    pub fn map(self, c: char) -> char {
+        match self {
+            Charset::Ascii => c,
+            Charset::Uk => match c {
+                '#' => '£',
+                c => c,
+            },
+            Charset::DecLineDrawing => match c {
+                '_' => ' ',
+                '`' => '◆',
+                'a' => '▒',
+                'b' => '␉',
+                'c' => '␌',
+                'd' => '␍',
+                'e' => '␊',
+                'f' => '°',
+                'g' => '±',
+                'h' => '␤',
+                'i' => '␋',
+                'j' => '┘',
+                'k' => '┐',
+                'l' => '┌',
+                'm' => '└',
+                'n' => '┼',
+                'o' => '⎺',
+                'p' => '⎻',
+                'q' => '─',
+                'r' => '⎼',
+                's' => '⎽',
+                't' => '├',
+                'u' => '┤',
+                'v' => '┴',
+                'w' => '┬',
+                'x' => '│',
+                'y' => '≤',
+                'z' => '≥',
+                '{' => 'π',
+                '|' => '≠',
+                '}' => '£',
+                '~' => '·',
+                c => c,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_drawing_map() {
+        let mapped: String = "lqqk\nx  x\nmqqj"
+            .chars()
+            .map(|c| Charset::DecLineDrawing.map(c))
+            .collect();
+        assert_eq!(mapped, "┌──┐\n│  │\n└──┘");
+        // Characters outside 0x5f..0x7e are unchanged
+        assert_eq!(Charset::DecLineDrawing.map('A'), 'A');
+    }
+
+    #[test]
+    fn test_uk_and_ascii_map() {
+        assert_eq!(Charset::Uk.map('#'), '£');
+        assert_eq!(Charset::Ascii.map('q'), 'q');
+    }
+}
@@ -5,9 +5,12 @@
 //! receiving output in the background.
 
 use crate::termtui::screen::Screen;
+use crate::termtui::search::{build_matcher, SearchDirection, SearchMatch, SearchOptions};
+use crate::termtui::selection::{line_bounds, word_bounds, Selection, SelectionMode};
+use crate::termtui::shell_integration::ShellCommand;
 
 /// Cursor position in copy mode
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
 pub struct CopyPos {
     pub x: i32,
     pub y: i32,
@@ -58,6 +61,39 @@ impl CopyMoveDir {
     }
 }
 
+/// Search state in copy mode
+#[derive(Clone, Debug, Default)]
+pub struct CopySearch {
+    /// Current query
+    pub query: String,
+    /// Direction of the last search (`n` repeats it, `N` reverses it)
+    pub direction: SearchDirection,
+    /// Regex / case sensitivity options
+    pub options: SearchOptions,
+    /// Whether the search prompt is accepting input
+    pub editing: bool,
+    /// Whether the query is an invalid regex
+    pub invalid: bool,
+    /// All matches on the frozen screen, in screen order
+    matches: Vec<SearchMatch>,
+    /// Index of the match under the cursor
+    current: Option<usize>,
+    /// Cursor position when the search prompt was opened
+    origin: CopyPos,
+}
+
+impl CopySearch {
+    /// Get all matches, in screen order
+    pub fn matches(&self) -> &[SearchMatch] {
+        &self.matches
+    }
+
+    /// Get the index of the match under the cursor
+    pub fn current(&self) -> Option<usize> {
+        self.current
+    }
+}
+
 /// Copy mode state
 #[derive(Clone, Default)]
 pub enum CopyMode {
@@ -72,12 +108,16 @@ pub enum CopyMode {
         cursor: CopyPos,
         /// Selection anchor (start of selection)
         anchor: Option<CopyPos>,
+        /// Shape of the selection
+        selection_mode: SelectionMode,
         /// Screen height for bounds
         screen_height: i32,
         /// Screen width for bounds
         screen_width: i32,
         /// Scrollback available
         scrollback_available: i32,
+        /// Search state
+        search: CopySearch,
     },
 }
 
@@ -90,15 +130,17 @@ impl CopyMode {
     /// Enter copy mode with a screen snapshot
     pub fn enter(screen: Screen, start: CopyPos) -> Self {
         let size = screen.size();
-        let scrollback = screen.primary_grid().scrollback_available() as i32;
+        let scrollback = screen.scrollback_available() as i32;
 
         CopyMode::Active {
             frozen_screen: Box::new(screen),
             cursor: start,
             anchor: None,
+            selection_mode: SelectionMode::Stream,
             screen_height: size.rows as i32,
             screen_width: size.cols as i32,
             scrollback_available: scrollback,
+            search: CopySearch::default(),
         }
     }
 
@@ -118,6 +160,7 @@ impl CopyMode {
             cursor.x = new_x;
             cursor.y = new_y;
         }
+        self.scroll_to_cursor();
     }
 
     /// Move cursor by direction
@@ -168,21 +211,342 @@ impl CopyMode {
                 }
             }
         }
+        self.scroll_to_cursor();
+    }
+
+    /// Scroll the frozen screen so the cursor row is visible
+    fn scroll_to_cursor(&mut self) {
+        if let CopyMode::Active {
+            frozen_screen,
+            cursor,
+            screen_height,
+            ..
+        } = self
+        {
+            let offset = frozen_screen.scrollback() as i32;
+            // Visible rows span -offset ..= screen_height - 1 - offset
+            if cursor.y < -offset {
+                frozen_screen.set_scrollback((-cursor.y) as usize);
+            } else if cursor.y > *screen_height - 1 - offset {
+                frozen_screen.set_scrollback((*screen_height - 1 - cursor.y).max(0) as usize);
+            }
+        }
+    }
+
+    /// Jump to the previous shell prompt (OSC 133) above the cursor
+    ///
+    /// Returns false if there is no earlier prompt.
+    pub fn jump_to_prev_prompt(&mut self) -> bool {
+        let Some(cursor) = self.cursor() else {
+            return false;
+        };
+        let target = self
+            .commands()
+            .iter()
+            .rev()
+            .map(|cmd| cmd.prompt_row)
+            .find(|&row| row < cursor.y);
+        self.jump_to_row(target)
+    }
+
+    /// Jump to the next shell prompt (OSC 133) below the cursor
+    ///
+    /// Returns false if there is no later prompt.
+    pub fn jump_to_next_prompt(&mut self) -> bool {
+        let Some(cursor) = self.cursor() else {
+            return false;
+        };
+        let target = self
+            .commands()
+            .iter()
+            .map(|cmd| cmd.prompt_row)
+            .find(|&row| row > cursor.y);
+        self.jump_to_row(target)
+    }
+
+    /// Select the output of the most recent command that produced output
+    ///
+    /// Returns false if no command output is known.
+    pub fn select_last_command_output(&mut self) -> bool {
+        let last_row = match self.frozen_screen() {
+            Some(screen) => screen.cursor_pos().row as i32,
+            None => return false,
+        };
+        let Some((start, end)) = self
+            .commands()
+            .iter()
+            .rev()
+            .find_map(|cmd| cmd.output_rows(last_row))
+        else {
+            return false;
+        };
+
+        if let CopyMode::Active {
+            cursor,
+            anchor,
+            selection_mode,
+            screen_width,
+            ..
+        } = self
+        {
+            *anchor = Some(CopyPos::new(0, start));
+            *cursor = CopyPos::new(*screen_width - 1, end);
+            *selection_mode = SelectionMode::Stream;
+        }
+        self.scroll_to_cursor();
+        true
+    }
+
+    /// Get the search state
+    pub fn search(&self) -> Option<&CopySearch> {
+        if let CopyMode::Active { search, .. } = self {
+            Some(search)
+        } else {
+            None
+        }
+    }
+
+    /// Check if the search prompt is accepting input
+    pub fn is_search_editing(&self) -> bool {
+        self.search().is_some_and(|search| search.editing)
+    }
+
+    /// Open the search prompt
+    pub fn start_search(&mut self, direction: SearchDirection) {
+        if let CopyMode::Active { cursor, search, .. } = self {
+            search.query.clear();
+            search.direction = direction;
+            search.editing = true;
+            search.origin = *cursor;
+        }
+        self.update_search();
+    }
+
+    /// Append a character to the query (incremental search)
+    pub fn search_push(&mut self, c: char) {
+        if let CopyMode::Active { search, .. } = self {
+            search.query.push(c);
+        }
+        self.update_search();
+    }
+
+    /// Remove the last character of the query
+    pub fn search_pop(&mut self) {
+        if let CopyMode::Active { search, .. } = self {
+            search.query.pop();
+        }
+        self.update_search();
+    }
+
+    /// Toggle regex matching
+    pub fn toggle_search_regex(&mut self) {
+        if let CopyMode::Active { search, .. } = self {
+            search.options.regex = !search.options.regex;
+        }
+        self.update_search();
+    }
+
+    /// Toggle case-insensitive matching
+    pub fn toggle_search_case(&mut self) {
+        if let CopyMode::Active { search, .. } = self {
+            search.options.case_insensitive = !search.options.case_insensitive;
+        }
+        self.update_search();
+    }
+
+    /// Close the search prompt, keeping the cursor on the current match
+    pub fn confirm_search(&mut self) {
+        if let CopyMode::Active { search, .. } = self {
+            search.editing = false;
+        }
+    }
+
+    /// Close the search prompt and return to where the search started
+    pub fn cancel_search(&mut self) {
+        if let CopyMode::Active { cursor, search, .. } = self {
+            *cursor = search.origin;
+            *search = CopySearch::default();
+        }
+        self.scroll_to_cursor();
+    }
+
+    /// Jump to the next match in the search direction (`n`)
+    ///
+    /// Wraps around at the ends. Returns false if there are no matches.
+    pub fn search_next(&mut self) -> bool {
+        match self.search() {
+            Some(search) => self.jump_to_match(search.direction, false),
+            None => false,
+        }
+    }
+
+    /// Jump to the next match against the search direction (`N`)
+    pub fn search_prev(&mut self) -> bool {
+        match self.search() {
+            Some(search) => self.jump_to_match(search.direction.reverse(), false),
+            None => false,
+        }
+    }
+
+    /// Recompute matches and jump to the first one from the search origin
+    fn update_search(&mut self) {
+        let CopyMode::Active {
+            frozen_screen,
+            cursor,
+            search,
+            ..
+        } = self
+        else {
+            return;
+        };
+
+        search.matches.clear();
+        search.current = None;
+        search.invalid = false;
+        if !search.query.is_empty() {
+            match build_matcher(&search.query, search.options) {
+                Ok(matcher) => search.matches = frozen_screen.search(&matcher),
+                Err(_) => search.invalid = true,
+            }
+        }
+
+        if search.editing {
+            *cursor = search.origin;
+            let direction = search.direction;
+            self.jump_to_match(direction, true);
+        } else {
+            search.current = search.matches.iter().position(|m| m.start == *cursor);
+        }
+        self.scroll_to_cursor();
+    }
+
+    /// Move the cursor to the closest match in a direction
+    ///
+    /// With `inclusive`, a match starting at the cursor counts (incremental
+    /// search); otherwise the search moves past it (`n` / `N`).
+    fn jump_to_match(&mut self, direction: SearchDirection, inclusive: bool) -> bool {
+        let CopyMode::Active { cursor, search, .. } = self else {
+            return false;
+        };
+        if search.matches.is_empty() {
+            return false;
+        }
+
+        let key = |pos: CopyPos| (pos.y, pos.x);
+        let here = key(*cursor);
+        let idx = match direction {
+            SearchDirection::Forward => search
+                .matches
+                .iter()
+                .position(|m| key(m.start) > here || (inclusive && key(m.start) == here))
+                .unwrap_or(0),
+            SearchDirection::Backward => search
+                .matches
+                .iter()
+                .rposition(|m| key(m.start) < here || (inclusive && key(m.start) == here))
+                .unwrap_or(search.matches.len() - 1),
+        };
+
+        *cursor = search.matches[idx].start;
+        search.current = Some(idx);
+        self.scroll_to_cursor();
+        true
+    }
+
+    /// Get the shell commands recorded on the frozen screen
+    fn commands(&self) -> Vec<ShellCommand> {
+        match self.frozen_screen() {
+            Some(screen) if !screen.is_alternate_screen() => screen.commands(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Move the cursor to the start of a row
+    fn jump_to_row(&mut self, row: Option<i32>) -> bool {
+        let Some(row) = row else {
+            return false;
+        };
+        if let CopyMode::Active { cursor, .. } = self {
+            *cursor = CopyPos::new(0, row);
+        }
+        self.scroll_to_cursor();
+        true
     }
 
     /// Set selection anchor at current cursor position
     pub fn set_anchor(&mut self) {
-        if let CopyMode::Active { cursor, anchor, .. } = self {
-            if anchor.is_some() {
+        self.start_selection(SelectionMode::Stream);
+    }
+
+    /// Start a selection of the given shape at the cursor (`v`, `V`, Ctrl+V)
+    ///
+    /// Like vim, repeating the same shape clears the selection and another
+    /// shape changes the current one.
+    pub fn start_selection(&mut self, mode: SelectionMode) {
+        if let CopyMode::Active {
+            cursor,
+            anchor,
+            selection_mode,
+            ..
+        } = self
+        {
+            if anchor.is_some() && *selection_mode == mode {
                 // Toggle anchor off
                 *anchor = None;
-            } else {
+            } else if anchor.is_none() {
                 // Set anchor at current position
                 *anchor = Some(*cursor);
             }
+            *selection_mode = mode;
+        }
+    }
+
+    /// Get the shape of the current selection
+    pub fn selection_mode(&self) -> Option<SelectionMode> {
+        match self {
+            CopyMode::Active {
+                anchor: Some(_),
+                selection_mode,
+                ..
+            } => Some(*selection_mode),
+            _ => None,
         }
     }
 
+    /// Select the word under a position (double click)
+    pub fn select_word(&mut self, pos: CopyPos, separators: &str) {
+        let Some(screen) = self.frozen_screen() else {
+            return;
+        };
+        let (start, end) = word_bounds(screen, pos, separators);
+        self.select_range(start, end, SelectionMode::Stream);
+    }
+
+    /// Select the line under a position, across soft wraps (triple click)
+    pub fn select_line(&mut self, pos: CopyPos) {
+        let Some(screen) = self.frozen_screen() else {
+            return;
+        };
+        let (start, end) = line_bounds(screen, pos.y);
+        self.select_range(start, end, SelectionMode::Line);
+    }
+
+    /// Select from `start` to `end`, leaving the cursor at the end
+    fn select_range(&mut self, start: CopyPos, end: CopyPos, mode: SelectionMode) {
+        if let CopyMode::Active {
+            cursor,
+            anchor,
+            selection_mode,
+            ..
+        } = self
+        {
+            *anchor = Some(start);
+            *cursor = end;
+            *selection_mode = mode;
+        }
+        self.scroll_to_cursor();
+    }
+
     /// Set end position (for mouse selection)
     pub fn set_end(&mut self) {
         if let CopyMode::Active { cursor, anchor, .. } = self {
@@ -202,24 +566,36 @@ impl CopyMode {
         }
     }
 
-    /// Get selected text from frozen screen
-    pub fn get_selected_text(&self) -> Option<String> {
+    /// Get the selection resolved against the frozen screen
+    pub fn selection(&self) -> Option<Selection> {
         if let CopyMode::Active {
             frozen_screen,
             cursor,
             anchor,
+            selection_mode,
             ..
         } = self
         {
             let anchor = (*anchor)?;
-            let (low, high) = CopyPos::to_low_high(&anchor, cursor);
-
-            Some(frozen_screen.get_selected_text(low.x, low.y, high.x, high.y))
+            Some(Selection::new(
+                frozen_screen,
+                anchor,
+                *cursor,
+                *selection_mode,
+            ))
         } else {
             None
         }
     }
 
+    /// Get selected text from frozen screen
+    ///
+    /// The cell under the cursor is included, as it is highlighted.
+    pub fn get_selected_text(&self) -> Option<String> {
+        let selection = self.selection()?;
+        Some(selection.text(self.frozen_screen()?))
+    }
+
     /// Get the frozen screen (for rendering in copy mode)
     pub fn frozen_screen(&self) -> Option<&Screen> {
         if let CopyMode::Active { frozen_screen, .. } = self {
@@ -242,6 +618,8 @@ impl CopyMode {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::termtui::search::SearchDirection;
+    use crate::termtui::selection::DEFAULT_WORD_SEPARATORS;
 
     fn make_test_screen() -> Screen {
         Screen::new(24, 80, 1000)
@@ -309,6 +687,51 @@ mod tests {
         assert_eq!(end.x, 15);
     }
 
+    #[test]
+    fn test_copy_mode_selection_modes() {
+        let mut parser = crate::termtui::parser::Parser::new(4, 10, 100);
+        parser.process(b"one two\r\nthree four");
+        let mut mode = CopyMode::enter(parser.screen().clone(), CopyPos::new(1, 0));
+        assert_eq!(mode.selection_mode(), None);
+
+        mode.start_selection(SelectionMode::Block);
+        mode.move_cursor(2, 1);
+        assert_eq!(mode.selection_mode(), Some(SelectionMode::Block));
+        assert_eq!(mode.get_selected_text(), Some("ne\nhre".to_string()));
+
+        // Another shape keeps the anchor, the same shape clears it
+        mode.start_selection(SelectionMode::Line);
+        assert_eq!(
+            mode.get_selected_text(),
+            Some("one two\nthree four".to_string())
+        );
+        mode.start_selection(SelectionMode::Stream);
+        assert_eq!(mode.get_selected_text(), Some("ne two\nthre".to_string()));
+        mode.start_selection(SelectionMode::Stream);
+        assert_eq!(mode.selection_mode(), None);
+    }
+
+    #[test]
+    fn test_copy_mode_select_word_and_line() {
+        let mut parser = crate::termtui::parser::Parser::new(4, 10, 100);
+        parser.process(b"$ ls src/main.rs\r\nok");
+        let mut mode = CopyMode::enter(parser.screen().clone(), CopyPos::new(0, 0));
+
+        // "src/main.rs" wraps onto the second row
+        mode.select_word(CopyPos::new(6, 0), DEFAULT_WORD_SEPARATORS);
+        assert_eq!(mode.get_selected_text(), Some("src/main.rs".to_string()));
+        assert_eq!(mode.cursor(), Some(CopyPos::new(5, 1)));
+        mode.select_word(CopyPos::new(6, 0), "/");
+        assert_eq!(mode.get_selected_text(), Some("src".to_string()));
+
+        mode.select_line(CopyPos::new(2, 1));
+        assert_eq!(mode.selection_mode(), Some(SelectionMode::Line));
+        assert_eq!(
+            mode.get_selected_text(),
+            Some("$ ls src/main.rs".to_string())
+        );
+    }
+
     #[test]
     fn test_copy_pos_to_low_high() {
         let a = CopyPos::new(10, 5);
@@ -318,4 +741,145 @@ mod tests {
         assert_eq!(low.y, 5);
         assert_eq!(high.y, 10);
     }
+
+    fn shell_screen() -> Screen {
+        let mut parser = crate::termtui::parser::Parser::new(5, 20, 100);
+        for (cmd, output, status) in [("one", "1\r\n", 0), ("two", "2a\r\n2b\r\n", 3)] {
+            parser.process(
+                format!(
+                    "\x1b]133;A\x07$ \x1b]133;B\x07{cmd}\r\n\x1b]133;C\x07{output}\x1b]133;D;{status}\x07"
+                )
+                .as_bytes(),
+            );
+        }
+        parser.process(b"\x1b]133;A\x07$ ");
+        parser.screen().clone()
+    }
+
+    #[test]
+    fn test_copy_mode_prompt_jumps() {
+        // Rows: prompt at -1, output 0, prompt 1, output 2..=3, prompt 4
+        let screen = shell_screen();
+        assert_eq!(
+            screen
+                .commands()
+                .iter()
+                .map(|c| c.prompt_row)
+                .collect::<Vec<_>>(),
+            vec![-1, 1, 4]
+        );
+
+        let mut mode = CopyMode::enter(screen, CopyPos::new(5, 4));
+        assert!(mode.jump_to_prev_prompt());
+        assert_eq!(mode.cursor(), Some(CopyPos::new(0, 1)));
+        assert!(mode.jump_to_prev_prompt());
+        assert_eq!(mode.cursor(), Some(CopyPos::new(0, -1)));
+        // The frozen screen scrolls so the prompt in scrollback is visible
+        assert_eq!(mode.frozen_screen().unwrap().scrollback(), 1);
+        assert!(!mode.jump_to_prev_prompt());
+
+        assert!(mode.jump_to_next_prompt());
+        assert_eq!(mode.cursor(), Some(CopyPos::new(0, 1)));
+    }
+
+    #[test]
+    fn test_copy_mode_select_last_command_output() {
+        let mut mode = CopyMode::enter(shell_screen(), CopyPos::new(0, 0));
+        assert!(mode.select_last_command_output());
+        assert_eq!(mode.get_selected_text(), Some("2a\n2b".to_string()));
+    }
+
+    #[test]
+    fn test_copy_mode_without_prompts() {
+        let mut mode = CopyMode::enter(make_test_screen(), CopyPos::new(3, 3));
+        assert!(!mode.jump_to_prev_prompt());
+        assert!(!mode.jump_to_next_prompt());
+        assert!(!mode.select_last_command_output());
+        assert_eq!(mode.cursor(), Some(CopyPos::new(3, 3)));
+    }
+
+    fn search_screen() -> Screen {
+        let mut parser = crate::termtui::parser::Parser::new(4, 10, 100);
+        // The last "needle" wraps across two rows
+        parser.process(b"a needle\r\nxx\r\nNeedle b\r\nabcdefneedle!");
+        parser.screen().clone()
+    }
+
+    fn type_query(mode: &mut CopyMode, query: &str) {
+        for c in query.chars() {
+            mode.search_push(c);
+        }
+    }
+
+    #[test]
+    fn test_copy_mode_incremental_search() {
+        let mut mode = CopyMode::enter(search_screen(), CopyPos::new(0, 0));
+        mode.start_search(SearchDirection::Forward);
+        assert!(mode.is_search_editing());
+
+        type_query(&mut mode, "nee");
+        // Rows: "a needle" -1, "xx" 0, "Needle b" 1, "abcdefneed" 2 (wraps), "le!" 3
+        let search = mode.search().unwrap();
+        assert_eq!(search.matches().len(), 2);
+        assert_eq!(mode.cursor(), Some(CopyPos::new(6, 2)));
+        assert_eq!(mode.search().unwrap().current(), Some(1));
+
+        type_query(&mut mode, "dle");
+        let matches = mode.search().unwrap().matches().to_vec();
+        assert_eq!(matches.len(), 2);
+        assert_eq!(matches[1].start, CopyPos::new(6, 2));
+        assert_eq!(matches[1].end, CopyPos::new(1, 3));
+
+        mode.search_pop();
+        mode.search_pop();
+        mode.search_pop();
+        mode.confirm_search();
+        assert!(!mode.is_search_editing());
+
+        // `n` wraps around to the first match, `N` goes back
+        assert!(mode.search_next());
+        assert_eq!(mode.cursor(), Some(CopyPos::new(2, -1)));
+        assert!(mode.search_prev());
+        assert_eq!(mode.cursor(), Some(CopyPos::new(6, 2)));
+    }
+
+    #[test]
+    fn test_copy_mode_search_options() {
+        let mut mode = CopyMode::enter(search_screen(), CopyPos::new(0, 0));
+        mode.start_search(SearchDirection::Backward);
+        type_query(&mut mode, "needle");
+        assert_eq!(mode.search().unwrap().matches().len(), 2);
+        // Backward from the origin finds the match in the scrollback
+        assert_eq!(mode.cursor(), Some(CopyPos::new(2, -1)));
+
+        mode.toggle_search_case();
+        assert_eq!(mode.search().unwrap().matches().len(), 3);
+
+        mode.search_pop();
+        mode.search_pop();
+        mode.search_pop();
+        mode.search_pop();
+        mode.search_pop();
+        mode.search_pop();
+        mode.toggle_search_regex();
+        type_query(&mut mode, "[");
+        assert!(mode.search().unwrap().invalid);
+        mode.search_pop();
+        type_query(&mut mode, "n.*e b");
+        assert_eq!(mode.search().unwrap().matches().len(), 1);
+    }
+
+    #[test]
+    fn test_copy_mode_cancel_search() {
+        let mut mode = CopyMode::enter(search_screen(), CopyPos::new(1, 1));
+        mode.start_search(SearchDirection::Forward);
+        type_query(&mut mode, "abcd");
+        assert_eq!(mode.cursor(), Some(CopyPos::new(0, 2)));
+
+        mode.cancel_search();
+        assert!(!mode.is_search_editing());
+        assert!(mode.search().unwrap().matches().is_empty());
+        assert_eq!(mode.cursor(), Some(CopyPos::new(1, 1)));
+        assert!(!mode.search_next());
+    }
 }
@@ -0,0 +1,29 @@
+//! Cursor shape requested with DECSCUSR (`CSI Ps SP q`)
+
+use termwiz::escape::csi::CursorStyle;
+
+/// Shape the cursor is drawn with
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CursorShape {
+    /// Full cell block
+    #[default]
+    Block,
+    /// Line under the cell
+    Underline,
+    /// Vertical bar at the left of the cell
+    Bar,
+}
+
+impl CursorShape {
+    /// Get the shape and blinking state requested by a DECSCUSR style
+    pub fn from_style(style: CursorStyle) -> (Self, bool) {
+        match style {
+            CursorStyle::Default | CursorStyle::SteadyBlock => (CursorShape::Block, false),
+            CursorStyle::BlinkingBlock => (CursorShape::Block, true),
+            CursorStyle::BlinkingUnderline => (CursorShape::Underline, true),
+            CursorStyle::SteadyUnderline => (CursorShape::Underline, false),
+            CursorStyle::BlinkingBar => (CursorShape::Bar, true),
+            CursorStyle::SteadyBar => (CursorShape::Bar, false),
+        }
+    }
+}
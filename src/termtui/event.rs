@@ -0,0 +1,26 @@
+//! Events emitted by the terminal widget
+
+use crate::termtui::hyperlink::LinkTarget;
+use crate::termtui::process::ProcessStatus;
+
+/// Events that can be emitted by `TermTui`.
+///
+/// Collect them with `TermTui::take_events` after handling input so the
+/// parent application can react (e.g., open a link in an editor pane).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TermTuiEvent {
+    /// A link was Ctrl+clicked.
+    LinkClicked {
+        /// The link under the pointer.
+        target: LinkTarget,
+    },
+    /// The program rang the bell (BEL).
+    Bell,
+    /// The program wrote nothing for the configured silence threshold.
+    Silence,
+    /// The child process exited.
+    ProcessExited {
+        /// How the process ended.
+        status: ProcessStatus,
+    },
+}
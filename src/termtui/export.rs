@@ -0,0 +1,434 @@
+//! Exporting scrollback as text, ANSI or HTML, and reloading ANSI exports
+
+use crate::termtui::attrs::{Attrs, Color, UnderlineStyle};
+use crate::termtui::cell::Cell;
+use crate::termtui::grid::Grid;
+use crate::termtui::hyperlink::Hyperlink;
+use crate::termtui::palette::{Palette, Rgb};
+use crate::termtui::parser::Parser;
+use std::sync::Arc;
+
+/// Output format for exported terminal contents
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// Plain text without attributes
+    #[default]
+    Text,
+    /// Text with SGR and OSC 8 escape sequences (reloadable with `import_ansi`)
+    Ansi,
+    /// Standalone HTML document with inline styles
+    Html,
+}
+
+impl ExportFormat {
+    /// Get the conventional file extension
+    pub fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Text => "txt",
+            ExportFormat::Ansi => "ansi",
+            ExportFormat::Html => "html",
+        }
+    }
+}
+
+/// Export the scrollback and screen of `grid`
+///
+/// Soft-wrapped rows are joined into one line and trailing blanks are
+/// dropped, so an ANSI export reflows when reloaded at another width.
+/// `palette` resolves indexed and default colors for HTML.
+pub fn export_grid(grid: &Grid, format: ExportFormat, palette: &Palette) -> String {
+    let lines = logical_lines(grid);
+    match format {
+        ExportFormat::Text => export_text(&lines),
+        ExportFormat::Ansi => export_ansi(&lines),
+        ExportFormat::Html => export_html(&lines, palette),
+    }
+}
+
+/// Reload an ANSI export into a fresh parser
+pub fn import_ansi(text: &str, rows: usize, cols: usize, scrollback: usize) -> Parser {
+    let mut parser = Parser::new(rows, cols, scrollback);
+    parser.load_ansi(text);
+    parser
+}
+
+/// Gather rows into lines of cells, joining soft-wrapped rows
+fn logical_lines(grid: &Grid) -> Vec<Vec<&Cell>> {
+    let mut lines = Vec::new();
+    let mut line = Vec::new();
+    for (_, row) in grid.all_rows() {
+        line.extend(row.cells().filter(|cell| !cell.is_wide_continuation()));
+        if !row.wrapped() {
+            while line.last().is_some_and(|cell| is_blank(cell)) {
+                line.pop();
+            }
+            lines.push(std::mem::take(&mut line));
+        }
+    }
+    if !line.is_empty() {
+        lines.push(line);
+    }
+    while lines.last().is_some_and(|line| line.is_empty()) {
+        lines.pop();
+    }
+    lines
+}
+
+/// Check whether a cell shows nothing but the default background
+fn is_blank(cell: &Cell) -> bool {
+    let attrs = cell.attrs();
+    cell.text().trim().is_empty()
+        && cell.hyperlink().is_none()
+        && attrs.bg == Color::Default
+        && !attrs.inverse()
+        && !attrs.underline()
+        && !attrs.strikethrough()
+        && !attrs.overline()
+}
+
+/// Get the text shown by a cell
+fn cell_text(cell: &Cell) -> &str {
+    if cell.text().is_empty() {
+        " "
+    } else {
+        cell.text()
+    }
+}
+
+fn export_text(lines: &[Vec<&Cell>]) -> String {
+    let mut output = String::new();
+    for line in lines {
+        let start = output.len();
+        for cell in line {
+            output.push_str(cell_text(cell));
+        }
+        output.truncate(start + output[start..].trim_end().len());
+        output.push('\n');
+    }
+    output
+}
+
+fn export_ansi(lines: &[Vec<&Cell>]) -> String {
+    let mut output = String::new();
+    for line in lines {
+        let mut attrs = Attrs::default();
+        let mut link: Option<&Arc<Hyperlink>> = None;
+        for cell in line {
+            if *cell.attrs() != attrs {
+                attrs = *cell.attrs();
+                output.push_str(&sgr(&attrs));
+            }
+            if !same_link(cell.hyperlink(), link) {
+                link = cell.hyperlink();
+                output.push_str(&osc8(link));
+            }
+            output.push_str(cell_text(cell));
+        }
+
+        // Close everything so the line feed does not carry a background
+        if link.is_some() {
+            output.push_str(&osc8(None));
+        }
+        if attrs != Attrs::default() {
+            output.push_str("\x1b[0m");
+        }
+        output.push('\n');
+    }
+    output
+}
+
+fn same_link(a: Option<&Arc<Hyperlink>>, b: Option<&Arc<Hyperlink>>) -> bool {
+    match (a, b) {
+        (Some(a), Some(b)) => Arc::ptr_eq(a, b) || a == b,
+        (None, None) => true,
+        _ => false,
+    }
+}
+
+/// Build the SGR sequence selecting `attrs` from a reset state
+fn sgr(attrs: &Attrs) -> String {
+    let mut params = vec!["0".to_string()];
+    if attrs.bold() {
+        params.push("1".into());
+    }
+    if attrs.dim() {
+        params.push("2".into());
+    }
+    if attrs.italic() {
+        params.push("3".into());
+    }
+    match attrs.underline_style() {
+        UnderlineStyle::None => {}
+        UnderlineStyle::Single => params.push("4".into()),
+        UnderlineStyle::Double => params.push("4:2".into()),
+        UnderlineStyle::Curly => params.push("4:3".into()),
+        UnderlineStyle::Dotted => params.push("4:4".into()),
+        UnderlineStyle::Dashed => params.push("4:5".into()),
+    }
+    if attrs.rapid_blink() {
+        params.push("6".into());
+    } else if attrs.blink() {
+        params.push("5".into());
+    }
+    if attrs.inverse() {
+        params.push("7".into());
+    }
+    if attrs.hidden() {
+        params.push("8".into());
+    }
+    if attrs.strikethrough() {
+        params.push("9".into());
+    }
+    if attrs.overline() {
+        params.push("53".into());
+    }
+    push_color(&mut params, attrs.fg, Some((30, 90)), 38);
+    push_color(&mut params, attrs.bg, Some((40, 100)), 48);
+    push_color(&mut params, attrs.underline_color, None, 58);
+    format!("\x1b[{}m", params.join(";"))
+}
+
+/// Add the SGR parameters for a color
+///
+/// `base` holds the codes for the 8 standard and 8 bright colors, when the
+/// attribute has them; `extended` introduces 256-color and RGB forms.
+fn push_color(params: &mut Vec<String>, color: Color, base: Option<(u8, u8)>, extended: u8) {
+    match (color, base) {
+        (Color::Default, _) => {}
+        (Color::Indexed(idx @ 0..=7), Some((normal, _))) => params.push((normal + idx).to_string()),
+        (Color::Indexed(idx @ 8..=15), Some((_, bright))) => {
+            params.push((bright + idx - 8).to_string())
+        }
+        (Color::Indexed(idx), _) => params.push(format!("{extended};5;{idx}")),
+        (Color::Rgb(r, g, b), _) => params.push(format!("{extended};2;{r};{g};{b}")),
+    }
+}
+
+/// Build an OSC 8 sequence opening `link`, or closing the current link
+fn osc8(link: Option<&Arc<Hyperlink>>) -> String {
+    match link {
+        Some(link) => match link.id() {
+            Some(id) => format!("\x1b]8;id={};{}\x1b\\", id, link.uri()),
+            None => format!("\x1b]8;;{}\x1b\\", link.uri()),
+        },
+        None => "\x1b]8;;\x1b\\".to_string(),
+    }
+}
+
+fn export_html(lines: &[Vec<&Cell>], palette: &Palette) -> String {
+    let mut output = String::new();
+    output.push_str("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<style>\n");
+    output.push_str(&format!(
+        "body {{ background-color: {}; color: {}; }}\n",
+        css_color(palette.background),
+        css_color(palette.foreground)
+    ));
+    output.push_str("pre { font-family: monospace; white-space: pre-wrap; }\n");
+    output.push_str("</style>\n</head>\n<body>\n<pre>");
+
+    for line in lines {
+        // Runs of cells sharing attributes and link become one element
+        let mut start = 0;
+        while start < line.len() {
+            let attrs = line[start].attrs();
+            let link = line[start].hyperlink();
+            let mut end = start + 1;
+            while end < line.len()
+                && line[end].attrs() == attrs
+                && same_link(line[end].hyperlink(), link)
+            {
+                end += 1;
+            }
+
+            let mut text = String::new();
+            for cell in &line[start..end] {
+                text.push_str(cell_text(cell));
+            }
+            let mut text = escape_html(&text);
+            let style = css_style(attrs, palette);
+            if !style.is_empty() {
+                text = format!("<span style=\"{style}\">{text}</span>");
+            }
+            if let Some(link) = link.filter(|link| is_html_safe_uri(link.uri())) {
+                text = format!("<a href=\"{}\">{text}</a>", escape_html(link.uri()));
+            }
+            output.push_str(&text);
+            start = end;
+        }
+        output.push('\n');
+    }
+
+    output.push_str("</pre>\n</body>\n</html>\n");
+    output
+}
+
+/// Build the inline CSS for `attrs` (empty for default attributes)
+fn css_style(attrs: &Attrs, palette: &Palette) -> String {
+    let resolve = |color: Color| match color {
+        Color::Default => None,
+        Color::Indexed(idx) => Some(palette.colors[idx as usize]),
+        Color::Rgb(r, g, b) => Some((r, g, b)),
+    };
+    let (mut fg, mut bg) = (resolve(attrs.fg), resolve(attrs.bg));
+    if attrs.inverse() {
+        (fg, bg) = (
+            Some(bg.unwrap_or(palette.background)),
+            Some(fg.unwrap_or(palette.foreground)),
+        );
+    }
+
+    let mut style = Vec::new();
+    if attrs.hidden() {
+        style.push("color: transparent".to_string());
+    } else if let Some(fg) = fg {
+        style.push(format!("color: {}", css_color(fg)));
+    }
+    if let Some(bg) = bg {
+        style.push(format!("background-color: {}", css_color(bg)));
+    }
+    if attrs.bold() {
+        style.push("font-weight: bold".to_string());
+    }
+    if attrs.dim() {
+        style.push("opacity: 0.5".to_string());
+    }
+    if attrs.italic() {
+        style.push("font-style: italic".to_string());
+    }
+
+    let mut decorations = Vec::new();
+    if attrs.underline() {
+        decorations.push("underline");
+    }
+    if attrs.strikethrough() {
+        decorations.push("line-through");
+    }
+    if attrs.overline() {
+        decorations.push("overline");
+    }
+    if !decorations.is_empty() {
+        style.push(format!("text-decoration-line: {}", decorations.join(" ")));
+    }
+    let decoration_style = match attrs.underline_style() {
+        UnderlineStyle::Double => Some("double"),
+        UnderlineStyle::Curly => Some("wavy"),
+        UnderlineStyle::Dotted => Some("dotted"),
+        UnderlineStyle::Dashed => Some("dashed"),
+        UnderlineStyle::None | UnderlineStyle::Single => None,
+    };
+    if let Some(decoration_style) = decoration_style {
+        style.push(format!("text-decoration-style: {decoration_style}"));
+    }
+    if let (true, Some(color)) = (attrs.underline(), resolve(attrs.underline_color)) {
+        style.push(format!("text-decoration-color: {}", css_color(color)));
+    }
+
+    style.join("; ")
+}
+
+/// Check that a link target may become an `<a href>` (no `javascript:` etc.)
+fn is_html_safe_uri(uri: &str) -> bool {
+    const SCHEMES: [&str; 4] = ["http", "https", "file", "mailto"];
+    uri.split_once(':').is_some_and(|(scheme, _)| {
+        SCHEMES
+            .iter()
+            .any(|allowed| scheme.eq_ignore_ascii_case(allowed))
+    })
+}
+
+fn css_color((r, g, b): Rgb) -> String {
+    format!("#{r:02x}{g:02x}{b:02x}")
+}
+
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn export(bytes: &[u8], cols: usize, format: ExportFormat) -> String {
+        let mut parser = Parser::new(4, cols, 100);
+        parser.process(bytes);
+        parser.screen().export(format)
+    }
+
+    #[test]
+    fn test_text_includes_scrollback_and_joins_wraps() {
+        let text = export(
+            b"one\r\ntwo\r\nthree\r\nfour\r\nfive\r\nabcdefghij  \r\n\r\n",
+            6,
+            ExportFormat::Text,
+        );
+        assert_eq!(text, "one\ntwo\nthree\nfour\nfive\nabcdefghij\n");
+    }
+
+    #[test]
+    fn test_ansi_round_trip_keeps_attrs() {
+        let input = b"plain \x1b[1;31mbold red\x1b[0m \x1b[4:3;58;5;9;48;2;1;2;3mcurly\x1b[0m\r\n\
+            \x1b]8;id=x;https://example.com\x1b\\link\x1b]8;;\x1b\\ \x1b[7;93mbright\x1b[0m\r\n";
+        let ansi = export(input, 40, ExportFormat::Ansi);
+
+        let mut original = Parser::new(4, 40, 100);
+        original.process(input);
+        let reloaded = import_ansi(&ansi, 4, 40, 100);
+
+        for row in 0..2 {
+            for col in 0..40 {
+                let a = original.screen().primary_grid().drawing_row(row);
+                let b = reloaded.screen().primary_grid().drawing_row(row);
+                let (a, b) = (a.unwrap().get(col).unwrap(), b.unwrap().get(col).unwrap());
+                assert_eq!(a.text(), b.text(), "text at {row},{col}");
+                assert_eq!(a.attrs(), b.attrs(), "attrs at {row},{col}");
+                assert_eq!(a.hyperlink(), b.hyperlink(), "link at {row},{col}");
+            }
+        }
+        assert_eq!(reloaded.screen().export(ExportFormat::Ansi), ansi);
+    }
+
+    #[test]
+    fn test_ansi_reflows_on_import() {
+        let ansi = export(b"abcdefghij\r\nend", 6, ExportFormat::Ansi);
+        assert_eq!(ansi, "abcdefghij\nend\n");
+
+        let reloaded = import_ansi(&ansi, 4, 4, 100);
+        assert_eq!(
+            reloaded.screen().export(ExportFormat::Text),
+            "abcdefghij\nend\n"
+        );
+        assert_eq!(reloaded.screen().cursor_pos().row, 3);
+    }
+
+    #[test]
+    fn test_html_escapes_and_styles() {
+        let html = export(b"<a&b> \x1b[1;32mok\x1b[0m", 20, ExportFormat::Html);
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("&lt;a&amp;b&gt; "));
+        assert!(html.contains("<span style=\"color: #00cd00; font-weight: bold\">ok</span>"));
+        assert!(html.contains("background-color: #000000; color: #e5e5e5"));
+    }
+
+    #[test]
+    fn test_html_links_only_for_safe_schemes() {
+        let html = export(
+            b"\x1b]8;;https://example.com/?a=1&b=2\x1b\\web\x1b]8;;\x1b\\ \
+              \x1b]8;;JavaScript:alert(1)\x1b\\bad\x1b]8;;\x1b\\",
+            40,
+            ExportFormat::Html,
+        );
+        assert!(html.contains("<a href=\"https://example.com/?a=1&amp;b=2\">web</a>"));
+        assert!(html.contains(" bad\n"));
+        assert!(!html.contains("alert"));
+    }
+}
@@ -1,6 +1,8 @@
 //! Terminal grid with VecDeque-based scrollback (mprocs architecture)
 
+use crate::termtui::cell::Cell;
 use crate::termtui::row::Row;
+use crate::termtui::shell_integration::RowMarks;
 use crate::termtui::size::Size;
 use std::collections::VecDeque;
 
@@ -131,6 +133,11 @@ impl Grid {
         self.scroll_bottom = bottom.min(self.size.rows).max(self.scroll_top + 1);
     }
 
+    /// Get scroll region as (top, bottom), bottom exclusive
+    pub fn scroll_region(&self) -> (u16, u16) {
+        (self.scroll_top, self.scroll_bottom)
+    }
+
     /// Reset scroll region to full screen
     pub fn reset_scroll_region(&mut self) {
         self.scroll_top = 0;
@@ -144,6 +151,14 @@ impl Grid {
         self.rows.get(idx)
     }
 
+    /// Get a row by its coordinate (row 0 is the top of the screen)
+    ///
+    /// Scrollback rows have negative coordinates.
+    pub fn row_at(&self, y: i32) -> Option<&Row> {
+        let idx = usize::try_from(self.row0() as i32 + y).ok()?;
+        self.rows.get(idx)
+    }
+
     /// Get a drawing row (for writing, ignores scrollback offset)
     pub fn drawing_row(&self, row: u16) -> Option<&Row> {
         let idx = self.row0() + row as usize;
@@ -171,44 +186,76 @@ impl Grid {
     }
 
     /// Scroll up within scroll region
+    ///
+    /// When the region covers the whole screen, rows scrolled off the top are
+    /// kept in the scrollback buffer. Rows scrolled out of a partial region
+    /// are discarded.
     pub fn scroll_up(&mut self, count: usize) {
-        for _ in 0..count {
-            // If scroll region is full screen, add to scrollback
-            if self.scroll_top == 0 && self.scroll_bottom == self.size.rows {
-                // Add new row at the end
-                self.rows.push_back(Row::new(self.size.cols));
+        if self.scroll_top == 0 && self.scroll_bottom == self.size.rows {
+            let max_rows = self.size.rows as usize + self.scrollback_len;
+            for _ in 0..count {
+                // Reuse the oldest row once the scrollback is full
+                let row = if self.rows.len() >= max_rows {
+                    self.rows.pop_front().map(|mut row| {
+                        row.clear();
+                        row
+                    })
+                } else {
+                    None
+                };
+                self.rows
+                    .push_back(row.unwrap_or_else(|| Row::new(self.size.cols)));
 
                 // Trim scrollback if needed
-                while self.rows.len() > self.size.rows as usize + self.scrollback_len {
+                while self.rows.len() > max_rows {
                     self.rows.pop_front();
                 }
-            } else {
-                // Scroll within region only
-                let top_idx = self.row0() + self.scroll_top as usize;
-                let bottom_idx = self.row0() + self.scroll_bottom as usize - 1;
-
-                if top_idx < self.rows.len() && bottom_idx < self.rows.len() {
-                    // Remove top row of region
-                    self.rows.remove(top_idx);
-                    // Insert new row at bottom of region
-                    self.rows.insert(bottom_idx, Row::new(self.size.cols));
-                }
             }
+        } else {
+            self.delete_lines(self.scroll_top, count);
         }
     }
 
     /// Scroll down within scroll region
     pub fn scroll_down(&mut self, count: usize) {
+        self.insert_lines(self.scroll_top, count);
+    }
+
+    /// Insert blank lines at `row`, pushing the rows below it down
+    ///
+    /// Only rows inside the scroll region move; rows pushed past the bottom
+    /// margin are discarded. Does nothing if `row` is outside the region.
+    pub fn insert_lines(&mut self, row: u16, count: usize) {
+        if row < self.scroll_top || row >= self.scroll_bottom {
+            return;
+        }
+
+        let count = count.min((self.scroll_bottom - row) as usize);
+        let at = self.row0() + row as usize;
+        let bottom = self.row0() + self.scroll_bottom as usize - 1;
+
         for _ in 0..count {
-            let top_idx = self.row0() + self.scroll_top as usize;
-            let bottom_idx = self.row0() + self.scroll_bottom as usize - 1;
-
-            if top_idx < self.rows.len() && bottom_idx < self.rows.len() {
-                // Remove bottom row of region
-                self.rows.remove(bottom_idx);
-                // Insert new row at top of region
-                self.rows.insert(top_idx, Row::new(self.size.cols));
-            }
+            self.rows.remove(bottom);
+            self.rows.insert(at, Row::new(self.size.cols));
+        }
+    }
+
+    /// Delete lines at `row`, pulling the rows below it up
+    ///
+    /// Only rows inside the scroll region move; blank rows are inserted at the
+    /// bottom margin. Does nothing if `row` is outside the region.
+    pub fn delete_lines(&mut self, row: u16, count: usize) {
+        if row < self.scroll_top || row >= self.scroll_bottom {
+            return;
+        }
+
+        let count = count.min((self.scroll_bottom - row) as usize);
+        let at = self.row0() + row as usize;
+        let bottom = self.row0() + self.scroll_bottom as usize - 1;
+
+        for _ in 0..count {
+            self.rows.remove(at);
+            self.rows.insert(bottom, Row::new(self.size.cols));
         }
     }
 
@@ -276,7 +323,81 @@ impl Grid {
         // Clamp cursor and scroll region
         self.pos.col = self.pos.col.min(new_size.cols.saturating_sub(1));
         self.pos.row = self.pos.row.min(new_size.rows.saturating_sub(1));
-        self.scroll_bottom = new_size.rows;
+        self.reset_scroll_region();
+    }
+
+    /// Resize the grid, reflowing soft-wrapped lines to the new width
+    ///
+    /// Rows joined by `wrapped` form one logical line, which is split again
+    /// at the new width in both the screen and the scrollback. The cursor
+    /// stays on the same character of its logical line. `pending_wrap` tells
+    /// whether the cursor sits past the last column; the pending wrap state
+    /// at the new width is returned.
+    pub fn resize_reflow(&mut self, new_size: Size, pending_wrap: bool) -> bool {
+        if new_size.cols == 0 || new_size.rows == 0 {
+            self.resize(new_size);
+            return false;
+        }
+
+        let cols = new_size.cols;
+        let screen_rows = new_size.rows as usize;
+        let cursor_idx = self.row0() + self.pos.row as usize;
+
+        let mut rows: Vec<Row> = Vec::with_capacity(self.rows.len());
+        let mut cursor = None;
+        let mut line = LogicalLine::default();
+
+        for (i, row) in std::mem::take(&mut self.rows).into_iter().enumerate() {
+            let row_cursor = (i == cursor_idx).then_some((self.pos.col, pending_wrap));
+            line.push_row(&row, row_cursor);
+            if !row.wrapped() {
+                cursor = std::mem::take(&mut line).layout(cols, &mut rows).or(cursor);
+            }
+        }
+        if !line.row_starts.is_empty() {
+            cursor = line.layout(cols, &mut rows).or(cursor);
+        }
+
+        let (mut cursor_row, cursor_col, pending_wrap) = cursor.unwrap_or((0, 0, false));
+
+        // Drop blank rows below the cursor, then keep the cursor on screen
+        let blank = Cell::new();
+        while rows.len() > cursor_row + 1
+            && rows
+                .last()
+                .is_some_and(|row| row.marks().is_empty() && row.cells().all(|cell| *cell == blank))
+        {
+            rows.pop();
+        }
+        rows.truncate(cursor_row + screen_rows);
+        while rows.len() < screen_rows {
+            rows.push(Row::new(cols));
+        }
+
+        // Enforce the scrollback limit
+        let excess = rows.len().saturating_sub(screen_rows + self.scrollback_len);
+        rows.drain(..excess);
+        cursor_row -= excess;
+
+        self.rows = rows.into();
+        self.size = new_size;
+
+        let row0 = self.row0();
+        self.pos = Pos::new(cursor_col, (cursor_row - row0) as u16);
+        self.used_rows = self
+            .used_rows
+            .min(screen_rows)
+            .max(self.pos.row as usize + 1);
+        self.scrollback_offset = self.scrollback_offset.min(row0);
+        if let Some(saved) = self.saved_pos {
+            self.saved_pos = Some(Pos::new(
+                saved.col.min(cols - 1),
+                saved.row.min(new_size.rows - 1),
+            ));
+        }
+        self.reset_scroll_region();
+
+        pending_wrap
     }
 
     /// Get selected text from coordinates
@@ -326,6 +447,34 @@ impl Grid {
             .join("\n")
     }
 
+    /// Get the text of a rectangle of cells, one line per row
+    ///
+    /// Columns `left..right` are taken from every row, soft wraps included.
+    pub fn get_block_text(&self, left: i32, top: i32, right: i32, bottom: i32) -> String {
+        let left = left.max(0) as u16;
+        let right = right.max(0) as u16;
+        (top..=bottom)
+            .filter_map(|y| self.row_at(y))
+            .map(|row| {
+                let mut line = String::new();
+                row.write_contents(&mut line, left.min(row.width()), right.min(row.width()));
+                line.trim_end().to_string()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Get the text of the drawing rows, one line per row
+    ///
+    /// Trailing whitespace and trailing empty rows are removed.
+    pub fn contents(&self) -> String {
+        let lines = self
+            .drawing_rows()
+            .map(|row| row.contents_trimmed())
+            .collect::<Vec<_>>();
+        lines.join("\n").trim_end().to_string()
+    }
+
     /// Iterate over visible rows
     pub fn visible_rows(&self) -> impl Iterator<Item = &Row> {
         let start = self.row0().saturating_sub(self.scrollback_offset);
@@ -333,6 +482,17 @@ impl Grid {
         self.rows.iter().skip(start).take(end - start)
     }
 
+    /// Iterate over all rows (scrollback and screen) with their row coordinate
+    ///
+    /// Row 0 is the top of the screen; scrollback rows are negative.
+    pub fn all_rows(&self) -> impl Iterator<Item = (i32, &Row)> {
+        let row0 = self.row0() as i32;
+        self.rows
+            .iter()
+            .enumerate()
+            .map(move |(i, row)| (i as i32 - row0, row))
+    }
+
     /// Iterate over drawing rows (ignoring scrollback offset)
     pub fn drawing_rows(&self) -> impl Iterator<Item = &Row> {
         let start = self.row0();
@@ -340,6 +500,102 @@ impl Grid {
     }
 }
 
+/// A logical line gathered from soft-wrapped rows during reflow
+#[derive(Default)]
+struct LogicalLine {
+    /// Cells of the line, without wide character continuations
+    cells: Vec<Cell>,
+    /// Cell index where each source row started, with its markers
+    row_starts: Vec<(usize, RowMarks)>,
+    /// Cursor offset into `cells` and whether a wrap was pending
+    cursor: Option<(usize, bool)>,
+}
+
+impl LogicalLine {
+    /// Append a row, noting the cursor column if it is on this row
+    fn push_row(&mut self, row: &Row, cursor: Option<(u16, bool)>) {
+        self.row_starts.push((self.cells.len(), *row.marks()));
+
+        for (col, cell) in row.cells().enumerate() {
+            if let Some((cursor_col, pending)) = cursor {
+                if col == cursor_col as usize {
+                    // A cursor on a continuation belongs to the wide character
+                    let idx = if cell.is_wide_continuation() {
+                        self.cells.len().saturating_sub(1)
+                    } else {
+                        self.cells.len()
+                    };
+                    self.cursor = Some((idx + pending as usize, pending));
+                }
+            }
+            if !cell.is_wide_continuation() {
+                self.cells.push(cell.clone());
+            }
+        }
+
+        if cursor.is_some() && self.cursor.is_none() {
+            self.cursor = Some((self.cells.len(), false));
+        }
+    }
+
+    /// Split the line into rows of `cols` cells, appending them to `out`
+    ///
+    /// Returns the cursor as (row index in `out`, column, pending wrap) if it
+    /// is on this line.
+    fn layout(mut self, cols: u16, out: &mut Vec<Row>) -> Option<(usize, u16, bool)> {
+        // Trailing blanks are not part of the line, unless the cursor is past them
+        let keep = self.cursor.map(|(offset, _)| offset).unwrap_or(0);
+        let blank = Cell::new();
+        while self.cells.len() > keep && self.cells.last() == Some(&blank) {
+            self.cells.pop();
+        }
+
+        let first = out.len();
+        // (row offset, column, width) of each cell
+        let mut positions = Vec::with_capacity(self.cells.len());
+        let mut row = Row::new(cols);
+        let (mut r, mut c) = (0, 0);
+
+        for cell in self.cells {
+            if c >= cols {
+                row.set_wrapped(true);
+                out.push(std::mem::replace(&mut row, Row::new(cols)));
+                r += 1;
+                c = 0;
+            }
+            // A wide character in the last column is cut off, as when printed
+            let width = cell.width().max(1) as u16;
+            positions.push((r, c, width));
+            if width == 2 && c + 1 < cols {
+                let mut continuation = cell.clone();
+                continuation.set_wide_continuation();
+                *row.get_mut(c + 1).unwrap() = continuation;
+            }
+            *row.get_mut(c).unwrap() = cell;
+            c += width;
+        }
+        out.push(row);
+
+        for (start, marks) in self.row_starts {
+            let r = positions.get(start).map_or(r, |&(r, _, _)| r);
+            out[first + r].marks_mut().merge(marks);
+        }
+
+        let (offset, pending) = self.cursor?;
+        if !pending {
+            if let Some(&(r, c, _)) = positions.get(offset) {
+                return Some((first + r, c, false));
+            }
+        }
+        // The cursor follows the last cell it is after
+        Some(match offset.checked_sub(1).and_then(|i| positions.get(i)) {
+            None => (first, 0, false),
+            Some(&(r, c, width)) if c + width >= cols => (first + r, cols - 1, true),
+            Some(&(r, c, width)) => (first + r, c + width, false),
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -420,6 +676,25 @@ mod tests {
         assert_eq!(text, "Hello");
     }
 
+    #[test]
+    fn test_grid_get_block_text() {
+        let mut grid = Grid::new(Size::new(10, 3), 100);
+        for (y, line) in ["abcdef", "ghijkl", "mn"].iter().enumerate() {
+            if let Some(row) = grid.drawing_row_mut(y as u16) {
+                for (i, c) in line.chars().enumerate() {
+                    if let Some(cell) = row.get_mut(i as u16) {
+                        cell.set_char(c);
+                    }
+                }
+            }
+        }
+
+        assert_eq!(grid.get_block_text(1, 0, 4, 2), "bcd\nhij\nn");
+        assert!(grid.row_at(2).is_some());
+        assert!(grid.row_at(3).is_none());
+        assert!(grid.row_at(-1).is_none());
+    }
+
     #[test]
     fn test_grid_resize() {
         let mut grid = Grid::new(Size::new(80, 24), 100);
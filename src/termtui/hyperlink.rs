@@ -0,0 +1,292 @@
+//! Hyperlinks (OSC 8) and link detection in terminal text
+
+use crate::termtui::row::Row;
+use regex::Regex;
+use std::ops::Range;
+use std::sync::OnceLock;
+
+/// An explicit hyperlink set by the application with OSC 8
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Hyperlink {
+    /// Optional link id (cells sharing an id belong to the same link)
+    id: Option<String>,
+    /// Link target
+    uri: String,
+}
+
+impl Hyperlink {
+    /// Create a hyperlink without an id
+    pub fn new(uri: impl Into<String>) -> Self {
+        Self {
+            id: None,
+            uri: uri.into(),
+        }
+    }
+
+    /// Create a hyperlink with an explicit id
+    pub fn with_id(uri: impl Into<String>, id: impl Into<String>) -> Self {
+        Self {
+            id: Some(id.into()),
+            uri: uri.into(),
+        }
+    }
+
+    /// Get the link id, if the application set one
+    pub fn id(&self) -> Option<&str> {
+        self.id.as_deref()
+    }
+
+    /// Get the link target
+    pub fn uri(&self) -> &str {
+        &self.uri
+    }
+}
+
+impl From<&termwiz::hyperlink::Hyperlink> for Hyperlink {
+    fn from(link: &termwiz::hyperlink::Hyperlink) -> Self {
+        Self {
+            id: link.params().get("id").cloned(),
+            uri: link.uri().to_string(),
+        }
+    }
+}
+
+/// What a link points at
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LinkTarget {
+    /// Explicit OSC 8 hyperlink
+    Hyperlink(Hyperlink),
+    /// URL detected in plain text
+    Url(String),
+    /// `path:line[:col]` reference detected in plain text
+    FileLocation {
+        /// File path as printed
+        path: String,
+        /// Line number (1-based)
+        line: u32,
+        /// Column number (1-based), if present
+        column: Option<u32>,
+    },
+}
+
+/// A link detected in a visible row
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DetectedLink {
+    /// Visible row index
+    pub row: u16,
+    /// First column of the link
+    pub start_col: u16,
+    /// Column just past the end of the link
+    pub end_col: u16,
+    /// Link target
+    pub target: LinkTarget,
+}
+
+impl DetectedLink {
+    /// Check if the link covers the given column
+    pub fn contains(&self, col: u16) -> bool {
+        col >= self.start_col && col < self.end_col
+    }
+}
+
+fn url_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r#"\b(?:https?|ftp|file)://[^\s<>"'`]+"#).unwrap())
+}
+
+fn file_location_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(
+            r"(?P<path>(?:[\w.~+@-]*/)+[\w.+@-]+|[\w+@-]+\.[\w.+@-]*[\w+@-]):(?P<line>\d+)(?::(?P<col>\d+))?",
+        )
+        .unwrap()
+    })
+}
+
+/// Trim trailing punctuation that usually ends a sentence rather than a URL
+fn trim_url(url: &str) -> &str {
+    let mut url = url;
+    loop {
+        let Some(last) = url.chars().last() else {
+            return url;
+        };
+        let unbalanced = match last {
+            ')' => url.matches('(').count() < url.matches(')').count(),
+            ']' => url.matches('[').count() < url.matches(']').count(),
+            '.' | ',' | ';' | ':' | '!' | '?' | '}' | '>' => true,
+            _ => false,
+        };
+        if !unbalanced {
+            return url;
+        }
+        url = &url[..url.len() - last.len_utf8()];
+    }
+}
+
+/// Detect URLs and `path:line[:col]` references in a line of text
+///
+/// Returns byte ranges into `text` with their targets, ordered by position.
+pub fn detect_links(text: &str) -> Vec<(Range<usize>, LinkTarget)> {
+    let mut links: Vec<(Range<usize>, LinkTarget)> = url_regex()
+        .find_iter(text)
+        .map(|m| {
+            let url = trim_url(m.as_str());
+            (
+                m.start()..m.start() + url.len(),
+                LinkTarget::Url(url.to_string()),
+            )
+        })
+        .collect();
+
+    for caps in file_location_regex().captures_iter(text) {
+        let whole = caps.get(0).unwrap();
+        // Host:port inside a URL looks like a file location; URLs win
+        if links
+            .iter()
+            .any(|(range, _)| whole.start() < range.end && range.start < whole.end())
+        {
+            continue;
+        }
+        let Ok(line) = caps["line"].parse() else {
+            continue;
+        };
+        let column = caps.name("col").and_then(|c| c.as_str().parse().ok());
+        links.push((
+            whole.range(),
+            LinkTarget::FileLocation {
+                path: caps["path"].to_string(),
+                line,
+                column,
+            },
+        ));
+    }
+
+    links.sort_by_key(|(range, _)| range.start);
+    links
+}
+
+/// Detect links in a row, mapping byte ranges back to columns
+pub(crate) fn detect_row_links(row: &Row, row_idx: u16) -> Vec<DetectedLink> {
+    let mut text = String::new();
+    // (byte offset, column) for the start of each cell's text
+    let mut offsets = Vec::new();
+
+    for (col, cell) in row.cells().enumerate() {
+        if cell.is_wide_continuation() {
+            continue;
+        }
+        offsets.push((text.len(), col as u16));
+        text.push_str(cell.text());
+    }
+
+    let col_at = |byte: usize| -> u16 {
+        match offsets.binary_search_by_key(&byte, |&(offset, _)| offset) {
+            Ok(idx) => offsets[idx].1,
+            Err(idx) => offsets[idx.saturating_sub(1)].1,
+        }
+    };
+
+    detect_links(&text)
+        .into_iter()
+        .map(|(range, target)| {
+            let last_col = col_at(range.end.saturating_sub(1));
+            let last_width = row.get(last_col).map(|c| c.width()).unwrap_or(1) as u16;
+            DetectedLink {
+                row: row_idx,
+                start_col: col_at(range.start),
+                end_col: last_col + last_width,
+                target,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn targets(text: &str) -> Vec<(String, LinkTarget)> {
+        detect_links(text)
+            .into_iter()
+            .map(|(range, target)| (text[range].to_string(), target))
+            .collect()
+    }
+
+    #[test]
+    fn test_detect_url() {
+        let links = targets("see https://example.com/docs?q=1 for details");
+        assert_eq!(
+            links,
+            vec![(
+                "https://example.com/docs?q=1".to_string(),
+                LinkTarget::Url("https://example.com/docs?q=1".to_string())
+            )]
+        );
+    }
+
+    #[test]
+    fn test_url_trailing_punctuation() {
+        let links = targets(
+            "(docs at https://example.com/a.) and https://en.wikipedia.org/wiki/Rust_(language).",
+        );
+        assert_eq!(links[0].0, "https://example.com/a");
+        assert_eq!(links[1].0, "https://en.wikipedia.org/wiki/Rust_(language)");
+    }
+
+    #[test]
+    fn test_detect_file_location() {
+        let links = targets("  --> src/termtui/screen.rs:120:9");
+        assert_eq!(
+            links,
+            vec![(
+                "src/termtui/screen.rs:120:9".to_string(),
+                LinkTarget::FileLocation {
+                    path: "src/termtui/screen.rs".to_string(),
+                    line: 120,
+                    column: Some(9),
+                }
+            )]
+        );
+
+        let links = targets("main.c:42: warning: unused variable");
+        assert_eq!(
+            links[0].1,
+            LinkTarget::FileLocation {
+                path: "main.c".to_string(),
+                line: 42,
+                column: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_url_port_is_not_a_file_location() {
+        let links = targets("http://localhost.dev:8080/index.html");
+        assert_eq!(links.len(), 1);
+        assert!(matches!(links[0].1, LinkTarget::Url(_)));
+    }
+
+    #[test]
+    fn test_no_false_positives() {
+        assert!(targets("12:30 meeting, ratio 3:2, key: value").is_empty());
+    }
+
+    #[test]
+    fn test_detect_row_links_columns() {
+        let mut row = Row::new(40);
+        for (col, ch) in "你 x.rs:3 ok".chars().enumerate() {
+            // Lay out the wide char over two columns
+            let col = if col == 0 { 0 } else { col as u16 + 1 };
+            row.get_mut(col).unwrap().set_text(ch.to_string());
+        }
+        row.get_mut(1).unwrap().set_wide_continuation();
+
+        let links = detect_row_links(&row, 2);
+        assert_eq!(links.len(), 1);
+        assert_eq!(links[0].row, 2);
+        assert_eq!(links[0].start_col, 3);
+        assert_eq!(links[0].end_col, 9);
+        assert!(links[0].contains(3) && links[0].contains(8) && !links[0].contains(9));
+    }
+}
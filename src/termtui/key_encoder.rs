@@ -0,0 +1,469 @@
+//! xterm-compatible key encoding (with optional kitty keyboard protocol)
+
+use crossterm::event::{KeyCode, KeyEvent, KeyEventState, KeyModifiers};
+
+/// Kitty keyboard flag: disambiguate escape codes
+pub const KITTY_DISAMBIGUATE_ESCAPE_CODES: u16 = 1;
+/// Kitty keyboard flag: report event types (press/repeat/release)
+pub const KITTY_REPORT_EVENT_TYPES: u16 = 2;
+/// Kitty keyboard flag: report alternate keys
+pub const KITTY_REPORT_ALTERNATE_KEYS: u16 = 4;
+/// Kitty keyboard flag: report all keys as escape codes
+pub const KITTY_REPORT_ALL_KEYS_AS_ESCAPE_CODES: u16 = 8;
+/// Kitty keyboard flag: report associated text
+pub const KITTY_REPORT_ASSOCIATED_TEXT: u16 = 16;
+
+/// Keyboard modes requested by the child program that affect key encoding
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct KeyboardModes {
+    /// Application cursor keys (DECCKM): arrows send `ESC O A` instead of `ESC [ A`
+    pub application_cursor: bool,
+    /// Application keypad (DECKPAM): keypad keys send `ESC O` sequences
+    pub application_keypad: bool,
+    /// Active kitty keyboard protocol flags (0 = legacy encoding)
+    pub kitty_flags: u16,
+}
+
+/// Encode a key event as the bytes an xterm-compatible terminal would send
+///
+/// Returns an empty string for keys that have no terminal representation.
+pub fn encode_key(key: &KeyEvent, modes: KeyboardModes) -> String {
+    let mods = key.modifiers;
+    let param = modifier_param(mods);
+
+    if modes.kitty_flags != 0 {
+        if let Some(seq) = encode_kitty(key, modes.kitty_flags) {
+            return seq;
+        }
+    }
+
+    if key.state.contains(KeyEventState::KEYPAD) && modes.application_keypad {
+        if let Some(seq) = encode_keypad(key.code) {
+            return seq;
+        }
+    }
+
+    match key.code {
+        KeyCode::Char(c) => encode_char(c, mods),
+        KeyCode::Enter => alt_prefix(mods, "\r"),
+        KeyCode::Backspace => {
+            if mods.contains(KeyModifiers::CONTROL) {
+                alt_prefix(mods, "\x08")
+            } else {
+                alt_prefix(mods, "\x7f")
+            }
+        }
+        KeyCode::Tab if mods.contains(KeyModifiers::SHIFT) => "\x1b[Z".to_string(),
+        KeyCode::Tab => alt_prefix(mods, "\t"),
+        KeyCode::BackTab => "\x1b[Z".to_string(),
+        KeyCode::Esc => alt_prefix(mods, "\x1b"),
+        KeyCode::Up => cursor_key('A', param, modes),
+        KeyCode::Down => cursor_key('B', param, modes),
+        KeyCode::Right => cursor_key('C', param, modes),
+        KeyCode::Left => cursor_key('D', param, modes),
+        KeyCode::Home => cursor_key('H', param, modes),
+        KeyCode::End => cursor_key('F', param, modes),
+        KeyCode::Insert => tilde_key(2, param),
+        KeyCode::Delete => tilde_key(3, param),
+        KeyCode::PageUp => tilde_key(5, param),
+        KeyCode::PageDown => tilde_key(6, param),
+        KeyCode::F(n) => function_key(n, param),
+        _ => String::new(),
+    }
+}
+
+/// xterm modifier parameter (1 + shift + 2*alt + 4*ctrl + 8*meta), or `None`
+/// when no modifier is held
+fn modifier_param(mods: KeyModifiers) -> Option<u8> {
+    let mut value = 0;
+    if mods.contains(KeyModifiers::SHIFT) {
+        value |= 1;
+    }
+    if mods.contains(KeyModifiers::ALT) {
+        value |= 2;
+    }
+    if mods.contains(KeyModifiers::CONTROL) {
+        value |= 4;
+    }
+    if mods.contains(KeyModifiers::META) || mods.contains(KeyModifiers::SUPER) {
+        value |= 8;
+    }
+    (value != 0).then_some(value + 1)
+}
+
+/// Prefix a sequence with ESC when Alt is held
+fn alt_prefix(mods: KeyModifiers, seq: &str) -> String {
+    if mods.contains(KeyModifiers::ALT) {
+        format!("\x1b{}", seq)
+    } else {
+        seq.to_string()
+    }
+}
+
+/// Encode a printable character, applying Ctrl and Alt
+fn encode_char(c: char, mods: KeyModifiers) -> String {
+    let text = if mods.contains(KeyModifiers::CONTROL) {
+        match c.to_ascii_lowercase() {
+            'a'..='z' => ((c.to_ascii_lowercase() as u8 - b'a' + 1) as char).to_string(),
+            '@' | ' ' | '2' => "\x00".to_string(),
+            '[' | '3' => "\x1b".to_string(),
+            '\\' | '4' => "\x1c".to_string(),
+            ']' | '5' => "\x1d".to_string(),
+            '^' | '6' => "\x1e".to_string(),
+            '_' | '7' | '/' => "\x1f".to_string(),
+            '?' | '8' => "\x7f".to_string(),
+            _ => c.to_string(),
+        }
+    } else {
+        c.to_string()
+    };
+    alt_prefix(mods, &text)
+}
+
+/// Encode an arrow/Home/End key, honoring DECCKM
+fn cursor_key(final_byte: char, param: Option<u8>, modes: KeyboardModes) -> String {
+    match param {
+        Some(m) => format!("\x1b[1;{}{}", m, final_byte),
+        None if modes.application_cursor => format!("\x1bO{}", final_byte),
+        None => format!("\x1b[{}", final_byte),
+    }
+}
+
+/// Encode a `CSI n ~` key
+fn tilde_key(code: u8, param: Option<u8>) -> String {
+    match param {
+        Some(m) => format!("\x1b[{};{}~", code, m),
+        None => format!("\x1b[{}~", code),
+    }
+}
+
+/// Encode a function key (F1-F20)
+fn function_key(n: u8, param: Option<u8>) -> String {
+    let code = match n {
+        1..=4 => {
+            let final_byte = (b'P' + n - 1) as char;
+            return match param {
+                Some(m) => format!("\x1b[1;{}{}", m, final_byte),
+                None => format!("\x1bO{}", final_byte),
+            };
+        }
+        5 => 15,
+        6 => 17,
+        7 => 18,
+        8 => 19,
+        9 => 20,
+        10 => 21,
+        11 => 23,
+        12 => 24,
+        13 => 25,
+        14 => 26,
+        15 => 28,
+        16 => 29,
+        17 => 31,
+        18 => 32,
+        19 => 33,
+        20 => 34,
+        _ => return String::new(),
+    };
+    tilde_key(code, param)
+}
+
+/// Encode a keypad key in application keypad mode (DECKPAM)
+fn encode_keypad(code: KeyCode) -> Option<String> {
+    let final_byte = match code {
+        KeyCode::Char(c @ '0'..='9') => (b'p' + (c as u8 - b'0')) as char,
+        KeyCode::Char('.') => 'n',
+        KeyCode::Char('+') => 'k',
+        KeyCode::Char('-') => 'm',
+        KeyCode::Char('*') => 'j',
+        KeyCode::Char('/') => 'o',
+        KeyCode::Char('=') => 'X',
+        KeyCode::Enter => 'M',
+        _ => return None,
+    };
+    Some(format!("\x1bO{}", final_byte))
+}
+
+/// Encode a key with the kitty keyboard protocol (`CSI code ; mods u`)
+///
+/// Returns `None` for keys that keep their legacy encoding under the active
+/// flags (functional keys such as arrows and F-keys always do).
+fn encode_kitty(key: &KeyEvent, flags: u16) -> Option<String> {
+    let mods = key.modifiers;
+    let all_keys = flags & KITTY_REPORT_ALL_KEYS_AS_ESCAPE_CODES != 0;
+    let has_mods = mods.intersects(
+        KeyModifiers::ALT | KeyModifiers::CONTROL | KeyModifiers::SUPER | KeyModifiers::META,
+    );
+
+    let code = match key.code {
+        KeyCode::Esc => 27,
+        KeyCode::Enter if all_keys || has_mods => 13,
+        KeyCode::Tab if all_keys || has_mods => 9,
+        KeyCode::Backspace if all_keys || has_mods => 127,
+        KeyCode::Char(c) if all_keys || has_mods => c.to_lowercase().next().unwrap_or(c) as u32,
+        _ => return None,
+    };
+
+    let mut value = 0;
+    if mods.contains(KeyModifiers::SHIFT) {
+        value |= 1;
+    }
+    if mods.contains(KeyModifiers::ALT) {
+        value |= 2;
+    }
+    if mods.contains(KeyModifiers::CONTROL) {
+        value |= 4;
+    }
+    if mods.contains(KeyModifiers::SUPER) {
+        value |= 8;
+    }
+    if mods.contains(KeyModifiers::HYPER) {
+        value |= 16;
+    }
+    if mods.contains(KeyModifiers::META) {
+        value |= 32;
+    }
+
+    Some(if value == 0 {
+        format!("\x1b[{}u", code)
+    } else {
+        format!("\x1b[{};{}u", code, value + 1)
+    })
+}
+
+/// Bracketed paste start marker (`?2004`)
+const PASTE_START: &str = "\x1b[200~";
+/// Bracketed paste end marker (`?2004`)
+const PASTE_END: &str = "\x1b[201~";
+
+/// Encode pasted text as the bytes an xterm-compatible terminal would send
+///
+/// Line endings are normalized to `\r`, as if typed. When `bracketed` is set the
+/// text is wrapped in `ESC[200~ … ESC[201~`, and any paste markers embedded in
+/// the text are stripped so pasted content cannot end the paste early.
+pub fn encode_paste(text: &str, bracketed: bool) -> String {
+    let mut body = text.replace("\r\n", "\r").replace('\n', "\r");
+
+    if !bracketed {
+        return body;
+    }
+
+    // Stripping one marker can splice together another, so repeat until stable
+    while body.contains(PASTE_START) || body.contains(PASTE_END) {
+        body = body.replace(PASTE_START, "").replace(PASTE_END, "");
+    }
+
+    format!("{}{}{}", PASTE_START, body, PASTE_END)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encode(code: KeyCode, mods: KeyModifiers) -> String {
+        encode_key(&KeyEvent::new(code, mods), KeyboardModes::default())
+    }
+
+    fn encode_with(code: KeyCode, mods: KeyModifiers, modes: KeyboardModes) -> String {
+        encode_key(&KeyEvent::new(code, mods), modes)
+    }
+
+    #[test]
+    fn test_plain_keys() {
+        assert_eq!(encode(KeyCode::Char('a'), KeyModifiers::NONE), "a");
+        assert_eq!(encode(KeyCode::Enter, KeyModifiers::NONE), "\r");
+        assert_eq!(encode(KeyCode::Tab, KeyModifiers::NONE), "\t");
+        assert_eq!(encode(KeyCode::Backspace, KeyModifiers::NONE), "\x7f");
+        assert_eq!(encode(KeyCode::Esc, KeyModifiers::NONE), "\x1b");
+    }
+
+    #[test]
+    fn test_ctrl_and_alt_chars() {
+        assert_eq!(encode(KeyCode::Char('c'), KeyModifiers::CONTROL), "\x03");
+        assert_eq!(encode(KeyCode::Char(' '), KeyModifiers::CONTROL), "\x00");
+        assert_eq!(encode(KeyCode::Char('/'), KeyModifiers::CONTROL), "\x1f");
+        assert_eq!(encode(KeyCode::Char('b'), KeyModifiers::ALT), "\x1bb");
+        assert_eq!(
+            encode(
+                KeyCode::Char('x'),
+                KeyModifiers::CONTROL | KeyModifiers::ALT
+            ),
+            "\x1b\x18"
+        );
+        assert_eq!(encode(KeyCode::Backspace, KeyModifiers::ALT), "\x1b\x7f");
+        assert_eq!(encode(KeyCode::Backspace, KeyModifiers::CONTROL), "\x08");
+    }
+
+    #[test]
+    fn test_backtab() {
+        assert_eq!(encode(KeyCode::BackTab, KeyModifiers::SHIFT), "\x1b[Z");
+        assert_eq!(encode(KeyCode::Tab, KeyModifiers::SHIFT), "\x1b[Z");
+    }
+
+    #[test]
+    fn test_arrows_normal_and_application_mode() {
+        assert_eq!(encode(KeyCode::Up, KeyModifiers::NONE), "\x1b[A");
+        assert_eq!(encode(KeyCode::Left, KeyModifiers::NONE), "\x1b[D");
+
+        let app = KeyboardModes {
+            application_cursor: true,
+            ..Default::default()
+        };
+        assert_eq!(encode_with(KeyCode::Up, KeyModifiers::NONE, app), "\x1bOA");
+        assert_eq!(
+            encode_with(KeyCode::Home, KeyModifiers::NONE, app),
+            "\x1bOH"
+        );
+
+        // Modified arrows always use the CSI form
+        assert_eq!(
+            encode_with(KeyCode::Up, KeyModifiers::CONTROL, app),
+            "\x1b[1;5A"
+        );
+    }
+
+    #[test]
+    fn test_modified_arrows() {
+        assert_eq!(encode(KeyCode::Right, KeyModifiers::SHIFT), "\x1b[1;2C");
+        assert_eq!(encode(KeyCode::Left, KeyModifiers::ALT), "\x1b[1;3D");
+        assert_eq!(encode(KeyCode::Up, KeyModifiers::CONTROL), "\x1b[1;5A");
+        assert_eq!(
+            encode(KeyCode::Down, KeyModifiers::CONTROL | KeyModifiers::SHIFT),
+            "\x1b[1;6B"
+        );
+        assert_eq!(encode(KeyCode::End, KeyModifiers::SHIFT), "\x1b[1;2F");
+    }
+
+    #[test]
+    fn test_tilde_keys() {
+        assert_eq!(encode(KeyCode::Insert, KeyModifiers::NONE), "\x1b[2~");
+        assert_eq!(encode(KeyCode::Delete, KeyModifiers::NONE), "\x1b[3~");
+        assert_eq!(encode(KeyCode::PageUp, KeyModifiers::NONE), "\x1b[5~");
+        assert_eq!(
+            encode(KeyCode::PageDown, KeyModifiers::CONTROL),
+            "\x1b[6;5~"
+        );
+    }
+
+    #[test]
+    fn test_function_keys() {
+        assert_eq!(encode(KeyCode::F(1), KeyModifiers::NONE), "\x1bOP");
+        assert_eq!(encode(KeyCode::F(4), KeyModifiers::NONE), "\x1bOS");
+        assert_eq!(encode(KeyCode::F(1), KeyModifiers::SHIFT), "\x1b[1;2P");
+        assert_eq!(encode(KeyCode::F(5), KeyModifiers::NONE), "\x1b[15~");
+        assert_eq!(encode(KeyCode::F(10), KeyModifiers::NONE), "\x1b[21~");
+        assert_eq!(encode(KeyCode::F(12), KeyModifiers::CONTROL), "\x1b[24;5~");
+    }
+
+    #[test]
+    fn test_application_keypad() {
+        let keypad = |code| KeyEvent {
+            state: KeyEventState::KEYPAD,
+            ..KeyEvent::new(code, KeyModifiers::NONE)
+        };
+        let app = KeyboardModes {
+            application_keypad: true,
+            ..Default::default()
+        };
+
+        assert_eq!(encode_key(&keypad(KeyCode::Char('5')), app), "\x1bOu");
+        assert_eq!(encode_key(&keypad(KeyCode::Enter), app), "\x1bOM");
+        assert_eq!(
+            encode_key(&keypad(KeyCode::Char('5')), KeyboardModes::default()),
+            "5"
+        );
+    }
+
+    #[test]
+    fn test_kitty_disambiguate() {
+        let kitty = KeyboardModes {
+            kitty_flags: KITTY_DISAMBIGUATE_ESCAPE_CODES,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            encode_with(KeyCode::Esc, KeyModifiers::NONE, kitty),
+            "\x1b[27u"
+        );
+        assert_eq!(
+            encode_with(KeyCode::Char('c'), KeyModifiers::CONTROL, kitty),
+            "\x1b[99;5u"
+        );
+        assert_eq!(
+            encode_with(
+                KeyCode::Char('I'),
+                KeyModifiers::CONTROL | KeyModifiers::SHIFT,
+                kitty
+            ),
+            "\x1b[105;6u"
+        );
+        assert_eq!(
+            encode_with(KeyCode::Enter, KeyModifiers::ALT, kitty),
+            "\x1b[13;3u"
+        );
+
+        // Plain text and functional keys keep their legacy encoding
+        assert_eq!(
+            encode_with(KeyCode::Char('a'), KeyModifiers::NONE, kitty),
+            "a"
+        );
+        assert_eq!(encode_with(KeyCode::Enter, KeyModifiers::NONE, kitty), "\r");
+        assert_eq!(
+            encode_with(KeyCode::Up, KeyModifiers::NONE, kitty),
+            "\x1b[A"
+        );
+    }
+
+    #[test]
+    fn test_kitty_report_all_keys() {
+        let kitty = KeyboardModes {
+            kitty_flags: KITTY_DISAMBIGUATE_ESCAPE_CODES | KITTY_REPORT_ALL_KEYS_AS_ESCAPE_CODES,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            encode_with(KeyCode::Char('a'), KeyModifiers::NONE, kitty),
+            "\x1b[97u"
+        );
+        assert_eq!(
+            encode_with(KeyCode::Enter, KeyModifiers::NONE, kitty),
+            "\x1b[13u"
+        );
+        assert_eq!(
+            encode_with(KeyCode::Tab, KeyModifiers::NONE, kitty),
+            "\x1b[9u"
+        );
+        assert_eq!(
+            encode_with(KeyCode::Backspace, KeyModifiers::NONE, kitty),
+            "\x1b[127u"
+        );
+    }
+
+    #[test]
+    fn test_paste_unbracketed() {
+        assert_eq!(encode_paste("echo hi", false), "echo hi");
+        assert_eq!(encode_paste("a\nb\r\nc", false), "a\rb\rc");
+    }
+
+    #[test]
+    fn test_paste_bracketed() {
+        assert_eq!(
+            encode_paste("ls\nrm -rf x\n", true),
+            "\x1b[200~ls\rrm -rf x\r\x1b[201~"
+        );
+    }
+
+    #[test]
+    fn test_paste_strips_embedded_markers() {
+        assert_eq!(
+            encode_paste("a\x1b[201~\nb", true),
+            "\x1b[200~a\rb\x1b[201~"
+        );
+        assert_eq!(encode_paste("x\x1b[200~y", true), "\x1b[200~xy\x1b[201~");
+        // Removing the inner marker must not splice together a new one
+        assert_eq!(
+            encode_paste("\x1b[20\x1b[201~1~", true),
+            "\x1b[200~\x1b[201~"
+        );
+    }
+}
@@ -35,8 +35,19 @@ impl Default for TermTuiKeyBindings {
             copy_bottom: KeyEvent::new(KeyCode::Char('G'), KeyModifiers::NONE),
             copy_word_left: KeyEvent::new(KeyCode::Char('b'), KeyModifiers::NONE),
             copy_word_right: KeyEvent::new(KeyCode::Char('w'), KeyModifiers::NONE),
+            copy_prev_prompt: KeyEvent::new(KeyCode::Char('['), KeyModifiers::NONE),
+            copy_next_prompt: KeyEvent::new(KeyCode::Char(']'), KeyModifiers::NONE),
+            copy_select_output: KeyEvent::new(KeyCode::Char('o'), KeyModifiers::NONE),
+            copy_search_forward: KeyEvent::new(KeyCode::Char('/'), KeyModifiers::NONE),
+            copy_search_backward: KeyEvent::new(KeyCode::Char('?'), KeyModifiers::NONE),
+            copy_search_next: KeyEvent::new(KeyCode::Char('n'), KeyModifiers::NONE),
+            copy_search_prev: KeyEvent::new(KeyCode::Char('N'), KeyModifiers::NONE),
+            copy_search_toggle_regex: KeyEvent::new(KeyCode::Char('r'), KeyModifiers::ALT),
+            copy_search_toggle_case: KeyEvent::new(KeyCode::Char('c'), KeyModifiers::ALT),
             copy_start_selection: KeyEvent::new(KeyCode::Char('v'), KeyModifiers::NONE),
             copy_start_selection_alt: KeyEvent::new(KeyCode::Char(' '), KeyModifiers::NONE),
+            copy_line_selection: KeyEvent::new(KeyCode::Char('V'), KeyModifiers::SHIFT),
+            copy_block_selection: KeyEvent::new(KeyCode::Char('v'), KeyModifiers::CONTROL),
             copy_and_exit: KeyEvent::new(KeyCode::Char('y'), KeyModifiers::NONE),
             copy_and_exit_alt: KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE),
         }
@@ -1,11 +1,23 @@
 //! Key matching utility for keybindings
 
 use crate::termtui::keybindings::TermTuiKeyBindings;
-use crossterm::event::KeyEvent;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 
 impl TermTuiKeyBindings {
     /// Check if a key event matches a keybinding (ignoring state field differences)
+    ///
+    /// For character keys, Shift is ignored: the character already reflects it
+    /// (`G`, `?`), and terminals disagree on whether they report it.
     pub fn key_matches(key: &KeyEvent, binding: &KeyEvent) -> bool {
-        key.code == binding.code && key.modifiers == binding.modifiers
+        if key.code != binding.code {
+            return false;
+        }
+        match key.code {
+            KeyCode::Char(_) => {
+                key.modifiers.difference(KeyModifiers::SHIFT)
+                    == binding.modifiers.difference(KeyModifiers::SHIFT)
+            }
+            _ => key.modifiers == binding.modifiers,
+        }
     }
 }
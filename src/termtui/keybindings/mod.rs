@@ -63,10 +63,32 @@ pub struct TermTuiKeyBindings {
     pub copy_word_left: KeyEvent,
     /// Move word right (default: w)
     pub copy_word_right: KeyEvent,
+    /// Jump to previous shell prompt (default: [)
+    pub copy_prev_prompt: KeyEvent,
+    /// Jump to next shell prompt (default: ])
+    pub copy_next_prompt: KeyEvent,
+    /// Select last command output (default: o)
+    pub copy_select_output: KeyEvent,
+    /// Search forward (default: /)
+    pub copy_search_forward: KeyEvent,
+    /// Search backward (default: ?)
+    pub copy_search_backward: KeyEvent,
+    /// Jump to next match (default: n)
+    pub copy_search_next: KeyEvent,
+    /// Jump to previous match (default: N)
+    pub copy_search_prev: KeyEvent,
+    /// Toggle regex search (default: Alt+R)
+    pub copy_search_toggle_regex: KeyEvent,
+    /// Toggle case-insensitive search (default: Alt+C)
+    pub copy_search_toggle_case: KeyEvent,
     /// Start/toggle selection (default: v or Space)
     pub copy_start_selection: KeyEvent,
     /// Alternative start selection (default: Space)
     pub copy_start_selection_alt: KeyEvent,
+    /// Start/toggle line-wise selection (default: V)
+    pub copy_line_selection: KeyEvent,
+    /// Start/toggle block (rectangular) selection (default: Ctrl+V)
+    pub copy_block_selection: KeyEvent,
     /// Copy and exit (default: y or Enter)
     pub copy_and_exit: KeyEvent,
     /// Alternative copy and exit (default: Enter)
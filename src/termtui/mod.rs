@@ -11,36 +11,92 @@
 //! bytes → termwiz Parser → actions → Screen.handle_action() → Grid (VecDeque<Row>)
 //! ```
 
+mod asciicast;
 mod attrs;
+mod backend;
 mod cell;
+mod charset;
 mod copy_mode;
+mod cursor;
+mod event;
+mod export;
 mod grid;
+mod hyperlink;
+mod key_encoder;
 mod keybindings;
+mod monitor;
+mod mouse;
+mod osc52;
+mod palette;
 mod parser;
+mod process;
 mod row;
 mod screen;
+mod search;
+mod selection;
+mod shell_integration;
 mod size;
+mod spawn_options;
+pub mod testing;
 mod widget;
 
-pub use attrs::{Attrs, Color};
+pub use asciicast::{
+    AsciicastEvent, AsciicastEventKind, AsciicastPlayer, AsciicastRecorder, AsciicastRecording,
+};
+pub use attrs::{Attrs, Color, UnderlineStyle};
+pub use backend::{ChildBackend, PtyBackend, StreamBackend, TermTuiBackend};
 pub use cell::Cell;
-pub use copy_mode::{CopyMode, CopyMoveDir, CopyPos};
+pub use charset::Charset;
+pub use copy_mode::{CopyMode, CopyMoveDir, CopyPos, CopySearch};
+pub use cursor::CursorShape;
+pub use event::TermTuiEvent;
+pub use export::{export_grid, import_ansi, ExportFormat};
 pub use grid::{Grid, Pos};
+pub use hyperlink::{detect_links, DetectedLink, Hyperlink, LinkTarget};
+pub use key_encoder::{
+    encode_key, encode_paste, KeyboardModes, KITTY_DISAMBIGUATE_ESCAPE_CODES,
+    KITTY_REPORT_ALL_KEYS_AS_ESCAPE_CODES, KITTY_REPORT_ALTERNATE_KEYS,
+    KITTY_REPORT_ASSOCIATED_TEXT, KITTY_REPORT_EVENT_TYPES,
+};
 pub use keybindings::TermTuiKeyBindings;
+pub use monitor::TermTuiAlerts;
+pub use mouse::{encode_mouse_event, MouseProtocolEncoding, MouseProtocolMode};
+pub use osc52::Osc52Policy;
+pub use palette::{Palette, Rgb};
 pub use parser::Parser;
+pub use process::ProcessStatus;
 pub use row::Row;
 pub use screen::Screen;
+pub use search::{SearchDirection, SearchMatch, SearchOptions};
+pub use selection::{Selection, SelectionMode, DEFAULT_WORD_SEPARATORS};
+pub use shell_integration::{RowMarks, ShellCommand};
 pub use size::Size;
+pub use spawn_options::TermTuiSpawnOptions;
 pub use widget::TermTuiWidget;
 
+use crate::clipboard::Clipboard;
+use crate::termtui::monitor::Monitor;
 use anyhow::Result;
-use portable_pty::{native_pty_system, Child, CommandBuilder, MasterPty, PtySize};
-use ratatui::layout::Rect;
+use portable_pty::CommandBuilder;
+use ratatui::layout::{Position, Rect};
 use ratatui::style::{Color as RatatuiColor, Style};
 use ratatui::widgets::{Block, BorderType, Borders};
 use ratatui::Frame;
 use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::Notify;
+
+/// Bytes read from the PTY per batch
+const READ_BUFFER_SIZE: usize = 64 * 1024;
+
+/// How long a blinking cursor stays on or off
+const CURSOR_BLINK_INTERVAL: Duration = Duration::from_millis(530);
+
+/// Longest gap between the clicks of a double or triple click
+const MULTI_CLICK_INTERVAL: Duration = Duration::from_millis(400);
 
 /// TermTui - Terminal widget with mprocs-style architecture
 ///
@@ -63,9 +119,14 @@ pub struct TermTui {
     pub copy_mode: CopyMode,
 
     /// Process management
-    _master: Option<Arc<Mutex<Box<dyn MasterPty + Send>>>>,
-    _child: Option<Box<dyn Child + Send + Sync>>,
+    backend: Option<Box<dyn TermTuiBackend>>,
     writer: Option<Arc<Mutex<Box<dyn Write + Send>>>>,
+    /// Command the child was spawned with (reused by `restart`)
+    command: Option<CommandBuilder>,
+    /// Last known process status (`None` without a process)
+    status: Option<ProcessStatus>,
+    /// Whether the title shows the exit status once the process ends
+    exit_status_in_title: bool,
 
     /// Styling
     pub border_style: Style,
@@ -73,6 +134,39 @@ pub struct TermTui {
 
     /// Customizable keybindings
     pub keybindings: TermTuiKeyBindings,
+
+    /// Characters that end a word for double-click selection
+    word_separators: String,
+    /// Time, cell and count of the last left click (for double/triple clicks)
+    last_click: Option<(Instant, CopyPos, u8)>,
+
+    /// Clipboard used for copying (`None` for `Clipboard::global`)
+    clipboard: Option<Clipboard>,
+
+    /// Whether the host places the real cursor instead of the widget drawing one
+    host_cursor: bool,
+    /// Start of the cursor blink cycle
+    cursor_blink_epoch: Instant,
+
+    /// Events waiting to be collected by the application
+    events: Vec<TermTuiEvent>,
+
+    /// Bell, activity and silence monitoring
+    monitor: Monitor,
+
+    /// Set by the reader thread when new output was parsed
+    dirty: Arc<AtomicBool>,
+    /// Signalled by the reader thread when new output was parsed
+    dirty_notify: Arc<Notify>,
+    /// Bumped on restart so the previous reader thread drops its output
+    reader_generation: Arc<AtomicU64>,
+
+    /// Active session recording, fed by the reader thread
+    recorder: Arc<Mutex<Option<AsciicastRecorder>>>,
+    /// Recording played back instead of a live process
+    player: Option<AsciicastPlayer>,
+    /// When playback last advanced
+    last_playback_tick: Option<Instant>,
 }
 
 impl TermTui {
@@ -85,106 +179,450 @@ impl TermTui {
             title: title.into(),
             focused: false,
             copy_mode: CopyMode::None,
-            _master: None,
-            _child: None,
+            backend: None,
             writer: None,
+            command: None,
+            status: None,
+            exit_status_in_title: false,
             border_style: Style::default().fg(RatatuiColor::White),
             focused_border_style: Style::default().fg(RatatuiColor::Cyan),
             keybindings: TermTuiKeyBindings::default(),
+            word_separators: DEFAULT_WORD_SEPARATORS.to_string(),
+            last_click: None,
+            clipboard: None,
+            host_cursor: false,
+            cursor_blink_epoch: Instant::now(),
+            events: Vec::new(),
+            monitor: Monitor::new(),
+            dirty: Arc::new(AtomicBool::new(false)),
+            dirty_notify: Arc::new(Notify::new()),
+            reader_generation: Arc::new(AtomicU64::new(0)),
+            recorder: Arc::new(Mutex::new(None)),
+            player: None,
+            last_playback_tick: None,
         }
     }
 
+    /// Set the palette reported to color queries (builder pattern)
+    pub fn with_palette(self, palette: Palette) -> Self {
+        self.parser.lock().unwrap().set_palette(palette);
+        self
+    }
+
+    /// Offer the kitty keyboard protocol to the child program (builder pattern)
+    ///
+    /// Off by default. When enabled, programs can push kitty keyboard flags
+    /// (`CSI > u`) to receive unambiguous key encodings.
+    pub fn with_kitty_keyboard(self, enabled: bool) -> Self {
+        self.parser
+            .lock()
+            .unwrap()
+            .screen_mut()
+            .set_kitty_keyboard_enabled(enabled);
+        self
+    }
+
     /// Set custom keybindings (builder pattern)
     pub fn with_keybindings(mut self, keybindings: TermTuiKeyBindings) -> Self {
         self.keybindings = keybindings;
         self
     }
 
+    /// Copy through `clipboard` instead of the global one (builder pattern)
+    pub fn with_clipboard(mut self, clipboard: Clipboard) -> Self {
+        self.clipboard = Some(clipboard);
+        self
+    }
+
+    /// Let programs in the terminal set the clipboard with OSC 52 (builder pattern)
+    ///
+    /// Off by default (`Osc52Policy::Deny`). Allowed requests are copied
+    /// through this terminal's clipboard.
+    pub fn with_osc52_policy(self, policy: Osc52Policy) -> Self {
+        self.parser
+            .lock()
+            .unwrap()
+            .screen_mut()
+            .set_osc52_policy(policy);
+        self
+    }
+
+    /// Let the host terminal draw the cursor (builder pattern)
+    ///
+    /// Instead of drawing the cursor into the buffer, rendering a focused
+    /// terminal calls `frame.set_cursor_position`, so the host's own cursor
+    /// (and its shape and blinking) is used.
+    pub fn with_host_cursor(mut self, enabled: bool) -> Self {
+        self.host_cursor = enabled;
+        self
+    }
+
+    /// Set the characters that end a word for double-click selection (builder pattern)
+    ///
+    /// Whitespace always ends a word. Defaults to `DEFAULT_WORD_SEPARATORS`.
+    pub fn with_word_separators(mut self, separators: impl Into<String>) -> Self {
+        self.word_separators = separators.into();
+        self
+    }
+
+    /// Raise an alert when the program rings the bell (builder pattern)
+    ///
+    /// On by default. See `alerts` and `TermTuiEvent::Bell`.
+    pub fn with_monitor_bell(mut self, enabled: bool) -> Self {
+        self.monitor.bell = enabled;
+        self
+    }
+
+    /// Raise an alert when the program writes output (builder pattern)
+    ///
+    /// Off by default, since busy programs would raise it constantly.
+    pub fn with_monitor_activity(mut self, enabled: bool) -> Self {
+        self.monitor.activity = enabled;
+        self
+    }
+
+    /// Raise an alert after `threshold` without output (builder pattern)
+    ///
+    /// Off by default. Each quiet period after some output is reported once
+    /// with `TermTuiEvent::Silence`.
+    pub fn with_monitor_silence(mut self, threshold: Option<Duration>) -> Self {
+        self.monitor.silence = threshold;
+        self
+    }
+
+    /// Flash the screen when the program rings the bell (builder pattern)
+    ///
+    /// Off by default. The flash is drawn by the next renders within a short
+    /// time of the bell, so the application should redraw soon after.
+    pub fn with_visual_bell(mut self, enabled: bool) -> Self {
+        self.monitor.visual_bell = enabled;
+        self
+    }
+
+    /// Show the exit status in the title once the process ends (builder pattern)
+    ///
+    /// The title then reads e.g. "Build [exited 1]".
+    pub fn with_exit_status_in_title(mut self, enabled: bool) -> Self {
+        self.exit_status_in_title = enabled;
+        self
+    }
+
     /// Spawn a terminal with a command
+    ///
+    /// Uses the default `TermTuiSpawnOptions`; see `spawn_with_options` to
+    /// set the environment, working directory, size or scrollback.
     pub fn spawn_with_command(
         title: impl Into<String>,
         command: &str,
         args: &[&str],
     ) -> Result<Self> {
-        let rows = 24;
-        let cols = 80;
-
-        let pty_system = native_pty_system();
-        let pty_size = PtySize {
-            rows,
-            cols,
-            pixel_width: 0,
-            pixel_height: 0,
-        };
-
-        let pair = pty_system.openpty(pty_size)?;
+        let options = TermTuiSpawnOptions::new()
+            .with_command(command)
+            .with_args(args.iter().copied());
+        Self::spawn_with_options(title, options)
+    }
 
-        let mut cmd = CommandBuilder::new(command);
-        for arg in args {
-            cmd.arg(arg);
+    /// Spawn a terminal as described by `options`
+    pub fn spawn_with_options(
+        title: impl Into<String>,
+        options: TermTuiSpawnOptions,
+    ) -> Result<Self> {
+        let cmd = options.to_command()?;
+        let (rows, cols) = options.size();
+
+        let mut term = Self::new(title);
+        let mut parser = Parser::new(rows as usize, cols as usize, options.scrollback());
+        if let Some(ansi) = options.preload() {
+            parser.load_ansi(ansi);
+            // Replies to queries in the restored text must not reach the child
+            parser.take_replies();
         }
+        *term.parser.lock().unwrap() = parser;
+        term.spawn(cmd)?;
+        Ok(term)
+    }
 
-        // Set terminal type
-        cmd.env("TERM", "xterm-256color");
-
-        let current_dir = std::env::current_dir()?;
-        cmd.cwd(current_dir);
-
-        let child = pair.slave.spawn_command(cmd)?;
-
-        // Set non-blocking mode
-        #[cfg(unix)]
-        {
-            if let Some(fd) = pair.master.as_raw_fd() {
-                unsafe {
-                    let flags = libc::fcntl(fd, libc::F_GETFL, 0);
-                    libc::fcntl(fd, libc::F_SETFL, flags | libc::O_NONBLOCK);
-                }
-            }
-        }
+    /// Create a terminal that plays back a recording instead of a process
+    ///
+    /// The screen takes the recorded size and is not resized to fit the
+    /// render area. Playback starts when the terminal is first rendered.
+    pub fn from_recording(title: impl Into<String>, recording: AsciicastRecording) -> Self {
+        let player = AsciicastPlayer::new(recording);
+        let mut term = Self::new(title);
+        *term.parser.lock().unwrap() = player.new_parser();
+        term.player = Some(player);
+        term
+    }
 
-        let reader = pair.master.try_clone_reader()?;
-        let writer = pair.master.take_writer()?;
+    /// Create a terminal that plays back a `.cast` file
+    pub fn from_asciicast(title: impl Into<String>, path: impl AsRef<Path>) -> Result<Self> {
+        let recording = AsciicastRecording::load(path)?;
+        Ok(Self::from_recording(title, recording))
+    }
 
-        let writer = Arc::new(Mutex::new(writer));
+    /// Create a terminal fed by a backend
+    ///
+    /// Use this for sources other than a local PTY, such as a piped
+    /// `std::process::Child` (`ChildBackend`) or a socket (`StreamBackend`).
+    pub fn from_backend(
+        title: impl Into<String>,
+        backend: impl TermTuiBackend + 'static,
+    ) -> Result<Self> {
+        let mut term = Self::new(title);
+        term.attach(Box::new(backend))?;
+        Ok(term)
+    }
 
-        let parser = Parser::new(rows as usize, cols as usize, 10000);
-        let parser = Arc::new(Mutex::new(parser));
-        let parser_clone = Arc::clone(&parser);
+    /// Spawn `cmd` in a new PTY sized like the screen and start reading it
+    fn spawn(&mut self, cmd: CommandBuilder) -> Result<()> {
+        let size = self.parser.lock().unwrap().size();
+        let backend = PtyBackend::spawn(cmd.clone(), size)?;
+        self.attach(Box::new(backend))?;
+        self.command = Some(cmd);
+        Ok(())
+    }
 
-        // Spawn read thread (using std::thread for sync compatibility)
+    /// Take over the backend's streams and start reading its output
+    fn attach(&mut self, mut backend: Box<dyn TermTuiBackend>) -> Result<()> {
+        let reader = backend.take_reader()?;
+        let writer = Arc::new(Mutex::new(backend.take_writer()?));
+
+        let parser_clone = Arc::clone(&self.parser);
+        let writer_clone = Arc::clone(&writer);
+        let dirty = Arc::clone(&self.dirty);
+        let dirty_notify = Arc::clone(&self.dirty_notify);
+        let recorder = Arc::clone(&self.recorder);
+        let output = Arc::clone(&self.monitor.output);
+        let generation = Arc::clone(&self.reader_generation);
+        let my_generation = generation.load(Ordering::Acquire);
+
+        // Blocking read thread: each read returns whatever output is ready,
+        // which is parsed as one batch before waking the application
         std::thread::spawn(move || {
-            let mut buf = [0u8; 8192];
+            let mut buf = vec![0u8; READ_BUFFER_SIZE];
             let mut reader = reader;
             loop {
                 match reader.read(&mut buf) {
                     Ok(0) => break,
                     Ok(n) => {
-                        if let Ok(mut parser) = parser_clone.lock() {
-                            parser.process(&buf[..n]);
+                        let replies = match parser_clone.lock() {
+                            // Output of a process replaced by `restart`
+                            Ok(_) if generation.load(Ordering::Acquire) != my_generation => {
+                                return;
+                            }
+                            Ok(mut parser) => {
+                                parser.process(&buf[..n]);
+                                parser.take_replies()
+                            }
+                            Err(_) => Vec::new(),
+                        };
+
+                        if let Ok(mut recorder) = recorder.lock() {
+                            if let Some(recorder) = recorder.as_mut() {
+                                let _ = recorder.output(&buf[..n]);
+                            }
                         }
-                        std::thread::sleep(std::time::Duration::from_millis(10));
-                    }
-                    Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {
-                        std::thread::sleep(std::time::Duration::from_millis(10));
+
+                        // Answer terminal queries (DA, DSR, OSC colors)
+                        if !replies.is_empty() {
+                            if let Ok(mut writer) = writer_clone.lock() {
+                                let _ = writer.write_all(&replies);
+                                let _ = writer.flush();
+                            }
+                        }
+
+                        output.record();
+                        dirty.store(true, Ordering::Release);
+                        dirty_notify.notify_one();
                     }
+                    Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {}
                     Err(_) => break,
                 }
             }
+
+            // Wake the application so it notices the exit
+            dirty.store(true, Ordering::Release);
+            dirty_notify.notify_one();
         });
 
-        Ok(Self {
-            parser,
-            title: title.into(),
-            focused: false,
-            copy_mode: CopyMode::None,
-            _master: None,
-            _child: Some(child),
-            writer: Some(writer),
-            border_style: Style::default().fg(RatatuiColor::White),
-            focused_border_style: Style::default().fg(RatatuiColor::Cyan),
-            keybindings: TermTuiKeyBindings::default(),
-        })
+        self.backend = Some(backend);
+        self.writer = Some(writer);
+        self.status = Some(ProcessStatus::Running);
+        Ok(())
+    }
+
+    /// Get the notifier signalled whenever new output has been parsed
+    ///
+    /// Await `notified()` (e.g. in `tokio::select!`) to redraw only when the
+    /// screen changed. Notifications coalesce, and one is also sent when the
+    /// process output ends.
+    pub fn dirty_notifier(&self) -> Arc<Notify> {
+        Arc::clone(&self.dirty_notify)
+    }
+
+    /// Check whether new output arrived since the last call, clearing the flag
+    ///
+    /// For applications that poll instead of awaiting `dirty_notifier`.
+    pub fn take_dirty(&self) -> bool {
+        self.dirty.swap(false, Ordering::AcqRel)
+    }
+
+    /// Start recording output and resizes to an asciicast v2 file
+    ///
+    /// Replaces any recording already in progress.
+    pub fn start_recording(&self, path: impl AsRef<Path>) -> Result<()> {
+        let size = self.parser.lock().unwrap().size();
+        let term = self
+            .command
+            .as_ref()
+            .and_then(|cmd| cmd.get_env("TERM"))
+            .and_then(|term| term.to_str());
+        let recorder = AsciicastRecorder::create(path, size.cols, size.rows, term)?;
+        self.record_to(recorder);
+        Ok(())
+    }
+
+    /// Record output and resizes with the given recorder
+    pub fn record_to(&self, recorder: AsciicastRecorder) {
+        *self.recorder.lock().unwrap() = Some(recorder);
+    }
+
+    /// Stop recording, flushing the file
+    pub fn stop_recording(&self) -> Result<()> {
+        if let Some(mut recorder) = self.recorder.lock().unwrap().take() {
+            recorder.flush()?;
+        }
+        Ok(())
+    }
+
+    /// Check if a recording is in progress
+    pub fn is_recording(&self) -> bool {
+        self.recorder.lock().unwrap().is_some()
+    }
+
+    /// Get the playback state (`None` unless created from a recording)
+    pub fn player(&self) -> Option<&AsciicastPlayer> {
+        self.player.as_ref()
+    }
+
+    /// Toggle between playing and paused
+    pub fn toggle_playback(&mut self) {
+        self.tick_playback();
+        if let Some(player) = self.player.as_mut() {
+            player.toggle_pause();
+        }
+    }
+
+    /// Set the playback speed multiplier (e.g. `2.0` for double speed)
+    pub fn set_playback_speed(&mut self, speed: f64) {
+        self.tick_playback();
+        if let Some(player) = self.player.as_mut() {
+            player.set_speed(speed);
+        }
+    }
+
+    /// Jump to a position in the recording
+    pub fn seek_playback(&mut self, position: Duration) {
+        self.tick_playback();
+        if let Some(player) = self.player.as_mut() {
+            self.copy_mode = CopyMode::None;
+            player.seek(position, &mut self.parser.lock().unwrap());
+        }
+    }
+
+    /// Advance playback by the wall-clock time since the last tick
+    ///
+    /// Called by `render`; call it from a timer to keep playing while the
+    /// terminal is not redrawn.
+    pub fn tick_playback(&mut self) {
+        let Some(player) = self.player.as_mut() else {
+            return;
+        };
+        let now = Instant::now();
+        let elapsed = self
+            .last_playback_tick
+            .map(|last| now.duration_since(last))
+            .unwrap_or_default();
+        self.last_playback_tick = Some(now);
+        player.advance(elapsed, &mut self.parser.lock().unwrap());
+    }
+
+    /// Get the status of the child process (`None` if none was spawned)
+    pub fn status(&mut self) -> Option<ProcessStatus> {
+        self.poll_status();
+        self.status.clone()
+    }
+
+    /// Check whether a running child has exited, emitting `ProcessExited`
+    fn poll_status(&mut self) {
+        if self.status != Some(ProcessStatus::Running) {
+            return;
+        }
+        let Some(backend) = self.backend.as_mut() else {
+            return;
+        };
+        if let Ok(Some(status)) = backend.try_wait() {
+            self.events.push(TermTuiEvent::ProcessExited {
+                status: status.clone(),
+            });
+            self.status = Some(status);
+        }
+    }
+
+    /// Kill the child process
+    ///
+    /// For a PTY on Unix the process gets `SIGHUP` and a short grace period
+    /// before `SIGKILL`. Does nothing if the process already exited.
+    pub fn kill(&mut self) -> Result<()> {
+        self.poll_status();
+        if self.status == Some(ProcessStatus::Running) {
+            if let Some(backend) = self.backend.as_mut() {
+                backend.kill()?;
+            }
+            self.poll_status();
+        }
+        Ok(())
+    }
+
+    /// Send a signal (e.g. `libc::SIGTERM`) to the child process
+    #[cfg(unix)]
+    pub fn send_signal(&mut self, signal: i32) -> Result<()> {
+        self.poll_status();
+        if self.status != Some(ProcessStatus::Running) {
+            anyhow::bail!("no running process");
+        }
+        let pid = self
+            .backend
+            .as_ref()
+            .and_then(|backend| backend.process_id())
+            .ok_or_else(|| anyhow::anyhow!("process id unavailable"))?;
+
+        if unsafe { libc::kill(pid as libc::pid_t, signal) } != 0 {
+            return Err(std::io::Error::last_os_error().into());
+        }
+        Ok(())
+    }
+
+    /// Restart the child with the command it was spawned with
+    ///
+    /// A running process is killed first. The screen is reset so modes left
+    /// behind by the old process (alternate screen, mouse reporting) are gone.
+    pub fn restart(&mut self) -> Result<()> {
+        let cmd = self
+            .command
+            .clone()
+            .ok_or_else(|| anyhow::anyhow!("no command to restart"))?;
+
+        self.kill()?;
+        self.copy_mode = CopyMode::None;
+        {
+            // Retire the old reader under the parser lock so none of its
+            // output lands after the reset
+            let mut parser = self.parser.lock().unwrap();
+            self.reader_generation.fetch_add(1, Ordering::AcqRel);
+            parser.process(b"\x1bc");
+        }
+        self.spawn(cmd)
     }
 
     /// Handle keyboard input
@@ -204,9 +642,7 @@ impl TermTui {
         // Copy selection (configurable, default: Ctrl+Shift+C)
         if TermTuiKeyBindings::key_matches(&key, &self.keybindings.copy_selection) {
             if let Some(text) = self.copy_mode.get_selected_text() {
-                if let Ok(mut clipboard) = arboard::Clipboard::new() {
-                    let _ = clipboard.set_text(text);
-                }
+                let _ = self.clipboard().set_text(&text);
                 return true;
             }
         }
@@ -229,6 +665,10 @@ impl TermTui {
 
     /// Handle keyboard in copy mode
     fn handle_copy_mode_key(&mut self, key: crossterm::event::KeyEvent) -> bool {
+        if self.copy_mode.is_search_editing() {
+            return self.handle_search_input_key(key);
+        }
+
         let kb = &self.keybindings;
 
         // Exit copy mode
@@ -327,11 +767,61 @@ impl TermTui {
             return true;
         }
 
+        // Search forward / backward
+        if TermTuiKeyBindings::key_matches(&key, &kb.copy_search_forward) {
+            self.copy_mode.start_search(SearchDirection::Forward);
+            return true;
+        }
+        if TermTuiKeyBindings::key_matches(&key, &kb.copy_search_backward) {
+            self.copy_mode.start_search(SearchDirection::Backward);
+            return true;
+        }
+
+        // Next / previous match
+        if TermTuiKeyBindings::key_matches(&key, &kb.copy_search_next) {
+            self.copy_mode.search_next();
+            return true;
+        }
+        if TermTuiKeyBindings::key_matches(&key, &kb.copy_search_prev) {
+            self.copy_mode.search_prev();
+            return true;
+        }
+
+        // Previous shell prompt
+        if TermTuiKeyBindings::key_matches(&key, &kb.copy_prev_prompt) {
+            self.copy_mode.jump_to_prev_prompt();
+            return true;
+        }
+
+        // Next shell prompt
+        if TermTuiKeyBindings::key_matches(&key, &kb.copy_next_prompt) {
+            self.copy_mode.jump_to_next_prompt();
+            return true;
+        }
+
+        // Select last command output
+        if TermTuiKeyBindings::key_matches(&key, &kb.copy_select_output) {
+            self.copy_mode.select_last_command_output();
+            return true;
+        }
+
         // Start/toggle selection
         if TermTuiKeyBindings::key_matches(&key, &kb.copy_start_selection)
             || TermTuiKeyBindings::key_matches(&key, &kb.copy_start_selection_alt)
         {
-            self.copy_mode.set_anchor();
+            self.copy_mode.start_selection(SelectionMode::Stream);
+            return true;
+        }
+
+        // Start/toggle line-wise selection
+        if TermTuiKeyBindings::key_matches(&key, &kb.copy_line_selection) {
+            self.copy_mode.start_selection(SelectionMode::Line);
+            return true;
+        }
+
+        // Start/toggle block selection
+        if TermTuiKeyBindings::key_matches(&key, &kb.copy_block_selection) {
+            self.copy_mode.start_selection(SelectionMode::Block);
             return true;
         }
 
@@ -340,9 +830,7 @@ impl TermTui {
             || TermTuiKeyBindings::key_matches(&key, &kb.copy_and_exit_alt)
         {
             if let Some(text) = self.copy_mode.get_selected_text() {
-                if let Ok(mut clipboard) = arboard::Clipboard::new() {
-                    let _ = clipboard.set_text(text);
-                }
+                let _ = self.clipboard().set_text(&text);
             }
             self.copy_mode = CopyMode::None;
             return true;
@@ -351,47 +839,44 @@ impl TermTui {
         false
     }
 
-    /// Convert key event to terminal input sequence
-    fn key_to_terminal_input(&self, key: crossterm::event::KeyEvent) -> String {
+    /// Handle keyboard while typing a search query in copy mode
+    fn handle_search_input_key(&mut self, key: crossterm::event::KeyEvent) -> bool {
         use crossterm::event::{KeyCode, KeyModifiers};
 
+        let kb = &self.keybindings;
+
+        if TermTuiKeyBindings::key_matches(&key, &kb.copy_search_toggle_regex) {
+            self.copy_mode.toggle_search_regex();
+            return true;
+        }
+        if TermTuiKeyBindings::key_matches(&key, &kb.copy_search_toggle_case) {
+            self.copy_mode.toggle_search_case();
+            return true;
+        }
+
         match key.code {
-            KeyCode::Char(c) => {
-                if key.modifiers.contains(KeyModifiers::CONTROL) {
-                    match c.to_ascii_lowercase() {
-                        'a'..='z' => {
-                            let code = (c.to_ascii_lowercase() as u8 - b'a' + 1) as char;
-                            code.to_string()
-                        }
-                        '@' => "\x00".to_string(),
-                        '[' => "\x1b".to_string(),
-                        '\\' => "\x1c".to_string(),
-                        ']' => "\x1d".to_string(),
-                        '^' => "\x1e".to_string(),
-                        '_' => "\x1f".to_string(),
-                        _ => c.to_string(),
-                    }
-                } else if key.modifiers.contains(KeyModifiers::ALT) {
-                    format!("\x1b{}", c)
-                } else {
-                    c.to_string()
-                }
+            KeyCode::Enter => self.copy_mode.confirm_search(),
+            KeyCode::Esc => self.copy_mode.cancel_search(),
+            KeyCode::Backspace => self.copy_mode.search_pop(),
+            KeyCode::Char(c)
+                if !key
+                    .modifiers
+                    .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) =>
+            {
+                self.copy_mode.search_push(c)
             }
-            KeyCode::Enter => "\r".to_string(),
-            KeyCode::Backspace => "\x7f".to_string(),
-            KeyCode::Tab => "\t".to_string(),
-            KeyCode::Esc => "\x1b".to_string(),
-            KeyCode::Up => "\x1b[A".to_string(),
-            KeyCode::Down => "\x1b[B".to_string(),
-            KeyCode::Right => "\x1b[C".to_string(),
-            KeyCode::Left => "\x1b[D".to_string(),
-            KeyCode::Home => "\x1b[H".to_string(),
-            KeyCode::End => "\x1b[F".to_string(),
-            KeyCode::PageUp => "\x1b[5~".to_string(),
-            KeyCode::PageDown => "\x1b[6~".to_string(),
-            KeyCode::Delete => "\x1b[3~".to_string(),
-            _ => String::new(),
+            _ => return false,
         }
+        true
+    }
+
+    /// Convert key event to terminal input sequence
+    ///
+    /// Honors the application cursor/keypad modes and kitty keyboard flags
+    /// requested by the child program.
+    fn key_to_terminal_input(&self, key: crossterm::event::KeyEvent) -> String {
+        let modes = self.parser.lock().unwrap().screen().keyboard_modes();
+        encode_key(&key, modes)
     }
 
     /// Enter copy mode with frozen screen
@@ -401,16 +886,20 @@ impl TermTui {
         let size = screen.size();
 
         // Start at bottom-right of visible area
-        let start = CopyPos::new(size.cols as i32 - 1, size.rows as i32 - 1);
+        let bottom = size.rows as i32 - 1 - screen.scrollback() as i32;
+        let start = CopyPos::new(size.cols as i32 - 1, bottom);
         self.copy_mode = CopyMode::enter(screen, start);
     }
 
     /// Handle mouse events
     ///
     /// This method handles all mouse interactions:
+    /// - When the child program enabled mouse reporting, events are encoded and
+    ///   written to the PTY instead (hold Shift to select text locally)
     /// - Mouse drag: automatically enters copy mode and starts selection (like mprocs)
     /// - Mouse wheel: scrolls the terminal content
     /// - In copy mode: mouse click moves cursor, mouse drag selects
+    /// - Double click selects a word, triple click selects a line
     ///
     /// # Arguments
     /// * `event` - The mouse event from crossterm
@@ -419,19 +908,66 @@ impl TermTui {
     /// # Returns
     /// `true` if the event was handled, `false` otherwise
     pub fn handle_mouse(&mut self, event: crossterm::event::MouseEvent, area: Rect) -> bool {
-        use crossterm::event::{MouseButton, MouseEventKind};
+        use crossterm::event::{KeyModifiers, MouseButton, MouseEventKind};
 
         // Translate coordinates relative to the content area (inside borders)
         let content_x = event.column.saturating_sub(area.x + 1) as i32;
         let content_y = event.row.saturating_sub(area.y + 1) as i32;
 
+        // Ctrl+click opens the link under the pointer, if any
+        if event.kind == MouseEventKind::Down(MouseButton::Left)
+            && event.modifiers.contains(KeyModifiers::CONTROL)
+            && !self.copy_mode.is_active()
+        {
+            let link = self
+                .parser
+                .lock()
+                .unwrap()
+                .screen()
+                .link_at(content_y as u16, content_x as u16);
+            if let Some(target) = link {
+                self.events.push(TermTuiEvent::LinkClicked { target });
+                return true;
+            }
+        }
+
+        // Forward to the child program when it requested mouse reporting.
+        // Shift bypasses reporting so text can still be selected locally.
+        if !self.copy_mode.is_active() && !event.modifiers.contains(KeyModifiers::SHIFT) {
+            let (mode, encoding, size) = {
+                let parser = self.parser.lock().unwrap();
+                let screen = parser.screen();
+                (
+                    screen.mouse_protocol_mode(),
+                    screen.mouse_protocol_encoding(),
+                    screen.size(),
+                )
+            };
+
+            if mode != MouseProtocolMode::None {
+                let col = (content_x as u16).min(size.cols.saturating_sub(1));
+                let row = (content_y as u16).min(size.rows.saturating_sub(1));
+                if let Some(bytes) = encode_mouse_event(mode, encoding, &event, col, row) {
+                    self.send_bytes(&bytes);
+                }
+                return true;
+            }
+        }
+
+        // Selection rows are relative to the live screen, so account for scrollback
+        let scroll = match self.copy_mode.frozen_screen() {
+            Some(screen) => screen.scrollback(),
+            None => self.parser.lock().unwrap().screen().scrollback(),
+        };
+        let select_y = content_y - scroll as i32;
+
         match event.kind {
             MouseEventKind::Down(MouseButton::Left) => {
                 if self.copy_mode.is_active() {
                     // In copy mode, click moves cursor
                     if let CopyMode::Active { cursor, .. } = &mut self.copy_mode {
                         cursor.x = content_x;
-                        cursor.y = content_y;
+                        cursor.y = select_y;
                     }
                 } else {
                     // Not in copy mode - enter copy mode and position cursor
@@ -439,9 +975,10 @@ impl TermTui {
                     let screen = parser.screen().clone();
                     drop(parser);
 
-                    let start = CopyPos::new(content_x, content_y);
+                    let start = CopyPos::new(content_x, select_y);
                     self.copy_mode = CopyMode::enter(screen, start);
                 }
+                self.select_on_multi_click(CopyPos::new(content_x, select_y));
                 true
             }
             MouseEventKind::Drag(MouseButton::Left) => {
@@ -451,7 +988,7 @@ impl TermTui {
                     let screen = parser.screen().clone();
                     drop(parser);
 
-                    let start = CopyPos::new(content_x, content_y);
+                    let start = CopyPos::new(content_x, select_y);
                     self.copy_mode = CopyMode::enter(screen, start);
                     // Set anchor immediately for selection
                     self.copy_mode.set_anchor();
@@ -462,7 +999,7 @@ impl TermTui {
                     // Move cursor to new position
                     if let CopyMode::Active { cursor, .. } = &mut self.copy_mode {
                         cursor.x = content_x;
-                        cursor.y = content_y;
+                        cursor.y = select_y;
                     }
                 }
                 true
@@ -483,6 +1020,29 @@ impl TermTui {
         }
     }
 
+    /// Select the word or line under a double or triple click
+    ///
+    /// Clicks on the same cell within `MULTI_CLICK_INTERVAL` are counted; a
+    /// fourth click starts over.
+    fn select_on_multi_click(&mut self, pos: CopyPos) {
+        let now = Instant::now();
+        let count = match self.last_click {
+            Some((at, last_pos, count))
+                if last_pos == pos && now.duration_since(at) <= MULTI_CLICK_INTERVAL =>
+            {
+                count % 3 + 1
+            }
+            _ => 1,
+        };
+        self.last_click = Some((now, pos, count));
+
+        match count {
+            2 => self.copy_mode.select_word(pos, &self.word_separators),
+            3 => self.copy_mode.select_line(pos),
+            _ => {}
+        }
+    }
+
     /// Handle mouse down (start selection)
     ///
     /// Note: Consider using `handle_mouse` instead for comprehensive mouse handling.
@@ -496,9 +1056,11 @@ impl TermTui {
 
         let parser = self.parser.lock().unwrap();
         let screen = parser.screen().clone();
+        drop(parser);
 
         let start = CopyPos::new(content_x, content_y);
         self.copy_mode = CopyMode::enter(screen, start);
+        self.select_on_multi_click(start);
     }
 
     /// Handle mouse drag (update selection)
@@ -564,21 +1126,169 @@ impl TermTui {
 
     /// Send input to the terminal
     pub fn send_input(&self, text: &str) {
+        self.send_bytes(text.as_bytes());
+    }
+
+    /// Paste text into the terminal
+    ///
+    /// Uses bracketed paste when the child program enabled it (`?2004`), so
+    /// shells and editors can treat multi-line pastes as a single insertion.
+    pub fn paste(&self, text: &str) {
+        let bracketed = self.parser.lock().unwrap().screen().bracketed_paste();
+        self.send_input(&encode_paste(text, bracketed));
+    }
+
+    /// Send raw bytes to the terminal
+    pub fn send_bytes(&self, bytes: &[u8]) {
         if let Some(ref writer) = self.writer {
             let mut writer = writer.lock().unwrap();
-            let _ = writer.write_all(text.as_bytes());
+            let _ = writer.write_all(bytes);
             let _ = writer.flush();
         }
     }
 
+    /// Get the clipboard this terminal copies through
+    pub fn clipboard(&self) -> Clipboard {
+        self.clipboard.clone().unwrap_or_else(Clipboard::global)
+    }
+
+    /// Copy text that programs sent with OSC 52, if the policy allows it
+    fn apply_clipboard_requests(&mut self) {
+        let requests = self
+            .parser
+            .lock()
+            .unwrap()
+            .screen_mut()
+            .take_clipboard_requests();
+        // Only the latest copy matters
+        if let Some(text) = requests.last() {
+            let _ = self.clipboard().set_text(text);
+        }
+    }
+
+    /// Take the events emitted since the last call
+    ///
+    /// Also checks whether the child process exited and applies OSC 52
+    /// clipboard requests.
+    pub fn take_events(&mut self) -> Vec<TermTuiEvent> {
+        self.poll_status();
+        self.apply_clipboard_requests();
+        let events = self.monitor.take_events(self.bell_count(), Instant::now());
+        self.events.extend(events);
+        std::mem::take(&mut self.events)
+    }
+
+    /// Get the alerts raised since the terminal was last viewed
+    ///
+    /// A focused terminal counts as viewed each time it is rendered; call
+    /// `mark_viewed` when showing it some other way.
+    pub fn alerts(&self) -> TermTuiAlerts {
+        self.monitor.alerts(self.bell_count(), Instant::now())
+    }
+
+    /// Clear the alerts, as when the user looks at the terminal
+    pub fn mark_viewed(&mut self) {
+        self.monitor.mark_viewed(self.bell_count(), Instant::now());
+    }
+
+    fn bell_count(&self) -> u64 {
+        self.parser.lock().unwrap().screen().bell_count()
+    }
+
+    /// Detect URLs and `path:line[:col]` references in the visible rows
+    pub fn detect_links(&self) -> Vec<DetectedLink> {
+        self.parser.lock().unwrap().screen().detect_links()
+    }
+
+    /// Get the shell commands delimited by OSC 133 prompt marks
+    pub fn commands(&self) -> Vec<ShellCommand> {
+        self.parser.lock().unwrap().screen().commands()
+    }
+
+    /// Get the working directory last reported by the shell (OSC 7)
+    pub fn current_dir(&self) -> Option<PathBuf> {
+        self.parser
+            .lock()
+            .unwrap()
+            .screen()
+            .current_dir()
+            .map(Path::to_path_buf)
+    }
+
+    /// Export the scrollback and screen as text, ANSI or HTML
+    pub fn export_scrollback(&self, format: ExportFormat) -> String {
+        self.parser.lock().unwrap().screen().export(format)
+    }
+
+    /// Write the scrollback and screen to a file
+    pub fn dump_scrollback(&self, path: impl AsRef<Path>, format: ExportFormat) -> Result<()> {
+        std::fs::write(path, self.export_scrollback(format))?;
+        Ok(())
+    }
+
+    /// Load an ANSI export as if the program had printed it
+    ///
+    /// Call this before the program writes anything, or the history lands
+    /// below its output; `TermTuiSpawnOptions::with_preload` does that for
+    /// spawned processes.
+    pub fn preload_scrollback(&self, ansi: &str) {
+        let mut parser = self.parser.lock().unwrap();
+        parser.load_ansi(ansi);
+        // Replies to queries in the restored text must not reach the child
+        parser.take_replies();
+        drop(parser);
+        self.dirty.store(true, Ordering::Release);
+        self.dirty_notify.notify_one();
+    }
+
+    /// Load an ANSI export from a file (see `preload_scrollback`)
+    pub fn preload_scrollback_from(&self, path: impl AsRef<Path>) -> Result<()> {
+        let ansi = std::fs::read_to_string(path)?;
+        self.preload_scrollback(&ansi);
+        Ok(())
+    }
+
+    /// Get a handle to the terminal parser
+    pub fn parser(&self) -> Arc<Mutex<Parser>> {
+        Arc::clone(&self.parser)
+    }
+
     /// Resize the terminal
+    ///
+    /// Resizes the screen and, when a backend is attached, tells it the new
+    /// size (for a PTY the child receives `SIGWINCH` and redraws).
     pub fn resize(&mut self, rows: u16, cols: u16) {
-        let mut parser = self.parser.lock().unwrap();
-        parser.resize(rows as usize, cols as usize);
+        if rows == 0 || cols == 0 {
+            return;
+        }
+
+        {
+            let mut parser = self.parser.lock().unwrap();
+            parser.resize(rows as usize, cols as usize);
+        }
+
+        if let Some(recorder) = self.recorder.lock().unwrap().as_mut() {
+            let _ = recorder.resize(cols, rows);
+        }
+
+        if let Some(backend) = self.backend.as_mut() {
+            let _ = backend.resize(Size::new(cols, rows));
+        }
     }
 
     /// Render terminal content (without borders)
+    ///
+    /// Resizes the terminal automatically when `area` differs from the current size.
     pub fn render_content(&mut self, frame: &mut Frame, area: Rect) {
+        let size = self.parser.lock().unwrap().size();
+        if self.player.is_none()
+            && area.width > 0
+            && area.height > 0
+            && (size.rows != area.height || size.cols != area.width)
+        {
+            self.resize(area.height, area.width);
+        }
+
         let parser = self.parser.lock().unwrap();
         let screen = if let Some(frozen) = self.copy_mode.frozen_screen() {
             frozen
@@ -586,11 +1296,52 @@ impl TermTui {
             parser.screen()
         };
 
+        let now = Instant::now();
+        let bells = parser.screen().bell_count();
+        let flashing = self.monitor.flashing(bells, now);
+        let blink_phase =
+            self.cursor_blink_epoch.elapsed().as_millis() / CURSOR_BLINK_INTERVAL.as_millis();
         let widget = TermTuiWidget::new(screen)
             .scroll_offset(screen.scrollback())
-            .copy_mode(&self.copy_mode);
+            .copy_mode(&self.copy_mode)
+            .focused(self.focused)
+            .show_cursor(!self.host_cursor)
+            .cursor_blink_on(blink_phase % 2 == 0)
+            .visual_bell(flashing);
 
         frame.render_widget(widget, area);
+        drop(parser);
+
+        if self.focused {
+            self.monitor.mark_viewed(bells, now);
+        }
+
+        if self.host_cursor && self.focused {
+            if let Some(position) = self.cursor_position(area) {
+                frame.set_cursor_position(position);
+            }
+        }
+    }
+
+    /// Get where the cursor is on screen when the content is drawn in `area`
+    ///
+    /// `None` while the cursor is hidden, scrolled out of view, outside
+    /// `area`, or replaced by the copy-mode cursor.
+    pub fn cursor_position(&self, area: Rect) -> Option<Position> {
+        if self.copy_mode.is_active() {
+            return None;
+        }
+        let parser = self.parser.lock().unwrap();
+        let screen = parser.screen();
+        let pos = screen.cursor_pos();
+        if !screen.cursor_visible()
+            || screen.scrollback() > 0
+            || pos.row >= area.height
+            || pos.col >= area.width
+        {
+            return None;
+        }
+        Some(Position::new(area.x + pos.col, area.y + pos.row))
     }
 
     /// Render terminal with borders
@@ -598,23 +1349,49 @@ impl TermTui {
         use ratatui::layout::{Constraint, Direction, Layout};
         use ratatui::text::{Line, Span};
 
+        self.poll_status();
+        self.apply_clipboard_requests();
+        self.tick_playback();
+
         let border_style = if self.focused {
             self.focused_border_style
         } else {
             self.border_style
         };
 
+        let mut title = match self.status.as_ref().and_then(ProcessStatus::label) {
+            Some(label) if self.exit_status_in_title => format!("{} [{}]", self.title, label),
+            _ => self.title.clone(),
+        };
+        if let Some(badge) = self.alerts().badge() {
+            title = format!("{} {}", title, badge);
+        }
+
         // Split area for content and hotkey footer
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([Constraint::Min(3), Constraint::Length(1)])
             .split(area);
 
-        let block = Block::default()
+        let mut block = Block::default()
             .borders(Borders::ALL)
             .border_type(BorderType::Rounded)
             .border_style(border_style)
-            .title(self.title.as_str());
+            .title(title);
+
+        // Show the exit status of the last finished shell command
+        let last_status = self.parser.lock().unwrap().screen().last_exit_code();
+        if let Some(code) = last_status {
+            let status = if code == 0 {
+                Span::styled(" ✓ ", Style::default().fg(RatatuiColor::Green))
+            } else {
+                Span::styled(
+                    format!(" ✗ {} ", code),
+                    Style::default().fg(RatatuiColor::Red),
+                )
+            };
+            block = block.title(Line::from(status).right_aligned());
+        }
 
         let inner = block.inner(chunks[0]);
         frame.render_widget(block, chunks[0]);
@@ -622,7 +1399,75 @@ impl TermTui {
 
         // Render hotkey footer based on mode (showing configured keybindings)
         let kb = &self.keybindings;
-        let hotkeys = if self.copy_mode.is_active() {
+        let search = self
+            .copy_mode
+            .search()
+            .filter(|search| search.editing || !search.query.is_empty());
+        let hotkeys = if let Some(search) = search.filter(|search| search.editing) {
+            let prompt = match search.direction {
+                SearchDirection::Forward => "/",
+                SearchDirection::Backward => "?",
+            };
+            let toggle_style = |on: bool| {
+                if on {
+                    Style::default().fg(RatatuiColor::Cyan)
+                } else {
+                    Style::default().fg(RatatuiColor::DarkGray)
+                }
+            };
+            let regex_key = TermTuiKeyBindings::key_to_display_string(&kb.copy_search_toggle_regex);
+            let case_key = TermTuiKeyBindings::key_to_display_string(&kb.copy_search_toggle_case);
+
+            Line::from(vec![
+                Span::styled(
+                    " SEARCH ",
+                    Style::default()
+                        .fg(RatatuiColor::Black)
+                        .bg(RatatuiColor::Yellow),
+                ),
+                Span::raw(format!(" {}{}", prompt, search.query)),
+                Span::styled("█", Style::default().fg(RatatuiColor::Gray)),
+                Span::raw(" "),
+                Span::styled(
+                    search_counter(search),
+                    Style::default().fg(RatatuiColor::Yellow),
+                ),
+                Span::raw(" "),
+                Span::styled(regex_key, toggle_style(search.options.regex)),
+                Span::styled(" regex ", toggle_style(search.options.regex)),
+                Span::styled(case_key, toggle_style(search.options.case_insensitive)),
+                Span::styled(
+                    " ignore case",
+                    toggle_style(search.options.case_insensitive),
+                ),
+            ])
+        } else if let Some(player) = self.player.as_ref().filter(|_| !self.copy_mode.is_active()) {
+            let state = if player.is_paused() {
+                "paused"
+            } else if player.is_finished() {
+                "finished"
+            } else {
+                "playing"
+            };
+            Line::from(vec![
+                Span::styled(
+                    " PLAYBACK ",
+                    Style::default()
+                        .fg(RatatuiColor::Black)
+                        .bg(RatatuiColor::Magenta),
+                ),
+                Span::raw(format!(
+                    " {} / {} ",
+                    format_playback_time(player.position()),
+                    format_playback_time(player.duration())
+                )),
+                Span::styled(
+                    format!("{}x ", player.speed()),
+                    Style::default().fg(RatatuiColor::Cyan),
+                ),
+                Span::styled(state, Style::default().fg(RatatuiColor::DarkGray)),
+            ])
+        } else if self.copy_mode.is_active() {
             // Build move keys display string
             let move_keys = format!(
                 "{}/{}",
@@ -630,6 +1475,11 @@ impl TermTui {
                 TermTuiKeyBindings::key_to_display_string(&kb.copy_move_down)
             );
             let select_key = TermTuiKeyBindings::key_to_display_string(&kb.copy_start_selection);
+            let select_shape_keys = format!(
+                "{}/{}",
+                TermTuiKeyBindings::key_to_display_string(&kb.copy_line_selection),
+                TermTuiKeyBindings::key_to_display_string(&kb.copy_block_selection)
+            );
             let copy_key = TermTuiKeyBindings::key_to_display_string(&kb.copy_and_exit);
             let word_keys = format!(
                 "{}/{}",
@@ -646,9 +1496,20 @@ impl TermTui {
                 TermTuiKeyBindings::key_to_display_string(&kb.copy_top),
                 TermTuiKeyBindings::key_to_display_string(&kb.copy_bottom)
             );
+            let prompt_keys = format!(
+                "{}/{}",
+                TermTuiKeyBindings::key_to_display_string(&kb.copy_prev_prompt),
+                TermTuiKeyBindings::key_to_display_string(&kb.copy_next_prompt)
+            );
+            let output_key = TermTuiKeyBindings::key_to_display_string(&kb.copy_select_output);
+            let search_keys = format!(
+                "{}/{}",
+                TermTuiKeyBindings::key_to_display_string(&kb.copy_search_forward),
+                TermTuiKeyBindings::key_to_display_string(&kb.copy_search_backward)
+            );
             let exit_key = TermTuiKeyBindings::key_to_display_string(&kb.copy_exit);
 
-            Line::from(vec![
+            let mut spans = vec![
                 Span::styled(
                     " COPY ",
                     Style::default()
@@ -656,10 +1517,21 @@ impl TermTui {
                         .bg(RatatuiColor::Yellow),
                 ),
                 Span::raw(" "),
+            ];
+            if let Some(search) = search {
+                spans.push(Span::styled(
+                    search_counter(search),
+                    Style::default().fg(RatatuiColor::Yellow),
+                ));
+                spans.push(Span::raw(" "));
+            }
+            spans.extend(vec![
                 Span::styled(move_keys, Style::default().fg(RatatuiColor::Cyan)),
                 Span::raw(" move "),
                 Span::styled(select_key, Style::default().fg(RatatuiColor::Cyan)),
                 Span::raw(" select "),
+                Span::styled(select_shape_keys, Style::default().fg(RatatuiColor::Cyan)),
+                Span::raw(" lines/block "),
                 Span::styled(copy_key, Style::default().fg(RatatuiColor::Cyan)),
                 Span::raw(" copy "),
                 Span::styled(word_keys, Style::default().fg(RatatuiColor::Cyan)),
@@ -668,9 +1540,16 @@ impl TermTui {
                 Span::raw(" line "),
                 Span::styled(top_bot_keys, Style::default().fg(RatatuiColor::Cyan)),
                 Span::raw(" top/bot "),
+                Span::styled(prompt_keys, Style::default().fg(RatatuiColor::Cyan)),
+                Span::raw(" prompt "),
+                Span::styled(output_key, Style::default().fg(RatatuiColor::Cyan)),
+                Span::raw(" output "),
+                Span::styled(search_keys, Style::default().fg(RatatuiColor::Cyan)),
+                Span::raw(" search "),
                 Span::styled(exit_key, Style::default().fg(RatatuiColor::Cyan)),
                 Span::raw(" exit"),
-            ])
+            ]);
+            Line::from(spans)
         } else {
             let enter_copy_key = TermTuiKeyBindings::key_to_display_string(&kb.enter_copy_mode);
             let copy_selection_key = TermTuiKeyBindings::key_to_display_string(&kb.copy_selection);
@@ -689,10 +1568,30 @@ impl TermTui {
     }
 }
 
+/// Format a playback position as `m:ss`
+fn format_playback_time(time: Duration) -> String {
+    let secs = time.as_secs();
+    format!("{}:{:02}", secs / 60, secs % 60)
+}
+
+/// Format the match counter shown in the copy-mode hint line
+fn search_counter(search: &CopySearch) -> String {
+    if search.invalid {
+        return "[invalid regex]".to_string();
+    }
+    match (search.current(), search.matches().len()) {
+        (_, 0) if search.query.is_empty() => String::new(),
+        (_, 0) => "[no matches]".to_string(),
+        (Some(idx), total) => format!("[{}/{}]", idx + 1, total),
+        (None, total) => format!("[{} matches]", total),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use ratatui::style::Modifier;
 
     #[test]
     fn test_termtui_creation() {
@@ -759,6 +1658,10 @@ mod tests {
         // Arrow up
         let key = KeyEvent::new(KeyCode::Up, KeyModifiers::NONE);
         assert_eq!(term.key_to_terminal_input(key), "\x1b[A");
+
+        // Arrow up in application cursor mode (DECCKM)
+        term.parser().lock().unwrap().process(b"\x1b[?1h");
+        assert_eq!(term.key_to_terminal_input(key), "\x1bOA");
     }
 
     #[test]
@@ -783,6 +1686,43 @@ mod tests {
         assert!(!term.has_selection());
     }
 
+    #[test]
+    fn test_termtui_selection_while_scrolled_back() {
+        use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+
+        let mut term = TermTui::new("Test");
+        let area = ratatui::layout::Rect::new(0, 0, 82, 26);
+        {
+            let mut parser = term.parser.lock().unwrap();
+            for i in 0..30 {
+                parser.process(format!("line {}\r\n", i).as_bytes());
+            }
+        }
+
+        // "line 7" is the top of the live screen, "line 4" is three rows above
+        term.scroll_up(3);
+
+        let down = MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column: 1,
+            row: 1,
+            modifiers: KeyModifiers::NONE,
+        };
+        term.handle_mouse(down, area);
+        let drag = MouseEvent {
+            kind: MouseEventKind::Drag(MouseButton::Left),
+            column: 12,
+            ..down
+        };
+        term.handle_mouse(drag, area);
+        assert_eq!(term.get_selected_text().as_deref(), Some("line 4"));
+
+        // Keyboard copy mode starts on the bottom row of the scrolled view
+        term.clear_selection();
+        term.enter_copy_mode();
+        assert_eq!(term.copy_mode.cursor(), Some(CopyPos::new(79, 20)));
+    }
+
     #[test]
     fn test_termtui_keybindings() {
         // Test default keybindings
@@ -816,6 +1756,369 @@ mod tests {
         assert_eq!(term.keybindings.enter_copy_mode.code, KeyCode::Char('c'));
     }
 
+    #[test]
+    fn test_render_content_resizes_terminal() {
+        use ratatui::backend::TestBackend;
+        use ratatui::Terminal;
+
+        let mut term = TermTui::new("Test");
+        let mut terminal = Terminal::new(TestBackend::new(50, 12)).unwrap();
+
+        terminal
+            .draw(|frame| term.render_content(frame, frame.area()))
+            .unwrap();
+
+        let size = term.parser().lock().unwrap().size();
+        assert_eq!(size.rows, 12);
+        assert_eq!(size.cols, 50);
+    }
+
+    #[test]
+    fn test_recording_playback_controls() {
+        let cast = "{\"version\": 2, \"width\": 12, \"height\": 3}\n\
+                    [1.0, \"o\", \"first\"]\n\
+                    [60.0, \"o\", \" second\"]\n";
+        let recording = AsciicastRecording::parse(cast).unwrap();
+        let mut term = TermTui::from_recording("Replay", recording);
+        assert_eq!(term.parser.lock().unwrap().size(), Size::new(12, 3));
+        assert!(term.player().is_some_and(|player| !player.is_paused()));
+
+        term.seek_playback(Duration::from_secs(2));
+        let contents = term.parser.lock().unwrap().screen().contents();
+        assert_eq!(contents.lines().next(), Some("first"));
+
+        term.toggle_playback();
+        assert!(term.player().unwrap().is_paused());
+        term.set_playback_speed(4.0);
+        assert_eq!(term.player().unwrap().speed(), 4.0);
+
+        term.seek_playback(Duration::from_secs(90));
+        assert!(term.player().unwrap().is_finished());
+        let contents = term.parser.lock().unwrap().screen().contents();
+        assert_eq!(contents.lines().next(), Some("first second"));
+
+        // Rendering into a larger area keeps the recorded size
+        use ratatui::backend::TestBackend;
+        use ratatui::Terminal;
+        let mut terminal = Terminal::new(TestBackend::new(40, 10)).unwrap();
+        terminal
+            .draw(|frame| term.render(frame, frame.area()))
+            .unwrap();
+        assert_eq!(term.parser.lock().unwrap().size(), Size::new(12, 3));
+    }
+
+    #[test]
+    fn test_cursor_position_and_host_cursor() {
+        use ratatui::backend::TestBackend;
+        use ratatui::Terminal;
+
+        let mut term = TermTui::new("Cursor").with_host_cursor(true);
+        term.focused = true;
+        term.parser.lock().unwrap().process(b"\x1b[2;4H");
+        let area = Rect::new(5, 5, 20, 10);
+        assert_eq!(term.cursor_position(area), Some(Position::new(8, 6)));
+        assert_eq!(term.cursor_position(Rect::new(0, 0, 3, 1)), None);
+
+        let mut terminal = Terminal::new(TestBackend::new(40, 12)).unwrap();
+        terminal
+            .draw(|frame| term.render(frame, frame.area()))
+            .unwrap();
+        // Inside the border, and not drawn into the buffer
+        assert_eq!(terminal.get_cursor_position().unwrap(), Position::new(4, 2));
+        let buffer = terminal.backend().buffer();
+        assert!(!buffer[(4, 2)].modifier.contains(Modifier::REVERSED));
+
+        term.parser.lock().unwrap().process(b"\x1b[?25l");
+        assert_eq!(term.cursor_position(area), None);
+        term.parser.lock().unwrap().process(b"\x1b[?25h");
+        term.enter_copy_mode();
+        assert_eq!(term.cursor_position(area), None);
+    }
+
+    #[test]
+    fn test_copy_uses_configured_clipboard() {
+        use crate::clipboard::MemoryClipboard;
+
+        let memory = MemoryClipboard::new();
+        let mut term = TermTui::new("Copy").with_clipboard(Clipboard::memory(memory.clone()));
+        term.parser.lock().unwrap().process(b"hello world");
+
+        term.enter_copy_mode();
+        if let CopyMode::Active { cursor, .. } = &mut term.copy_mode {
+            *cursor = CopyPos::new(0, 0);
+        }
+        term.copy_mode.set_anchor();
+        if let CopyMode::Active { cursor, .. } = &mut term.copy_mode {
+            cursor.x = 5;
+        }
+        term.handle_key(KeyEvent::new(KeyCode::Char('y'), KeyModifiers::NONE));
+        assert_eq!(memory.text().as_deref(), Some("hello"));
+        assert!(!term.copy_mode.is_active());
+    }
+
+    #[test]
+    fn test_preload_scrollback_discards_replies() {
+        let term = TermTui::new("Restored");
+        term.preload_scrollback("old output\x1b[6n\x1b[c\x1b]11;?\x07\n");
+
+        let mut parser = term.parser.lock().unwrap();
+        assert!(parser.take_replies().is_empty());
+        assert!(parser.screen().contents().contains("old output"));
+    }
+
+    #[test]
+    fn test_osc52_requests_are_opt_in() {
+        use crate::clipboard::MemoryClipboard;
+
+        let memory = MemoryClipboard::new();
+        let mut term = TermTui::new("Osc52").with_clipboard(Clipboard::memory(memory.clone()));
+        term.parser
+            .lock()
+            .unwrap()
+            .process(b"\x1b]52;c;aGVsbG8=\x07");
+        term.take_events();
+        assert_eq!(memory.text(), None);
+
+        let mut term = TermTui::new("Osc52")
+            .with_clipboard(Clipboard::memory(memory.clone()))
+            .with_osc52_policy(Osc52Policy::AllowWrite);
+        term.parser
+            .lock()
+            .unwrap()
+            .process(b"\x1b]52;c;aGVsbG8=\x07");
+        term.take_events();
+        assert_eq!(memory.text().as_deref(), Some("hello"));
+    }
+
+    #[test]
+    fn test_bell_alerts_and_visual_bell() {
+        use ratatui::backend::TestBackend;
+        use ratatui::Terminal;
+
+        let mut term = TermTui::new("Job").with_visual_bell(true);
+        term.parser.lock().unwrap().process(b"done\x07");
+        assert_eq!(term.alerts().badge().as_deref(), Some("!"));
+        assert_eq!(term.take_events(), vec![TermTuiEvent::Bell]);
+        assert!(term.take_events().is_empty());
+
+        // An unfocused terminal keeps the alert and shows it in the title
+        let mut terminal = Terminal::new(TestBackend::new(30, 8)).unwrap();
+        terminal
+            .draw(|frame| term.render(frame, frame.area()))
+            .unwrap();
+        let buffer = terminal.backend().buffer();
+        let top: String = (0..30).map(|x| buffer[(x, 0)].symbol()).collect();
+        assert!(top.contains("Job !"));
+        assert!(buffer[(1, 1)].modifier.contains(Modifier::REVERSED));
+        assert!(term.alerts().bell);
+
+        // Rendering it focused counts as viewing it
+        term.focused = true;
+        terminal
+            .draw(|frame| term.render(frame, frame.area()))
+            .unwrap();
+        assert!(term.alerts().is_empty());
+
+        let mut term = TermTui::new("Quiet").with_monitor_bell(false);
+        term.parser.lock().unwrap().process(b"\x07");
+        assert!(term.alerts().is_empty());
+        assert!(term.take_events().is_empty());
+    }
+
+    #[test]
+    fn test_format_playback_time() {
+        assert_eq!(format_playback_time(Duration::from_millis(5500)), "0:05");
+        assert_eq!(format_playback_time(Duration::from_secs(125)), "2:05");
+    }
+
+    #[test]
+    fn test_exit_status_in_title() {
+        use ratatui::backend::TestBackend;
+        use ratatui::Terminal;
+
+        let title_row = |term: &mut TermTui| {
+            let mut terminal = Terminal::new(TestBackend::new(40, 8)).unwrap();
+            terminal
+                .draw(|frame| term.render(frame, frame.area()))
+                .unwrap();
+            let buffer = terminal.backend().buffer();
+            (0..40)
+                .map(|x| buffer[(x, 0)].symbol().to_string())
+                .collect::<String>()
+        };
+
+        let mut term = TermTui::new("Build");
+        term.status = Some(ProcessStatus::Exited(1));
+        assert!(!title_row(&mut term).contains("[exited 1]"));
+
+        let mut term = TermTui::new("Build").with_exit_status_in_title(true);
+        assert!(!title_row(&mut term).contains('['));
+        term.status = Some(ProcessStatus::Exited(1));
+        assert!(title_row(&mut term).contains("Build [exited 1]"));
+    }
+
+    #[test]
+    fn test_copy_mode_line_and_block_selection_keys() {
+        let mut term = TermTui::new("Test");
+        term.parser()
+            .lock()
+            .unwrap()
+            .process(b"one two\r\nthree four");
+        term.enter_copy_mode();
+        if let CopyMode::Active { cursor, .. } = &mut term.copy_mode {
+            *cursor = CopyPos::new(1, 0);
+        }
+
+        term.handle_key(KeyEvent::new(KeyCode::Char('v'), KeyModifiers::CONTROL));
+        term.handle_key(KeyEvent::new(KeyCode::Char('j'), KeyModifiers::NONE));
+        term.handle_key(KeyEvent::new(KeyCode::Char('l'), KeyModifiers::NONE));
+        assert_eq!(term.copy_mode.selection_mode(), Some(SelectionMode::Block));
+        assert_eq!(term.get_selected_text(), Some("ne\nhr".to_string()));
+
+        // Terminals may or may not report Shift with an uppercase letter
+        term.handle_key(KeyEvent::new(KeyCode::Char('V'), KeyModifiers::NONE));
+        assert_eq!(term.copy_mode.selection_mode(), Some(SelectionMode::Line));
+        assert_eq!(
+            term.get_selected_text(),
+            Some("one two\nthree four".to_string())
+        );
+        term.handle_key(KeyEvent::new(KeyCode::Char('V'), KeyModifiers::SHIFT));
+        assert_eq!(term.copy_mode.selection_mode(), None);
+    }
+
+    #[test]
+    fn test_double_and_triple_click_selection() {
+        use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+
+        let mut term = TermTui::new("Test").with_word_separators(" /");
+        let area = ratatui::layout::Rect::new(0, 0, 82, 26);
+        term.parser()
+            .lock()
+            .unwrap()
+            .process(b"$ cat /etc/hosts\r\nok");
+
+        // Inside the border, (8, 1) is column 7 of the first row
+        let click = MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column: 8,
+            row: 1,
+            modifiers: KeyModifiers::NONE,
+        };
+        term.handle_mouse(click, area);
+        assert_eq!(term.get_selected_text(), None);
+        term.handle_mouse(click, area);
+        assert_eq!(term.get_selected_text(), Some("etc".to_string()));
+        term.handle_mouse(click, area);
+        assert_eq!(
+            term.get_selected_text(),
+            Some("$ cat /etc/hosts".to_string())
+        );
+
+        // A click elsewhere starts counting again
+        term.handle_mouse(MouseEvent { column: 3, ..click }, area);
+        assert_eq!(
+            term.get_selected_text(),
+            Some("$ cat /etc/hosts".to_string())
+        );
+        term.handle_mouse(MouseEvent { column: 3, ..click }, area);
+        assert_eq!(term.get_selected_text(), Some("cat".to_string()));
+    }
+
+    #[test]
+    fn test_mouse_reporting_bypasses_local_selection() {
+        use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+
+        let mut term = TermTui::new("Test");
+        let area = ratatui::layout::Rect::new(0, 0, 80, 24);
+        term.parser()
+            .lock()
+            .unwrap()
+            .process(b"\x1b[?1000h\x1b[?1006h");
+
+        let click = MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column: 5,
+            row: 5,
+            modifiers: KeyModifiers::NONE,
+        };
+        assert!(term.handle_mouse(click, area));
+        assert!(!term.has_selection());
+
+        // Shift-click falls back to local selection
+        let shift_click = MouseEvent {
+            modifiers: KeyModifiers::SHIFT,
+            ..click
+        };
+        assert!(term.handle_mouse(shift_click, area));
+        assert!(term.has_selection());
+    }
+
+    #[test]
+    fn test_copy_mode_search_keys() {
+        let mut term = TermTui::new("Test");
+        term.parser()
+            .lock()
+            .unwrap()
+            .process(b"alpha\r\nbeta\r\nalpha");
+        term.enter_copy_mode();
+
+        let press = |term: &mut TermTui, code: KeyCode| {
+            term.handle_key(KeyEvent::new(code, KeyModifiers::NONE));
+        };
+
+        // `?` searches backward from the bottom-right corner
+        press(&mut term, KeyCode::Char('?'));
+        for c in "alpha".chars() {
+            press(&mut term, KeyCode::Char(c));
+        }
+        press(&mut term, KeyCode::Enter);
+        assert_eq!(term.copy_mode.cursor(), Some(CopyPos::new(0, 2)));
+        assert_eq!(search_counter(term.copy_mode.search().unwrap()), "[2/2]");
+
+        // `n` repeats the backward search; Shift+N reverses it
+        press(&mut term, KeyCode::Char('n'));
+        assert_eq!(term.copy_mode.cursor(), Some(CopyPos::new(0, 0)));
+        term.handle_key(KeyEvent::new(KeyCode::Char('N'), KeyModifiers::SHIFT));
+        assert_eq!(term.copy_mode.cursor(), Some(CopyPos::new(0, 2)));
+
+        // Keys typed in the search prompt don't move the cursor
+        press(&mut term, KeyCode::Char('/'));
+        press(&mut term, KeyCode::Char('j'));
+        assert!(term.copy_mode.search().unwrap().editing);
+        press(&mut term, KeyCode::Esc);
+        assert!(term.copy_mode.is_active());
+        assert_eq!(term.copy_mode.cursor(), Some(CopyPos::new(0, 2)));
+    }
+
+    #[test]
+    fn test_ctrl_click_emits_link_event() {
+        use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+
+        let mut term = TermTui::new("Test");
+        let area = ratatui::layout::Rect::new(0, 0, 80, 24);
+        term.parser()
+            .lock()
+            .unwrap()
+            .process(b"open https://example.com now");
+
+        // Column 10 of the content area (inside the border)
+        let click = MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column: 11,
+            row: 1,
+            modifiers: KeyModifiers::CONTROL,
+        };
+        assert!(term.handle_mouse(click, area));
+        assert_eq!(
+            term.take_events(),
+            vec![TermTuiEvent::LinkClicked {
+                target: LinkTarget::Url("https://example.com".to_string())
+            }]
+        );
+        assert!(term.take_events().is_empty());
+        assert!(!term.has_selection());
+    }
+
     #[test]
     fn test_mouse_scroll() {
         use crossterm::event::{MouseEvent, MouseEventKind};
@@ -841,4 +2144,24 @@ mod tests {
         };
         assert!(term.handle_mouse(scroll_down, area));
     }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_restart_drops_output_of_the_old_reader() {
+        use std::os::unix::net::UnixStream;
+
+        let (mut old_output, reader) = UnixStream::pair().unwrap();
+        let mut term = TermTui::new("Test");
+        term.attach(Box::new(StreamBackend::read_only(reader)))
+            .unwrap();
+        term.command = Some(CommandBuilder::new("true"));
+        term.restart().unwrap();
+
+        // The old reader is still blocked in `read` and wakes up with this
+        old_output.write_all(b"stale output").unwrap();
+        std::thread::sleep(Duration::from_millis(100));
+
+        let contents = term.parser.lock().unwrap().screen().contents();
+        assert!(!contents.contains("stale"), "{contents:?}");
+    }
 }
@@ -0,0 +1,245 @@
+//! Bell, activity and silence monitoring (tmux-style alerts)
+
+use crate::termtui::event::TermTuiEvent;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// How long a visual bell inverts the screen
+const VISUAL_BELL_DURATION: Duration = Duration::from_millis(150);
+
+/// Alerts raised since a terminal was last viewed
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TermTuiAlerts {
+    /// The program rang the bell
+    pub bell: bool,
+    /// The program wrote output
+    pub activity: bool,
+    /// The program has been quiet for the silence threshold
+    pub silence: bool,
+}
+
+impl TermTuiAlerts {
+    /// Check whether no alert is raised
+    pub fn is_empty(&self) -> bool {
+        !(self.bell || self.activity || self.silence)
+    }
+
+    /// Get a tmux-style badge: `!` for a bell, `#` for activity, `~` for silence
+    pub fn badge(&self) -> Option<String> {
+        let badge: String = [(self.bell, '!'), (self.activity, '#'), (self.silence, '~')]
+            .into_iter()
+            .filter_map(|(on, flag)| on.then_some(flag))
+            .collect();
+        (!badge.is_empty()).then_some(badge)
+    }
+}
+
+/// Output timing updated by the reader thread
+#[derive(Debug)]
+pub(crate) struct OutputActivity {
+    /// Number of output batches read
+    batches: AtomicU64,
+    /// When output was last read
+    last_output: Mutex<Instant>,
+}
+
+impl OutputActivity {
+    pub(crate) fn new() -> Self {
+        Self {
+            batches: AtomicU64::new(0),
+            last_output: Mutex::new(Instant::now()),
+        }
+    }
+
+    /// Note that a batch of output was read
+    pub(crate) fn record(&self) {
+        if let Ok(mut last_output) = self.last_output.lock() {
+            *last_output = Instant::now();
+        }
+        self.batches.fetch_add(1, Ordering::Release);
+    }
+
+    fn batches(&self) -> u64 {
+        self.batches.load(Ordering::Acquire)
+    }
+
+    fn last_output(&self) -> Instant {
+        *self.last_output.lock().unwrap()
+    }
+}
+
+/// Monitoring settings, and what was last viewed and reported
+///
+/// Bells are counted by the screen; the counts passed in here are compared
+/// against the counts seen when the terminal was last viewed.
+#[derive(Debug)]
+pub(crate) struct Monitor {
+    /// Raise an alert when the bell rings
+    pub(crate) bell: bool,
+    /// Raise an alert on any output
+    pub(crate) activity: bool,
+    /// Raise an alert after this long without output
+    pub(crate) silence: Option<Duration>,
+    /// Invert the screen briefly when the bell rings
+    pub(crate) visual_bell: bool,
+    pub(crate) output: Arc<OutputActivity>,
+    bells_viewed: u64,
+    batches_viewed: u64,
+    viewed_at: Instant,
+    bells_reported: u64,
+    /// Output time of the quiet period last reported as silence
+    silence_reported: Option<Instant>,
+    bells_flashed: u64,
+    flash_until: Option<Instant>,
+}
+
+impl Monitor {
+    pub(crate) fn new() -> Self {
+        Self {
+            bell: true,
+            activity: false,
+            silence: None,
+            visual_bell: false,
+            output: Arc::new(OutputActivity::new()),
+            bells_viewed: 0,
+            batches_viewed: 0,
+            viewed_at: Instant::now(),
+            bells_reported: 0,
+            silence_reported: None,
+            bells_flashed: 0,
+            flash_until: None,
+        }
+    }
+
+    /// Get the alerts raised since the terminal was last viewed
+    pub(crate) fn alerts(&self, bells: u64, now: Instant) -> TermTuiAlerts {
+        TermTuiAlerts {
+            bell: self.bell && bells > self.bells_viewed,
+            activity: self.activity && self.output.batches() > self.batches_viewed,
+            silence: self
+                .quiet_since(now)
+                .is_some_and(|last| last > self.viewed_at),
+        }
+    }
+
+    /// Clear the alerts
+    pub(crate) fn mark_viewed(&mut self, bells: u64, now: Instant) {
+        self.bells_viewed = bells;
+        self.batches_viewed = self.output.batches();
+        self.viewed_at = now;
+    }
+
+    /// Get events for bells and silences not reported yet
+    pub(crate) fn take_events(&mut self, bells: u64, now: Instant) -> Vec<TermTuiEvent> {
+        let mut events = Vec::new();
+        if self.bell && bells > self.bells_reported {
+            events.push(TermTuiEvent::Bell);
+        }
+        self.bells_reported = bells;
+
+        if let Some(last) = self.quiet_since(now) {
+            if self.silence_reported != Some(last) && self.output.batches() > 0 {
+                self.silence_reported = Some(last);
+                events.push(TermTuiEvent::Silence);
+            }
+        }
+        events
+    }
+
+    /// Check whether a visual bell flash is showing, starting one for new bells
+    pub(crate) fn flashing(&mut self, bells: u64, now: Instant) -> bool {
+        if self.visual_bell && bells > self.bells_flashed {
+            self.flash_until = Some(now + VISUAL_BELL_DURATION);
+        }
+        self.bells_flashed = bells;
+        self.flash_until.is_some_and(|until| now < until)
+    }
+
+    /// Get when output last arrived, if the silence threshold has passed since
+    fn quiet_since(&self, now: Instant) -> Option<Instant> {
+        let threshold = self.silence?;
+        let last = self.output.last_output();
+        (now.saturating_duration_since(last) >= threshold).then_some(last)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_badge() {
+        assert_eq!(TermTuiAlerts::default().badge(), None);
+        let alerts = TermTuiAlerts {
+            bell: true,
+            activity: true,
+            silence: false,
+        };
+        assert!(!alerts.is_empty());
+        assert_eq!(alerts.badge().as_deref(), Some("!#"));
+    }
+
+    #[test]
+    fn test_bell_and_activity_until_viewed() {
+        let mut monitor = Monitor::new();
+        monitor.activity = true;
+        let now = Instant::now();
+        assert!(monitor.alerts(0, now).is_empty());
+
+        monitor.output.record();
+        let alerts = monitor.alerts(2, now);
+        assert!(alerts.bell && alerts.activity && !alerts.silence);
+
+        // Events are reported once, alerts stay until viewed
+        assert_eq!(monitor.take_events(2, now), vec![TermTuiEvent::Bell]);
+        assert!(monitor.take_events(2, now).is_empty());
+        assert!(monitor.alerts(2, now).bell);
+
+        monitor.mark_viewed(2, now);
+        assert!(monitor.alerts(2, now).is_empty());
+        assert!(monitor.alerts(3, now).bell);
+
+        monitor.bell = false;
+        assert!(!monitor.alerts(4, now).bell);
+        assert!(monitor.take_events(4, now).is_empty());
+    }
+
+    #[test]
+    fn test_silence() {
+        let mut monitor = Monitor::new();
+        monitor.silence = Some(Duration::from_secs(5));
+        let start = Instant::now();
+        monitor.mark_viewed(0, start);
+        assert!(monitor
+            .take_events(0, start + Duration::from_secs(10))
+            .is_empty());
+
+        std::thread::sleep(Duration::from_millis(2));
+        monitor.output.record();
+        let later = Instant::now();
+        assert!(!monitor.alerts(0, later).silence);
+        assert!(monitor.take_events(0, later).is_empty());
+
+        let quiet = later + Duration::from_secs(5);
+        assert!(monitor.alerts(0, quiet).silence);
+        assert_eq!(monitor.take_events(0, quiet), vec![TermTuiEvent::Silence]);
+        assert!(monitor.take_events(0, quiet).is_empty());
+
+        // Viewing clears the alert until the next output goes quiet
+        monitor.mark_viewed(0, quiet);
+        assert!(!monitor.alerts(0, quiet + Duration::from_secs(60)).silence);
+    }
+
+    #[test]
+    fn test_visual_bell_flash() {
+        let mut monitor = Monitor::new();
+        let now = Instant::now();
+        assert!(!monitor.flashing(1, now));
+
+        monitor.visual_bell = true;
+        assert!(monitor.flashing(2, now));
+        assert!(monitor.flashing(2, now + Duration::from_millis(100)));
+        assert!(!monitor.flashing(2, now + VISUAL_BELL_DURATION));
+    }
+}
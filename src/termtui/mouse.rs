@@ -0,0 +1,262 @@
+//! Mouse reporting for child programs (xterm mouse tracking protocols)
+
+use crossterm::event::{KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+
+/// Mouse tracking mode requested by the child program
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MouseProtocolMode {
+    /// Mouse reporting disabled
+    #[default]
+    None,
+    /// X10 compatibility mode (`CSI ? 9 h`): button presses only
+    Press,
+    /// Normal tracking (`CSI ? 1000 h`): presses and releases
+    PressRelease,
+    /// Button-event tracking (`CSI ? 1002 h`): presses, releases and drags
+    ButtonMotion,
+    /// Any-event tracking (`CSI ? 1003 h`): all motion, with or without buttons
+    AnyMotion,
+}
+
+/// Encoding used for mouse reports
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MouseProtocolEncoding {
+    /// Legacy `CSI M Cb Cx Cy` bytes, limited to 223 columns/rows
+    #[default]
+    Default,
+    /// UTF-8 extended coordinates (`CSI ? 1005 h`)
+    Utf8,
+    /// SGR extended reports (`CSI ? 1006 h`): `CSI < Cb ; Cx ; Cy M/m`
+    Sgr,
+}
+
+/// Encode a mouse event as the report a child program expects
+///
+/// `col` and `row` are zero-based cell coordinates relative to the terminal
+/// content. Returns `None` when the event should not be reported in the given
+/// mode, or when the position cannot be represented by the encoding.
+pub fn encode_mouse_event(
+    mode: MouseProtocolMode,
+    encoding: MouseProtocolEncoding,
+    event: &MouseEvent,
+    col: u16,
+    row: u16,
+) -> Option<Vec<u8>> {
+    let (button, release, motion) = match event.kind {
+        MouseEventKind::Down(button) => (button_code(button), false, false),
+        MouseEventKind::Up(button) => (button_code(button), true, false),
+        MouseEventKind::Drag(button) => (button_code(button), false, true),
+        MouseEventKind::Moved => (3, false, true),
+        MouseEventKind::ScrollUp => (64, false, false),
+        MouseEventKind::ScrollDown => (65, false, false),
+        MouseEventKind::ScrollLeft => (66, false, false),
+        MouseEventKind::ScrollRight => (67, false, false),
+    };
+
+    let reported = match mode {
+        MouseProtocolMode::None => false,
+        MouseProtocolMode::Press => !release && !motion,
+        MouseProtocolMode::PressRelease => !motion,
+        MouseProtocolMode::ButtonMotion => !matches!(event.kind, MouseEventKind::Moved),
+        MouseProtocolMode::AnyMotion => true,
+    };
+    if !reported {
+        return None;
+    }
+
+    // X10 mode does not report modifiers
+    let mut modifiers = 0;
+    if mode != MouseProtocolMode::Press {
+        if event.modifiers.contains(KeyModifiers::SHIFT) {
+            modifiers |= 4;
+        }
+        if event.modifiers.contains(KeyModifiers::ALT) {
+            modifiers |= 8;
+        }
+        if event.modifiers.contains(KeyModifiers::CONTROL) {
+            modifiers |= 16;
+        }
+    }
+    let motion = if motion { 32 } else { 0 };
+
+    match encoding {
+        MouseProtocolEncoding::Sgr => {
+            let code = button | modifiers | motion;
+            let action = if release { 'm' } else { 'M' };
+            Some(format!("\x1b[<{};{};{}{}", code, col + 1, row + 1, action).into_bytes())
+        }
+        MouseProtocolEncoding::Default | MouseProtocolEncoding::Utf8 => {
+            // Legacy encodings cannot tell which button was released
+            let button = if release { 3 } else { button };
+            let code = 32 + (button | modifiers | motion) as u32;
+            let x = 32 + col as u32 + 1;
+            let y = 32 + row as u32 + 1;
+
+            let mut bytes = b"\x1b[M".to_vec();
+            if encoding == MouseProtocolEncoding::Default {
+                if x > 255 || y > 255 {
+                    return None;
+                }
+                bytes.extend([code as u8, x as u8, y as u8]);
+            } else {
+                if x > 2047 || y > 2047 {
+                    return None;
+                }
+                let mut buf = [0u8; 4];
+                for value in [code, x, y] {
+                    let c = char::from_u32(value)?;
+                    bytes.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+                }
+            }
+            Some(bytes)
+        }
+    }
+}
+
+/// Get the base button code for a mouse button
+fn button_code(button: MouseButton) -> u8 {
+    match button {
+        MouseButton::Left => 0,
+        MouseButton::Middle => 1,
+        MouseButton::Right => 2,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(kind: MouseEventKind, modifiers: KeyModifiers) -> MouseEvent {
+        MouseEvent {
+            kind,
+            column: 0,
+            row: 0,
+            modifiers,
+        }
+    }
+
+    fn press() -> MouseEvent {
+        event(MouseEventKind::Down(MouseButton::Left), KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn test_disabled_reports_nothing() {
+        let bytes = encode_mouse_event(
+            MouseProtocolMode::None,
+            MouseProtocolEncoding::Sgr,
+            &press(),
+            0,
+            0,
+        );
+        assert_eq!(bytes, None);
+    }
+
+    #[test]
+    fn test_default_encoding_press_release() {
+        let mode = MouseProtocolMode::PressRelease;
+        let encoding = MouseProtocolEncoding::Default;
+
+        let bytes = encode_mouse_event(mode, encoding, &press(), 4, 9);
+        assert_eq!(bytes.unwrap(), b"\x1b[M\x20\x25\x2a");
+
+        let release = event(MouseEventKind::Up(MouseButton::Left), KeyModifiers::NONE);
+        let bytes = encode_mouse_event(mode, encoding, &release, 4, 9);
+        assert_eq!(bytes.unwrap(), b"\x1b[M\x23\x25\x2a");
+    }
+
+    #[test]
+    fn test_default_encoding_out_of_range() {
+        let bytes = encode_mouse_event(
+            MouseProtocolMode::PressRelease,
+            MouseProtocolEncoding::Default,
+            &press(),
+            300,
+            0,
+        );
+        assert_eq!(bytes, None);
+    }
+
+    #[test]
+    fn test_utf8_encoding_large_coordinates() {
+        let bytes = encode_mouse_event(
+            MouseProtocolMode::PressRelease,
+            MouseProtocolEncoding::Utf8,
+            &press(),
+            299,
+            0,
+        )
+        .unwrap();
+
+        let mut expected = b"\x1b[M ".to_vec();
+        expected.extend("\u{14c}!".as_bytes());
+        assert_eq!(bytes, expected);
+    }
+
+    #[test]
+    fn test_sgr_encoding() {
+        let mode = MouseProtocolMode::PressRelease;
+        let encoding = MouseProtocolEncoding::Sgr;
+
+        let right = event(MouseEventKind::Down(MouseButton::Right), KeyModifiers::NONE);
+        let bytes = encode_mouse_event(mode, encoding, &right, 9, 19).unwrap();
+        assert_eq!(bytes, b"\x1b[<2;10;20M");
+
+        let release = event(MouseEventKind::Up(MouseButton::Right), KeyModifiers::NONE);
+        let bytes = encode_mouse_event(mode, encoding, &release, 9, 19).unwrap();
+        assert_eq!(bytes, b"\x1b[<2;10;20m");
+    }
+
+    #[test]
+    fn test_sgr_modifiers_and_wheel() {
+        let mode = MouseProtocolMode::PressRelease;
+        let encoding = MouseProtocolEncoding::Sgr;
+
+        let click = event(
+            MouseEventKind::Down(MouseButton::Left),
+            KeyModifiers::CONTROL | KeyModifiers::ALT,
+        );
+        let bytes = encode_mouse_event(mode, encoding, &click, 0, 0).unwrap();
+        assert_eq!(bytes, b"\x1b[<24;1;1M");
+
+        let wheel = event(MouseEventKind::ScrollDown, KeyModifiers::NONE);
+        let bytes = encode_mouse_event(mode, encoding, &wheel, 0, 0).unwrap();
+        assert_eq!(bytes, b"\x1b[<65;1;1M");
+    }
+
+    #[test]
+    fn test_x10_reports_presses_only() {
+        let mode = MouseProtocolMode::Press;
+        let encoding = MouseProtocolEncoding::Sgr;
+
+        let click = event(MouseEventKind::Down(MouseButton::Left), KeyModifiers::ALT);
+        let bytes = encode_mouse_event(mode, encoding, &click, 0, 0).unwrap();
+        assert_eq!(bytes, b"\x1b[<0;1;1M");
+
+        let release = event(MouseEventKind::Up(MouseButton::Left), KeyModifiers::NONE);
+        assert_eq!(encode_mouse_event(mode, encoding, &release, 0, 0), None);
+    }
+
+    #[test]
+    fn test_motion_modes() {
+        let encoding = MouseProtocolEncoding::Sgr;
+        let drag = event(MouseEventKind::Drag(MouseButton::Left), KeyModifiers::NONE);
+        let moved = event(MouseEventKind::Moved, KeyModifiers::NONE);
+
+        assert_eq!(
+            encode_mouse_event(MouseProtocolMode::PressRelease, encoding, &drag, 0, 0),
+            None
+        );
+
+        let bytes =
+            encode_mouse_event(MouseProtocolMode::ButtonMotion, encoding, &drag, 1, 1).unwrap();
+        assert_eq!(bytes, b"\x1b[<32;2;2M");
+        assert_eq!(
+            encode_mouse_event(MouseProtocolMode::ButtonMotion, encoding, &moved, 1, 1),
+            None
+        );
+
+        let bytes =
+            encode_mouse_event(MouseProtocolMode::AnyMotion, encoding, &moved, 1, 1).unwrap();
+        assert_eq!(bytes, b"\x1b[<35;2;2M");
+    }
+}
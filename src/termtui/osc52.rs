@@ -0,0 +1,13 @@
+//! Policy for OSC 52 clipboard requests from programs in the terminal
+
+/// What a program in the terminal may do with the clipboard through OSC 52
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Osc52Policy {
+    /// Ignore OSC 52 requests
+    #[default]
+    Deny,
+    /// Let programs set the clipboard (e.g. yanking in a remote vim)
+    ///
+    /// Reading the clipboard is never allowed.
+    AllowWrite,
+}
@@ -0,0 +1,131 @@
+//! Terminal color palette (answers OSC 4/10/11 color queries)
+
+/// An RGB color triple
+pub type Rgb = (u8, u8, u8);
+
+/// Color palette reported to child programs
+///
+/// Programs such as neovim and fish query the terminal colors (OSC 10/11 for
+/// the default foreground/background, OSC 4 for indexed colors) to pick a
+/// light or dark theme. The defaults match xterm's palette on a dark
+/// background.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Palette {
+    /// Default foreground color (OSC 10)
+    pub foreground: Rgb,
+    /// Default background color (OSC 11)
+    pub background: Rgb,
+    /// Cursor color (OSC 12)
+    pub cursor: Rgb,
+    /// Indexed colors 0-255 (OSC 4)
+    pub colors: [Rgb; 256],
+}
+
+impl Palette {
+    /// Create the default xterm palette
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the default foreground color (builder pattern)
+    pub fn with_foreground(mut self, color: Rgb) -> Self {
+        self.foreground = color;
+        self
+    }
+
+    /// Set the default background color (builder pattern)
+    pub fn with_background(mut self, color: Rgb) -> Self {
+        self.background = color;
+        self
+    }
+
+    /// Set the cursor color (builder pattern)
+    pub fn with_cursor(mut self, color: Rgb) -> Self {
+        self.cursor = color;
+        self
+    }
+
+    /// Set an indexed color (builder pattern)
+    pub fn with_color(mut self, index: u8, color: Rgb) -> Self {
+        self.colors[index as usize] = color;
+        self
+    }
+
+    /// Format a color as an X11 `rgb:rrrr/gggg/bbbb` specification
+    pub fn x11_color_spec(color: Rgb) -> String {
+        let (r, g, b) = color;
+        format!(
+            "rgb:{:02x}{:02x}/{:02x}{:02x}/{:02x}{:02x}",
+            r, r, g, g, b, b
+        )
+    }
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        const BASE: [Rgb; 16] = [
+            (0x00, 0x00, 0x00),
+            (0xcd, 0x00, 0x00),
+            (0x00, 0xcd, 0x00),
+            (0xcd, 0xcd, 0x00),
+            (0x00, 0x00, 0xee),
+            (0xcd, 0x00, 0xcd),
+            (0x00, 0xcd, 0xcd),
+            (0xe5, 0xe5, 0xe5),
+            (0x7f, 0x7f, 0x7f),
+            (0xff, 0x00, 0x00),
+            (0x00, 0xff, 0x00),
+            (0xff, 0xff, 0x00),
+            (0x5c, 0x5c, 0xff),
+            (0xff, 0x00, 0xff),
+            (0x00, 0xff, 0xff),
+            (0xff, 0xff, 0xff),
+        ];
+        const CUBE: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+        let mut colors = [(0, 0, 0); 256];
+        colors[..16].copy_from_slice(&BASE);
+
+        // 6x6x6 color cube
+        for (i, color) in colors[16..232].iter_mut().enumerate() {
+            *color = (CUBE[i / 36], CUBE[(i / 6) % 6], CUBE[i % 6]);
+        }
+
+        // Grayscale ramp
+        for (i, color) in colors[232..].iter_mut().enumerate() {
+            let level = 8 + 10 * i as u8;
+            *color = (level, level, level);
+        }
+
+        Self {
+            foreground: BASE[7],
+            background: BASE[0],
+            cursor: BASE[7],
+            colors,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_palette() {
+        let palette = Palette::default();
+        assert_eq!(palette.colors[1], (0xcd, 0x00, 0x00));
+        assert_eq!(palette.colors[16], (0, 0, 0));
+        assert_eq!(palette.colors[196], (255, 0, 0));
+        assert_eq!(palette.colors[231], (255, 255, 255));
+        assert_eq!(palette.colors[232], (8, 8, 8));
+        assert_eq!(palette.colors[255], (238, 238, 238));
+    }
+
+    #[test]
+    fn test_x11_color_spec() {
+        assert_eq!(
+            Palette::x11_color_spec((0x12, 0xab, 0xff)),
+            "rgb:1212/abab/ffff"
+        );
+    }
+}
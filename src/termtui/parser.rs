@@ -1,5 +1,6 @@
 //! Terminal parser - bridge between termwiz and Screen
 
+use crate::termtui::palette::Palette;
 use crate::termtui::screen::Screen;
 use crate::termtui::size::Size;
 use std::io::Write;
@@ -31,6 +32,23 @@ impl Parser {
         });
     }
 
+    /// Process text saved by an ANSI export
+    ///
+    /// Bare line feeds become CR LF, as a terminal's output processing would
+    /// do, so each line starts at the left edge.
+    pub fn load_ansi(&mut self, text: &str) {
+        let mut bytes = Vec::with_capacity(text.len() + text.len() / 32);
+        let mut prev = 0;
+        for &b in text.as_bytes() {
+            if b == b'\n' && prev != b'\r' {
+                bytes.push(b'\r');
+            }
+            bytes.push(b);
+            prev = b;
+        }
+        self.process(&bytes);
+    }
+
     /// Get the screen state
     pub fn screen(&self) -> &Screen {
         &self.screen
@@ -41,6 +59,16 @@ impl Parser {
         &mut self.screen
     }
 
+    /// Take reply bytes (answers to terminal queries) to write back to the PTY
+    pub fn take_replies(&mut self) -> Vec<u8> {
+        self.screen.take_replies()
+    }
+
+    /// Set the palette reported to color queries
+    pub fn set_palette(&mut self, palette: Palette) {
+        self.screen.set_palette(palette);
+    }
+
     /// Resize the terminal
     pub fn resize(&mut self, rows: usize, cols: usize) {
         self.screen.resize(rows, cols);
@@ -0,0 +1,80 @@
+//! Status of the process attached to a terminal
+
+use portable_pty::ExitStatus;
+
+/// Lifecycle state of the child process
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ProcessStatus {
+    /// The process is still running
+    Running,
+    /// The process exited with a status code
+    Exited(i32),
+    /// The process was terminated by a signal (e.g. "Killed")
+    Signaled(String),
+}
+
+impl ProcessStatus {
+    /// Check if the process is still running
+    pub fn is_running(&self) -> bool {
+        matches!(self, ProcessStatus::Running)
+    }
+
+    /// Get a short label for titles (`exited 1`), or `None` while running
+    pub fn label(&self) -> Option<String> {
+        match self {
+            ProcessStatus::Running => None,
+            ProcessStatus::Exited(code) => Some(format!("exited {}", code)),
+            ProcessStatus::Signaled(signal) => Some(signal.to_lowercase()),
+        }
+    }
+}
+
+impl From<ExitStatus> for ProcessStatus {
+    fn from(status: ExitStatus) -> Self {
+        // portable-pty only exposes the signal through its Display impl
+        match status.to_string().strip_prefix("Terminated by ") {
+            Some(signal) => ProcessStatus::Signaled(signal.to_string()),
+            None => ProcessStatus::Exited(status.exit_code() as i32),
+        }
+    }
+}
+
+impl From<std::process::ExitStatus> for ProcessStatus {
+    fn from(status: std::process::ExitStatus) -> Self {
+        ExitStatus::from(status).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_exit_status() {
+        assert_eq!(
+            ProcessStatus::from(ExitStatus::with_exit_code(3)),
+            ProcessStatus::Exited(3)
+        );
+        assert_eq!(
+            ProcessStatus::from(ExitStatus::with_exit_code(0)),
+            ProcessStatus::Exited(0)
+        );
+        assert_eq!(
+            ProcessStatus::from(ExitStatus::with_signal("Killed")),
+            ProcessStatus::Signaled("Killed".to_string())
+        );
+    }
+
+    #[test]
+    fn test_label() {
+        assert_eq!(ProcessStatus::Running.label(), None);
+        assert_eq!(
+            ProcessStatus::Exited(1).label(),
+            Some("exited 1".to_string())
+        );
+        assert_eq!(
+            ProcessStatus::Signaled("Terminated".to_string()).label(),
+            Some("terminated".to_string())
+        );
+    }
+}
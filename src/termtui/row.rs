@@ -2,6 +2,7 @@
 
 use crate::termtui::attrs::Attrs;
 use crate::termtui::cell::Cell;
+use crate::termtui::shell_integration::RowMarks;
 
 /// A row of terminal cells
 #[derive(Clone, Debug)]
@@ -12,6 +13,8 @@ pub struct Row {
     size: u16,
     /// Whether this row wraps to the next line
     wrapped: bool,
+    /// Shell integration (OSC 133) markers
+    marks: RowMarks,
 }
 
 impl Row {
@@ -21,6 +24,7 @@ impl Row {
             cells: (0..width).map(|_| Cell::new()).collect(),
             size: 0,
             wrapped: false,
+            marks: RowMarks::default(),
         }
     }
 
@@ -30,6 +34,7 @@ impl Row {
             cells: (0..width).map(|_| Cell::with_attrs(attrs)).collect(),
             size: 0,
             wrapped: false,
+            marks: RowMarks::default(),
         }
     }
 
@@ -77,6 +82,7 @@ impl Row {
         }
         self.size = 0;
         self.wrapped = false;
+        self.marks = RowMarks::default();
     }
 
     /// Clear cells from start to end (exclusive)
@@ -109,6 +115,16 @@ impl Row {
         self.wrapped = wrapped;
     }
 
+    /// Get the shell integration markers on this row
+    pub fn marks(&self) -> &RowMarks {
+        &self.marks
+    }
+
+    /// Get mutable shell integration markers
+    pub fn marks_mut(&mut self) -> &mut RowMarks {
+        &mut self.marks
+    }
+
     /// Check if a column is a wide character continuation
     pub fn is_wide_continuation(&self, col: u16) -> bool {
         self.cells
@@ -137,9 +153,14 @@ impl Row {
 
     /// Write cell contents to a string (for text extraction)
     pub fn write_contents(&self, output: &mut String, start: u16, end: u16) {
-        let start = start as usize;
+        let mut start = start as usize;
         let end = (end as usize).min(self.cells.len());
 
+        // Starting on a continuation still includes the wide character
+        if start > 0 && start < end && self.cells[start].is_wide_continuation() {
+            start -= 1;
+        }
+
         for cell in &self.cells[start..end] {
             if !cell.is_wide_continuation() {
                 output.push_str(cell.text());
@@ -1,22 +1,53 @@
 //! Terminal screen state manager
 
 use crate::termtui::attrs::{Attrs, Color};
+use crate::termtui::cell::Cell;
+use crate::termtui::charset::Charset;
+use crate::termtui::cursor::CursorShape;
+use crate::termtui::export::{export_grid, ExportFormat};
 use crate::termtui::grid::{Grid, Pos};
+use crate::termtui::hyperlink::{detect_row_links, DetectedLink, Hyperlink, LinkTarget};
+use crate::termtui::key_encoder::KeyboardModes;
+use crate::termtui::mouse::{MouseProtocolEncoding, MouseProtocolMode};
+use crate::termtui::osc52::Osc52Policy;
+use crate::termtui::palette::Palette;
+use crate::termtui::search::{find_matches, SearchMatch};
+use crate::termtui::shell_integration::{collect_commands, parse_cwd_uri, RowMarks, ShellCommand};
 use crate::termtui::size::Size;
-use termwiz::escape::csi::{Cursor, Edit, EraseInDisplay, EraseInLine, Mode, Sgr};
+use regex::Regex;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use termwiz::escape::csi::{
+    Cursor, CursorTabulationControl, DecPrivateMode, DecPrivateModeCode, Device, Edit,
+    EraseInDisplay, EraseInLine, Keyboard, KittyKeyboardMode, Mode, Sgr, TabulationClear,
+    TerminalMode, TerminalModeCode,
+};
+use termwiz::escape::osc::{ColorOrQuery, DynamicColorNumber, FinalTermSemanticPrompt};
 use termwiz::escape::{Action, ControlCode, Esc, EscCode, OperatingSystemCommand};
+use unicode_segmentation::UnicodeSegmentation;
 use unicode_width::UnicodeWidthChar;
 
 // Terminal mode flags
-const MODE_CURSOR_VISIBLE: u8 = 1 << 0;
-const MODE_ALTERNATE_SCREEN: u8 = 1 << 1;
-#[allow(dead_code)]
-const MODE_APPLICATION_CURSOR: u8 = 1 << 2;
-#[allow(dead_code)]
-const MODE_BRACKETED_PASTE: u8 = 1 << 3;
-const MODE_AUTO_WRAP: u8 = 1 << 4;
-#[allow(dead_code)]
-const MODE_ORIGIN: u8 = 1 << 5;
+const MODE_CURSOR_VISIBLE: u16 = 1 << 0;
+const MODE_ALTERNATE_SCREEN: u16 = 1 << 1;
+const MODE_APPLICATION_CURSOR: u16 = 1 << 2;
+const MODE_BRACKETED_PASTE: u16 = 1 << 3;
+const MODE_AUTO_WRAP: u16 = 1 << 4;
+const MODE_ORIGIN: u16 = 1 << 5;
+const MODE_CURSOR_BLINK: u16 = 1 << 6;
+const MODE_APPLICATION_KEYPAD: u16 = 1 << 7;
+const MODE_INSERT: u16 = 1 << 8;
+
+/// Default distance between tab stops
+const TAB_WIDTH: usize = 8;
+
+/// Tab stops every `TAB_WIDTH` columns
+fn default_tab_stops(cols: usize) -> Vec<bool> {
+    (0..cols).map(|col| col % TAB_WIDTH == 0).collect()
+}
+
+/// Maximum depth of the kitty keyboard flags stack
+const KITTY_KEYBOARD_STACK_LIMIT: usize = 16;
 
 /// Terminal screen state
 #[derive(Clone)]
@@ -27,14 +58,50 @@ pub struct Screen {
     alternate_grid: Grid,
     /// Current text attributes
     attrs: Attrs,
+    /// Active OSC 8 hyperlink applied to printed cells
+    hyperlink: Option<Arc<Hyperlink>>,
     /// Terminal modes
-    modes: u8,
+    modes: u16,
+    /// Cursor shape requested with DECSCUSR
+    cursor_shape: CursorShape,
     /// Window title
     title: String,
     /// Icon name
     icon_name: String,
+    /// Working directory reported by the shell (OSC 7)
+    current_dir: Option<PathBuf>,
+    /// Whether a shell command was started (OSC 133;A) and has not finished
+    command_open: bool,
+    /// Exit code of the last finished shell command (OSC 133;D)
+    last_exit_code: Option<i32>,
     /// Pending wrap (cursor at end of line)
     pending_wrap: bool,
+    /// Character sets designated to G0 and G1
+    charsets: [Charset; 2],
+    /// Whether G1 is shifted in (SO) instead of G0 (SI)
+    shift_out: bool,
+    /// Tab stop flag for each column
+    tab_stops: Vec<bool>,
+    /// Mouse tracking mode requested by the application
+    mouse_protocol_mode: MouseProtocolMode,
+    /// Mouse report encoding requested by the application
+    mouse_protocol_encoding: MouseProtocolEncoding,
+    /// Palette reported in answer to color queries
+    palette: Palette,
+    /// Reply bytes waiting to be written back to the application
+    replies: Vec<u8>,
+    /// What OSC 52 clipboard requests may do
+    osc52_policy: Osc52Policy,
+    /// Text programs asked to copy with OSC 52, oldest first
+    clipboard_requests: Vec<String>,
+    /// Number of times the bell (BEL) rang
+    bell_count: u64,
+    /// Whether the kitty keyboard protocol is offered to applications
+    kitty_keyboard_enabled: bool,
+    /// Active kitty keyboard protocol flags
+    kitty_keyboard_flags: u16,
+    /// Flags saved by `CSI > u` (push), restored by `CSI < u` (pop)
+    kitty_keyboard_stack: Vec<u16>,
 }
 
 impl Screen {
@@ -46,10 +113,28 @@ impl Screen {
             grid: Grid::new(size, scrollback),
             alternate_grid: Grid::new(size, 0), // No scrollback for alternate
             attrs: Attrs::default(),
+            hyperlink: None,
             modes: MODE_CURSOR_VISIBLE | MODE_AUTO_WRAP,
+            cursor_shape: CursorShape::default(),
             title: String::new(),
             icon_name: String::new(),
+            current_dir: None,
+            command_open: false,
+            last_exit_code: None,
             pending_wrap: false,
+            charsets: [Charset::Ascii; 2],
+            shift_out: false,
+            tab_stops: default_tab_stops(cols),
+            mouse_protocol_mode: MouseProtocolMode::default(),
+            mouse_protocol_encoding: MouseProtocolEncoding::default(),
+            palette: Palette::default(),
+            replies: Vec::new(),
+            osc52_policy: Osc52Policy::default(),
+            clipboard_requests: Vec::new(),
+            bell_count: 0,
+            kitty_keyboard_enabled: false,
+            kitty_keyboard_flags: 0,
+            kitty_keyboard_stack: Vec::new(),
         }
     }
 
@@ -91,6 +176,114 @@ impl Screen {
         self.mode(MODE_CURSOR_VISIBLE)
     }
 
+    /// Check if the cursor should blink
+    pub fn cursor_blinking(&self) -> bool {
+        self.mode(MODE_CURSOR_BLINK)
+    }
+
+    /// Get the cursor shape
+    pub fn cursor_shape(&self) -> CursorShape {
+        self.cursor_shape
+    }
+
+    /// Check if bracketed paste (`?2004`) is enabled
+    pub fn bracketed_paste(&self) -> bool {
+        self.mode(MODE_BRACKETED_PASTE)
+    }
+
+    /// Check if auto-wrap (DECAWM) is enabled
+    pub fn auto_wrap(&self) -> bool {
+        self.mode(MODE_AUTO_WRAP)
+    }
+
+    /// Check if origin mode (DECOM) is enabled
+    pub fn origin_mode(&self) -> bool {
+        self.mode(MODE_ORIGIN)
+    }
+
+    /// Get the keyboard modes that affect how keys are encoded
+    pub fn keyboard_modes(&self) -> KeyboardModes {
+        KeyboardModes {
+            application_cursor: self.mode(MODE_APPLICATION_CURSOR),
+            application_keypad: self.mode(MODE_APPLICATION_KEYPAD),
+            kitty_flags: self.kitty_keyboard_flags,
+        }
+    }
+
+    /// Check if the kitty keyboard protocol is offered to applications
+    pub fn kitty_keyboard_enabled(&self) -> bool {
+        self.kitty_keyboard_enabled
+    }
+
+    /// Offer the kitty keyboard protocol to applications (off by default)
+    ///
+    /// When disabled, `CSI ? u` queries go unanswered and push/set requests
+    /// are ignored, so applications fall back to legacy key encoding.
+    pub fn set_kitty_keyboard_enabled(&mut self, enabled: bool) {
+        self.kitty_keyboard_enabled = enabled;
+        if !enabled {
+            self.kitty_keyboard_flags = 0;
+            self.kitty_keyboard_stack.clear();
+        }
+    }
+
+    /// Get the mouse tracking mode requested by the application
+    pub fn mouse_protocol_mode(&self) -> MouseProtocolMode {
+        self.mouse_protocol_mode
+    }
+
+    /// Get the mouse report encoding requested by the application
+    pub fn mouse_protocol_encoding(&self) -> MouseProtocolEncoding {
+        self.mouse_protocol_encoding
+    }
+
+    /// Get the palette reported to color queries
+    pub fn palette(&self) -> &Palette {
+        &self.palette
+    }
+
+    /// Set the palette reported to color queries
+    pub fn set_palette(&mut self, palette: Palette) {
+        self.palette = palette;
+    }
+
+    /// Get the policy for OSC 52 clipboard requests
+    pub fn osc52_policy(&self) -> Osc52Policy {
+        self.osc52_policy
+    }
+
+    /// Set the policy for OSC 52 clipboard requests
+    pub fn set_osc52_policy(&mut self, policy: Osc52Policy) {
+        self.osc52_policy = policy;
+    }
+
+    /// Take the text programs asked to copy since the last call
+    pub fn take_clipboard_requests(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.clipboard_requests)
+    }
+
+    /// Get the number of times the bell rang
+    pub fn bell_count(&self) -> u64 {
+        self.bell_count
+    }
+
+    /// Take the reply bytes produced by terminal queries (DA, DSR, OSC colors)
+    ///
+    /// The caller is responsible for writing them to the application's input.
+    pub fn take_replies(&mut self) -> Vec<u8> {
+        std::mem::take(&mut self.replies)
+    }
+
+    /// Check if replies are waiting to be written
+    pub fn has_replies(&self) -> bool {
+        !self.replies.is_empty()
+    }
+
+    /// Queue a reply for the application
+    fn reply(&mut self, bytes: impl AsRef<[u8]>) {
+        self.replies.extend_from_slice(bytes.as_ref());
+    }
+
     /// Get window title
     pub fn title(&self) -> &str {
         &self.title
@@ -106,6 +299,11 @@ impl Screen {
         self.grid_mut().set_scrollback(offset);
     }
 
+    /// Get available scrollback lines of the active grid
+    pub fn scrollback_available(&self) -> usize {
+        self.grid().scrollback_available()
+    }
+
     /// Scroll screen up (for user interaction)
     pub fn scroll_screen_up(&mut self, n: usize) {
         let current = self.grid().scrollback();
@@ -123,27 +321,50 @@ impl Screen {
         self.grid().get_selected_text(low_x, low_y, high_x, high_y)
     }
 
+    /// Get the text of a rectangle of cells (block selection)
+    pub fn get_block_text(&self, left: i32, top: i32, right: i32, bottom: i32) -> String {
+        self.grid().get_block_text(left, top, right, bottom)
+    }
+
+    /// Get a row by its coordinate (scrollback rows are negative)
+    pub fn row_at(&self, y: i32) -> Option<&crate::termtui::row::Row> {
+        self.grid().row_at(y)
+    }
+
     /// Check a mode flag
-    fn mode(&self, mode: u8) -> bool {
+    fn mode(&self, mode: u16) -> bool {
         self.modes & mode != 0
     }
 
     /// Set a mode flag
-    #[allow(dead_code)]
-    fn set_mode(&mut self, mode: u8) {
+    fn set_mode(&mut self, mode: u16) {
         self.modes |= mode;
     }
 
     /// Clear a mode flag
-    #[allow(dead_code)]
-    fn clear_mode(&mut self, mode: u8) {
+    fn clear_mode(&mut self, mode: u16) {
         self.modes &= !mode;
     }
 
     /// Resize the screen
     pub fn resize(&mut self, rows: usize, cols: usize) {
         let size = Size::new(cols as u16, rows as u16);
-        self.grid.resize(size);
+        if size == self.size() {
+            return;
+        }
+
+        // New columns get the default tab stops
+        let old_cols = self.tab_stops.len();
+        self.tab_stops.truncate(cols);
+        self.tab_stops
+            .extend((old_cols..cols).map(|col| col % TAB_WIDTH == 0));
+
+        // Only the primary screen reflows; full-screen apps redraw anyway
+        if self.mode(MODE_ALTERNATE_SCREEN) {
+            self.grid.resize_reflow(size, false);
+        } else {
+            self.pending_wrap = self.grid.resize_reflow(size, self.pending_wrap);
+        }
         self.alternate_grid.resize(size);
     }
 
@@ -169,10 +390,16 @@ impl Screen {
 
     /// Handle a printable character
     fn text(&mut self, c: char) {
+        let c = self.charsets[self.shift_out as usize].map(c);
+
+        // Combining marks, ZWJ sequences, flags and variation selectors
+        // extend the grapheme cluster in the previous cell
+        if self.extend_previous_cluster(c) {
+            return;
+        }
+
         let char_width = c.width().unwrap_or(0);
         if char_width == 0 {
-            // Combining character - append to previous cell
-            // (simplified: skip for now)
             return;
         }
 
@@ -188,23 +415,29 @@ impl Screen {
             }
 
             // Move to next line
-            let pos = self.grid().pos();
-            if pos.row + 1 >= size.rows {
-                self.grid_mut().scroll_up(1);
-            } else {
-                self.grid_mut().set_row(pos.row + 1);
-            }
+            self.index();
             self.grid_mut().set_col(0);
         }
 
         let pos = self.grid().pos();
         let attrs = self.attrs; // Copy attrs to avoid borrow conflict
+        let hyperlink = self.hyperlink.clone();
+
+        // Insert mode (IRM) shifts the rest of the line right
+        if self.mode(MODE_INSERT) {
+            if let Some(row) = self.grid_mut().drawing_row_mut(pos.row) {
+                for _ in 0..char_width {
+                    row.insert(pos.col, Cell::new());
+                }
+            }
+        }
 
         // Write character to current cell
         if let Some(row) = self.grid_mut().drawing_row_mut(pos.row) {
             if let Some(cell) = row.get_mut(pos.col) {
-                cell.set_text(c.to_string());
+                cell.set_char(c);
                 cell.set_attrs(attrs);
+                cell.set_hyperlink(hyperlink.clone());
             }
 
             // Handle wide characters
@@ -212,6 +445,7 @@ impl Screen {
                 if let Some(next_cell) = row.get_mut(pos.col + 1) {
                     next_cell.set_wide_continuation();
                     next_cell.set_attrs(attrs);
+                    next_cell.set_hyperlink(hyperlink);
                 }
             }
         }
@@ -228,10 +462,82 @@ impl Screen {
         }
     }
 
+    /// Append `c` to the cell before the cursor if it continues its cluster
+    ///
+    /// Returns false if `c` starts a new grapheme cluster.
+    fn extend_previous_cluster(&mut self, c: char) -> bool {
+        let size = self.grid().size();
+        let pos = self.grid().pos();
+        let pending_wrap = self.pending_wrap;
+
+        // With a wrap pending the cursor is still on the last printed cell
+        let mut col = if pending_wrap {
+            pos.col
+        } else if pos.col > 0 {
+            pos.col - 1
+        } else {
+            return false;
+        };
+
+        let Some(row) = self.grid().drawing_row(pos.row) else {
+            return false;
+        };
+        if col > 0 && row.is_wide_continuation(col) {
+            col -= 1;
+        }
+        let Some(cell) = row.get(col) else {
+            return false;
+        };
+        // ASCII never joins ASCII; skip the segmentation on the hot path
+        if c.is_ascii() && cell.text().is_ascii() {
+            return false;
+        }
+
+        let mut cluster = cell.clone();
+        let mut text = cell.text().to_string();
+        text.push(c);
+        if text.graphemes(true).count() != 1 {
+            return false;
+        }
+        let old_width = cell.width();
+        cluster.set_text(text);
+        let new_width = cluster.width();
+
+        let Some(row) = self.grid_mut().drawing_row_mut(pos.row) else {
+            return false;
+        };
+        // An emoji presentation selector can widen the cluster to two columns
+        let widened = new_width > old_width && !pending_wrap;
+        if widened && col + 1 < size.cols {
+            let mut continuation = cluster.clone();
+            continuation.set_wide_continuation();
+            if let Some(next) = row.get_mut(col + 1) {
+                *next = continuation;
+            }
+        }
+        if let Some(cell) = row.get_mut(col) {
+            *cell = cluster;
+        }
+
+        if widened {
+            let new_col = col + new_width as u16;
+            if new_col >= size.cols {
+                if self.mode(MODE_AUTO_WRAP) {
+                    self.pending_wrap = true;
+                    self.grid_mut().set_col(size.cols - 1);
+                }
+            } else {
+                self.grid_mut().set_col(new_col);
+            }
+        }
+
+        true
+    }
+
     /// Handle control codes
     fn handle_control(&mut self, code: ControlCode) {
         match code {
-            ControlCode::Bell => {} // Could trigger bell callback
+            ControlCode::Bell => self.bell_count += 1,
             ControlCode::Backspace => {
                 let pos = self.grid().pos();
                 if pos.col > 0 {
@@ -240,20 +546,14 @@ impl Screen {
                 self.pending_wrap = false;
             }
             ControlCode::HorizontalTab => {
-                let pos = self.grid().pos();
-                let next_tab = ((pos.col / 8) + 1) * 8;
-                let size = self.grid().size();
-                self.grid_mut().set_col(next_tab.min(size.cols - 1));
+                let col = self.next_tab_stop(self.grid().pos().col);
+                self.grid_mut().set_col(col);
                 self.pending_wrap = false;
             }
+            ControlCode::ShiftOut => self.shift_out = true,
+            ControlCode::ShiftIn => self.shift_out = false,
             ControlCode::LineFeed | ControlCode::VerticalTab | ControlCode::FormFeed => {
-                let pos = self.grid().pos();
-                let size = self.grid().size();
-                if pos.row + 1 >= size.rows {
-                    self.grid_mut().scroll_up(1);
-                } else {
-                    self.grid_mut().set_row(pos.row + 1);
-                }
+                self.index();
                 self.pending_wrap = false;
             }
             ControlCode::CarriageReturn => {
@@ -264,6 +564,75 @@ impl Screen {
         }
     }
 
+    /// Get the next tab stop right of `col`, or the last column
+    fn next_tab_stop(&self, col: u16) -> u16 {
+        let last = self.grid().size().cols.saturating_sub(1);
+        ((col + 1)..=last)
+            .find(|&c| self.tab_stops.get(c as usize) == Some(&true))
+            .unwrap_or(last)
+    }
+
+    /// Get the previous tab stop left of `col`, or the first column
+    fn prev_tab_stop(&self, col: u16) -> u16 {
+        (0..col)
+            .rev()
+            .find(|&c| self.tab_stops.get(c as usize) == Some(&true))
+            .unwrap_or(0)
+    }
+
+    /// Set or clear the tab stop at the cursor column
+    fn set_tab_stop(&mut self, on: bool) {
+        let col = self.grid().pos().col as usize;
+        if let Some(stop) = self.tab_stops.get_mut(col) {
+            *stop = on;
+        }
+    }
+
+    /// Fill the screen with `E` for alignment checks (DECALN)
+    fn screen_alignment(&mut self) {
+        let size = self.grid().size();
+        self.grid_mut().reset_scroll_region();
+        for r in 0..size.rows {
+            if let Some(row) = self.grid_mut().drawing_row_mut(r) {
+                row.clear();
+                for col in 0..size.cols {
+                    if let Some(cell) = row.get_mut(col) {
+                        cell.set_text("E");
+                    }
+                }
+            }
+        }
+        self.grid_mut().set_pos(Pos::new(0, 0));
+        self.pending_wrap = false;
+    }
+
+    /// Move the cursor down one line, scrolling the scroll region when the
+    /// cursor sits on its bottom margin
+    fn index(&mut self) {
+        let pos = self.grid().pos();
+        let size = self.grid().size();
+        let (_, bottom) = self.grid().scroll_region();
+
+        if pos.row + 1 == bottom {
+            self.grid_mut().scroll_up(1);
+        } else if pos.row + 1 < size.rows {
+            self.grid_mut().set_row(pos.row + 1);
+        }
+    }
+
+    /// Move the cursor up one line, scrolling the scroll region when the
+    /// cursor sits on its top margin
+    fn reverse_index(&mut self) {
+        let pos = self.grid().pos();
+        let (top, _) = self.grid().scroll_region();
+
+        if pos.row == top {
+            self.grid_mut().scroll_down(1);
+        } else if pos.row > 0 {
+            self.grid_mut().set_row(pos.row - 1);
+        }
+    }
+
     /// Handle escape sequences
     fn handle_esc(&mut self, esc: Esc) {
         match esc {
@@ -274,40 +643,49 @@ impl Screen {
                 self.grid_mut().restore_pos();
             }
             Esc::Code(EscCode::ReverseIndex) => {
-                // Move cursor up, scrolling if needed
-                let pos = self.grid().pos();
-                if pos.row == 0 {
-                    self.grid_mut().scroll_down(1);
-                } else {
-                    self.grid_mut().set_row(pos.row - 1);
-                }
+                self.reverse_index();
+                self.pending_wrap = false;
             }
             Esc::Code(EscCode::Index) => {
-                // Move cursor down, scrolling if needed
-                let pos = self.grid().pos();
-                let size = self.grid().size();
-                if pos.row + 1 >= size.rows {
-                    self.grid_mut().scroll_up(1);
-                } else {
-                    self.grid_mut().set_row(pos.row + 1);
-                }
+                self.index();
+                self.pending_wrap = false;
             }
             Esc::Code(EscCode::NextLine) => {
                 // Like Index but also carriage return
-                let pos = self.grid().pos();
-                let size = self.grid().size();
-                if pos.row + 1 >= size.rows {
-                    self.grid_mut().scroll_up(1);
-                } else {
-                    self.grid_mut().set_row(pos.row + 1);
-                }
+                self.index();
                 self.grid_mut().set_col(0);
+                self.pending_wrap = false;
             }
+            Esc::Code(EscCode::DecApplicationKeyPad) => {
+                self.set_mode(MODE_APPLICATION_KEYPAD);
+            }
+            Esc::Code(EscCode::DecNormalKeyPad) => {
+                self.clear_mode(MODE_APPLICATION_KEYPAD);
+            }
+            Esc::Code(EscCode::AsciiCharacterSetG0) => self.charsets[0] = Charset::Ascii,
+            Esc::Code(EscCode::UkCharacterSetG0) => self.charsets[0] = Charset::Uk,
+            Esc::Code(EscCode::DecLineDrawingG0) => self.charsets[0] = Charset::DecLineDrawing,
+            Esc::Code(EscCode::AsciiCharacterSetG1) => self.charsets[1] = Charset::Ascii,
+            Esc::Code(EscCode::UkCharacterSetG1) => self.charsets[1] = Charset::Uk,
+            Esc::Code(EscCode::DecLineDrawingG1) => self.charsets[1] = Charset::DecLineDrawing,
+            Esc::Code(EscCode::HorizontalTabSet) => self.set_tab_stop(true),
+            Esc::Code(EscCode::DecScreenAlignmentDisplay) => self.screen_alignment(),
             Esc::Code(EscCode::FullReset) => {
+                self.exit_alternate_screen(true);
+                self.grid_mut().reset_scroll_region();
                 self.grid_mut().clear();
                 self.grid_mut().set_pos(Pos::new(0, 0));
                 self.attrs = Attrs::default();
+                self.hyperlink = None;
                 self.modes = MODE_CURSOR_VISIBLE | MODE_AUTO_WRAP;
+                self.cursor_shape = CursorShape::default();
+                self.mouse_protocol_mode = MouseProtocolMode::default();
+                self.mouse_protocol_encoding = MouseProtocolEncoding::default();
+                self.kitty_keyboard_flags = 0;
+                self.kitty_keyboard_stack.clear();
+                self.charsets = [Charset::Ascii; 2];
+                self.shift_out = false;
+                self.tab_stops = default_tab_stops(self.tab_stops.len());
             }
             _ => {}
         }
@@ -322,31 +700,116 @@ impl Screen {
             CSI::Edit(edit) => self.handle_edit(edit),
             CSI::Sgr(sgr) => self.handle_sgr(sgr),
             CSI::Mode(mode) => self.handle_mode(mode),
-            CSI::Window(_) => {}   // Window manipulation not implemented
-            CSI::Keyboard(_) => {} // Keyboard modes not implemented
-            CSI::Mouse(_) => {}    // Mouse reporting not implemented
-            CSI::Device(_) => {}   // Device queries not implemented
+            CSI::Window(_) => {} // Window manipulation not implemented
+            CSI::Keyboard(keyboard) => self.handle_keyboard(keyboard),
+            CSI::Mouse(_) => {} // Mouse reporting not implemented
+            CSI::Device(device) => self.handle_device(*device),
+            _ => {}
+        }
+    }
+
+    /// Handle kitty keyboard protocol requests
+    fn handle_keyboard(&mut self, keyboard: Keyboard) {
+        if !self.kitty_keyboard_enabled {
+            return;
+        }
+
+        match keyboard {
+            Keyboard::SetKittyState { flags, mode } => {
+                let flags = flags.bits();
+                self.kitty_keyboard_flags = match mode {
+                    KittyKeyboardMode::AssignAll => flags,
+                    KittyKeyboardMode::SetSpecified => self.kitty_keyboard_flags | flags,
+                    KittyKeyboardMode::ClearSpecified => self.kitty_keyboard_flags & !flags,
+                };
+            }
+            Keyboard::PushKittyState { flags, .. } => {
+                if self.kitty_keyboard_stack.len() >= KITTY_KEYBOARD_STACK_LIMIT {
+                    self.kitty_keyboard_stack.remove(0);
+                }
+                self.kitty_keyboard_stack.push(self.kitty_keyboard_flags);
+                self.kitty_keyboard_flags = flags.bits();
+            }
+            Keyboard::PopKittyState(n) => {
+                for _ in 0..n.max(1) {
+                    match self.kitty_keyboard_stack.pop() {
+                        Some(flags) => self.kitty_keyboard_flags = flags,
+                        None => {
+                            self.kitty_keyboard_flags = 0;
+                            break;
+                        }
+                    }
+                }
+            }
+            Keyboard::QueryKittySupport => {
+                let report = format!("\x1b[?{}u", self.kitty_keyboard_flags);
+                self.reply(report);
+            }
+            Keyboard::ReportKittyState(_) => {}
+        }
+    }
+
+    /// Handle device queries
+    fn handle_device(&mut self, device: Device) {
+        match device {
+            Device::RequestPrimaryDeviceAttributes => {
+                // VT220 with ANSI color
+                self.reply(b"\x1b[?62;22c");
+            }
+            Device::RequestSecondaryDeviceAttributes => {
+                self.reply(b"\x1b[>1;10;0c");
+            }
+            Device::StatusReport => {
+                // Terminal OK
+                self.reply(b"\x1b[0n");
+            }
+            Device::RequestTerminalNameAndVersion => {
+                let version = format!(
+                    "\x1bP>|{}({})\x1b\\",
+                    env!("CARGO_PKG_NAME"),
+                    env!("CARGO_PKG_VERSION")
+                );
+                self.reply(version);
+            }
             _ => {}
         }
     }
 
     /// Handle cursor movement
     fn handle_cursor(&mut self, cursor: Cursor) {
+        // DECSCUSR only changes how the cursor looks
+        if let Cursor::CursorStyle(style) = cursor {
+            let (shape, blinking) = CursorShape::from_style(style);
+            self.cursor_shape = shape;
+            self.toggle_mode(MODE_CURSOR_BLINK, blinking);
+            return;
+        }
+
         let size = self.grid().size();
         let pos = self.grid().pos();
 
         match cursor {
-            Cursor::Position { line, col } => {
-                let row = line.as_zero_based().min(size.rows.saturating_sub(1) as u32) as u16;
+            Cursor::Position { line, col } | Cursor::CharacterAndLinePosition { line, col } => {
+                let row = self.origin_row(line.as_zero_based());
                 let col = col.as_zero_based().min(size.cols.saturating_sub(1) as u32) as u16;
                 self.grid_mut().set_pos(Pos::new(col, row));
             }
+            Cursor::LinePositionAbsolute(line) => {
+                let row = self.origin_row(line.saturating_sub(1));
+                self.grid_mut().set_row(row);
+            }
             Cursor::Up(n) => {
-                let new_row = pos.row.saturating_sub(n as u16);
+                // Stop at the top margin when starting inside the scroll region
+                let (top, _) = self.grid().scroll_region();
+                let min_row = if pos.row >= top { top } else { 0 };
+                let new_row = pos.row.saturating_sub(n as u16).max(min_row);
                 self.grid_mut().set_row(new_row);
             }
             Cursor::Down(n) => {
-                let new_row = (pos.row + n as u16).min(size.rows - 1);
+                // Stop at the bottom margin when starting inside the scroll region
+                let (_, bottom) = self.grid().scroll_region();
+                let max_row = if pos.row < bottom { bottom } else { size.rows } - 1;
+                let new_row = pos.row.saturating_add(n as u16).min(max_row);
                 self.grid_mut().set_row(new_row);
             }
             Cursor::Left(n) => {
@@ -369,17 +832,74 @@ impl Screen {
                 let new_row = pos.row.saturating_sub(n as u16);
                 self.grid_mut().set_pos(Pos::new(0, new_row));
             }
+            Cursor::SetTopAndBottomMargins { top, bottom } => {
+                let top = top.as_zero_based().min(size.rows as u32) as u16;
+                let bottom = bottom.as_one_based().min(size.rows as u32) as u16;
+                // The region must span at least two lines
+                if top + 1 < bottom {
+                    self.grid_mut().set_scroll_region(top, bottom);
+                    let row = self.origin_row(0);
+                    self.grid_mut().set_pos(Pos::new(0, row));
+                }
+            }
+            Cursor::RequestActivePositionReport => {
+                // Rows are reported relative to the scroll region in origin mode
+                let top = if self.mode(MODE_ORIGIN) {
+                    self.grid().scroll_region().0
+                } else {
+                    0
+                };
+                let report = format!("\x1b[{};{}R", pos.row.saturating_sub(top) + 1, pos.col + 1);
+                self.reply(report);
+            }
             Cursor::SaveCursor => {
                 self.grid_mut().save_pos();
             }
             Cursor::RestoreCursor => {
                 self.grid_mut().restore_pos();
             }
+            Cursor::ForwardTabulation(n) => {
+                let col = (0..n.max(1)).fold(pos.col, |col, _| self.next_tab_stop(col));
+                self.grid_mut().set_col(col);
+            }
+            Cursor::BackwardTabulation(n) => {
+                let col = (0..n.max(1)).fold(pos.col, |col, _| self.prev_tab_stop(col));
+                self.grid_mut().set_col(col);
+            }
+            Cursor::TabulationClear(clear) => match clear {
+                TabulationClear::ClearCharacterTabStopAtActivePosition => self.set_tab_stop(false),
+                TabulationClear::ClearAllCharacterTabStops | TabulationClear::ClearAllTabStops => {
+                    self.tab_stops.fill(false)
+                }
+                _ => {}
+            },
+            Cursor::TabulationControl(control) => match control {
+                CursorTabulationControl::SetCharacterTabStopAtActivePosition => {
+                    self.set_tab_stop(true)
+                }
+                CursorTabulationControl::ClearCharacterTabStopAtActivePosition => {
+                    self.set_tab_stop(false)
+                }
+                CursorTabulationControl::ClearAllCharacterTabStops => self.tab_stops.fill(false),
+                _ => {}
+            },
             _ => {}
         }
         self.pending_wrap = false;
     }
 
+    /// Translate a zero-based row from an escape sequence into a grid row,
+    /// honoring origin mode (DECOM)
+    fn origin_row(&self, row: u32) -> u16 {
+        let size = self.grid().size();
+        let (top, bottom) = if self.mode(MODE_ORIGIN) {
+            self.grid().scroll_region()
+        } else {
+            (0, size.rows)
+        };
+        (top as u32 + row).min(bottom.saturating_sub(1) as u32) as u16
+    }
+
     /// Handle edit operations
     fn handle_edit(&mut self, edit: Edit) {
         let size = self.grid().size();
@@ -418,7 +938,7 @@ impl Screen {
             Edit::InsertCharacter(n) => {
                 if let Some(row) = self.grid_mut().drawing_row_mut(pos.row) {
                     for _ in 0..n {
-                        row.insert(pos.col, Default::default());
+                        row.insert(pos.col, Cell::new());
                     }
                 }
             }
@@ -435,14 +955,14 @@ impl Screen {
                 }
             }
             Edit::InsertLine(n) => {
-                for _ in 0..n {
-                    self.grid_mut().scroll_down(1);
-                }
+                self.grid_mut().insert_lines(pos.row, n as usize);
+                self.grid_mut().set_col(0);
+                self.pending_wrap = false;
             }
             Edit::DeleteLine(n) => {
-                for _ in 0..n {
-                    self.grid_mut().scroll_up(1);
-                }
+                self.grid_mut().delete_lines(pos.row, n as usize);
+                self.grid_mut().set_col(0);
+                self.pending_wrap = false;
             }
             Edit::ScrollDown(n) => {
                 self.grid_mut().scroll_down(n as usize);
@@ -460,30 +980,37 @@ impl Screen {
             Sgr::Reset => {
                 self.attrs.reset();
             }
-            Sgr::Intensity(intensity) => match intensity {
-                termwiz::cell::Intensity::Bold => {
-                    self.attrs.set_bold(true);
-                }
-                termwiz::cell::Intensity::Normal => {
-                    self.attrs.set_bold(false);
-                }
-                termwiz::cell::Intensity::Half => {
-                    self.attrs.set_bold(false);
-                }
-            },
+            Sgr::Intensity(intensity) => {
+                use termwiz::cell::Intensity;
+                self.attrs.set_bold(intensity == Intensity::Bold);
+                self.attrs.set_dim(intensity == Intensity::Half);
+            }
             Sgr::Italic(on) => {
                 self.attrs.set_italic(on);
             }
             Sgr::Underline(underline) => {
-                self.attrs
-                    .set_underline(underline != termwiz::cell::Underline::None);
+                self.attrs.set_underline_style(underline.into());
+            }
+            Sgr::UnderlineColor(color) => {
+                self.attrs.underline_color = Color::from(color);
+            }
+            Sgr::Blink(blink) => {
+                use termwiz::cell::Blink;
+                self.attrs.set_blink(blink == Blink::Slow);
+                self.attrs.set_rapid_blink(blink == Blink::Rapid);
             }
             Sgr::Inverse(on) => {
                 self.attrs.set_inverse(on);
             }
+            Sgr::Invisible(on) => {
+                self.attrs.set_hidden(on);
+            }
             Sgr::StrikeThrough(on) => {
                 self.attrs.set_strikethrough(on);
             }
+            Sgr::Overline(on) => {
+                self.attrs.set_overline(on);
+            }
             Sgr::Foreground(color) => {
                 self.attrs.fg = Color::from(color);
             }
@@ -496,54 +1023,373 @@ impl Screen {
 
     /// Handle mode changes
     fn handle_mode(&mut self, mode: Mode) {
-        // Standard ANSI modes - not commonly used
-        let _ = mode;
+        match mode {
+            Mode::SetDecPrivateMode(DecPrivateMode::Code(code)) => {
+                self.set_dec_private_mode(code, true)
+            }
+            Mode::ResetDecPrivateMode(DecPrivateMode::Code(code)) => {
+                self.set_dec_private_mode(code, false)
+            }
+            Mode::SetMode(TerminalMode::Code(TerminalModeCode::Insert)) => {
+                self.set_mode(MODE_INSERT)
+            }
+            Mode::ResetMode(TerminalMode::Code(TerminalModeCode::Insert)) => {
+                self.clear_mode(MODE_INSERT)
+            }
+            // X10 mouse reporting (`CSI ? 9 h`) has no named termwiz code
+            Mode::SetDecPrivateMode(DecPrivateMode::Unspecified(9)) => {
+                self.set_mouse_mode(MouseProtocolMode::Press, true)
+            }
+            Mode::ResetDecPrivateMode(DecPrivateMode::Unspecified(9)) => {
+                self.set_mouse_mode(MouseProtocolMode::Press, false)
+            }
+            _ => {}
+        }
     }
 
-    /// Handle OSC (Operating System Command)
-    fn handle_osc(&mut self, osc: OperatingSystemCommand) {
-        match osc {
-            OperatingSystemCommand::SetWindowTitle(title)
-            | OperatingSystemCommand::SetWindowTitleSun(title) => {
-                self.title = title;
+    /// Apply a DEC private mode (`CSI ? n h` / `CSI ? n l`)
+    fn set_dec_private_mode(&mut self, code: DecPrivateModeCode, on: bool) {
+        match code {
+            DecPrivateModeCode::ApplicationCursorKeys => {
+                self.toggle_mode(MODE_APPLICATION_CURSOR, on)
             }
-            OperatingSystemCommand::SetIconName(name)
-            | OperatingSystemCommand::SetIconNameSun(name) => {
-                self.icon_name = name;
+            DecPrivateModeCode::ShowCursor => self.toggle_mode(MODE_CURSOR_VISIBLE, on),
+            DecPrivateModeCode::AutoWrap => {
+                self.toggle_mode(MODE_AUTO_WRAP, on);
+                self.pending_wrap = false;
             }
-            OperatingSystemCommand::SetIconNameAndWindowTitle(title) => {
-                self.title = title.clone();
-                self.icon_name = title;
+            DecPrivateModeCode::StartBlinkingCursor => self.toggle_mode(MODE_CURSOR_BLINK, on),
+            DecPrivateModeCode::OriginMode => {
+                self.toggle_mode(MODE_ORIGIN, on);
+                // DECOM moves the cursor to the (new) home position
+                let row = self.origin_row(0);
+                self.grid_mut().set_pos(Pos::new(0, row));
+                self.pending_wrap = false;
+            }
+            DecPrivateModeCode::EnableAlternateScreen => {
+                if on {
+                    self.enter_alternate_screen(false);
+                } else {
+                    self.exit_alternate_screen(false);
+                }
+            }
+            DecPrivateModeCode::OptEnableAlternateScreen => {
+                if on {
+                    self.enter_alternate_screen(false);
+                } else {
+                    self.exit_alternate_screen(true);
+                }
+            }
+            DecPrivateModeCode::ClearAndEnableAlternateScreen => {
+                if on {
+                    self.grid.save_pos();
+                    self.enter_alternate_screen(true);
+                } else {
+                    self.exit_alternate_screen(false);
+                    self.grid.restore_pos();
+                }
+            }
+            DecPrivateModeCode::MouseTracking => {
+                self.set_mouse_mode(MouseProtocolMode::PressRelease, on)
+            }
+            DecPrivateModeCode::ButtonEventMouse => {
+                self.set_mouse_mode(MouseProtocolMode::ButtonMotion, on)
+            }
+            DecPrivateModeCode::AnyEventMouse => {
+                self.set_mouse_mode(MouseProtocolMode::AnyMotion, on)
+            }
+            DecPrivateModeCode::Utf8Mouse => {
+                self.set_mouse_encoding(MouseProtocolEncoding::Utf8, on)
+            }
+            DecPrivateModeCode::SGRMouse => self.set_mouse_encoding(MouseProtocolEncoding::Sgr, on),
+            DecPrivateModeCode::BracketedPaste => self.toggle_mode(MODE_BRACKETED_PASTE, on),
+            DecPrivateModeCode::SaveCursor => {
+                if on {
+                    self.grid_mut().save_pos();
+                } else {
+                    self.grid_mut().restore_pos();
+                }
             }
             _ => {}
         }
     }
 
-    /// Get visible rows iterator (for rendering)
-    pub fn visible_rows(&self) -> impl Iterator<Item = &crate::termtui::row::Row> {
-        self.grid().visible_rows()
+    /// Enable a mouse tracking mode, or disable it if it is the active one
+    fn set_mouse_mode(&mut self, mode: MouseProtocolMode, on: bool) {
+        if on {
+            self.mouse_protocol_mode = mode;
+        } else if self.mouse_protocol_mode == mode {
+            self.mouse_protocol_mode = MouseProtocolMode::None;
+        }
     }
 
-    /// Check if in alternate screen mode
-    pub fn is_alternate_screen(&self) -> bool {
-        self.mode(MODE_ALTERNATE_SCREEN)
+    /// Enable a mouse report encoding, or disable it if it is the active one
+    fn set_mouse_encoding(&mut self, encoding: MouseProtocolEncoding, on: bool) {
+        if on {
+            self.mouse_protocol_encoding = encoding;
+        } else if self.mouse_protocol_encoding == encoding {
+            self.mouse_protocol_encoding = MouseProtocolEncoding::Default;
+        }
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
 
-    #[test]
-    fn test_screen_new() {
-        let screen = Screen::new(24, 80, 1000);
-        assert_eq!(screen.size().rows, 24);
-        assert_eq!(screen.size().cols, 80);
+    /// Set or clear a mode flag
+    fn toggle_mode(&mut self, mode: u16, on: bool) {
+        if on {
+            self.set_mode(mode);
+        } else {
+            self.clear_mode(mode);
+        }
     }
 
-    #[test]
-    fn test_screen_text() {
-        let mut screen = Screen::new(24, 80, 1000);
+    /// Switch to the alternate grid, carrying over the cursor position
+    fn enter_alternate_screen(&mut self, clear: bool) {
+        if self.mode(MODE_ALTERNATE_SCREEN) {
+            return;
+        }
+
+        let pos = self.grid.pos();
+        if clear {
+            self.alternate_grid.clear();
+        }
+        self.alternate_grid.set_pos(pos);
+        self.set_mode(MODE_ALTERNATE_SCREEN);
+        self.pending_wrap = false;
+    }
+
+    /// Switch back to the primary grid, carrying over the cursor position
+    fn exit_alternate_screen(&mut self, clear: bool) {
+        if !self.mode(MODE_ALTERNATE_SCREEN) {
+            return;
+        }
+
+        let pos = self.alternate_grid.pos();
+        if clear {
+            self.alternate_grid.clear();
+        }
+        self.grid.set_pos(pos);
+        self.clear_mode(MODE_ALTERNATE_SCREEN);
+        self.pending_wrap = false;
+    }
+
+    /// Handle OSC 133 semantic prompt marks
+    fn handle_semantic_prompt(&mut self, prompt: FinalTermSemanticPrompt) {
+        match prompt {
+            FinalTermSemanticPrompt::FreshLine => self.fresh_line(),
+            FinalTermSemanticPrompt::FreshLineAndStartPrompt { .. } => {
+                self.fresh_line();
+                self.mark_row(|marks| marks.prompt_start = true);
+                if !self.is_alternate_screen() {
+                    self.command_open = true;
+                }
+            }
+            FinalTermSemanticPrompt::MarkEndOfCommandWithFreshLine { .. } => {
+                self.mark_row(|marks| marks.command_end = true);
+                self.finish_command(None);
+                self.fresh_line();
+            }
+            FinalTermSemanticPrompt::MarkEndOfPromptAndStartOfInputUntilNextMarker
+            | FinalTermSemanticPrompt::MarkEndOfPromptAndStartOfInputUntilEndOfLine => {
+                self.mark_row(|marks| marks.input_start = true);
+            }
+            FinalTermSemanticPrompt::MarkEndOfInputAndStartOfOutput { .. } => {
+                self.mark_row(|marks| marks.output_start = true);
+            }
+            FinalTermSemanticPrompt::CommandStatus { status, .. } => {
+                self.mark_row(|marks| {
+                    marks.command_end = true;
+                    marks.exit_code = Some(status);
+                });
+                self.finish_command(Some(status));
+            }
+            // Continuation prompts don't start a new command
+            FinalTermSemanticPrompt::StartPrompt(_) => {}
+        }
+    }
+
+    /// Record the end of the running shell command on the primary screen
+    ///
+    /// Like `commands`, an end mark without a running command is ignored.
+    fn finish_command(&mut self, exit_code: Option<i32>) {
+        if self.command_open && !self.is_alternate_screen() {
+            self.command_open = false;
+            self.last_exit_code = exit_code;
+        }
+    }
+
+    /// Move to the start of the next line unless already at the left margin
+    fn fresh_line(&mut self) {
+        if self.grid().pos().col != 0 || self.pending_wrap {
+            self.pending_wrap = false;
+            self.index();
+            self.grid_mut().set_col(0);
+        }
+    }
+
+    /// Update the shell integration markers on the cursor row
+    fn mark_row(&mut self, update: impl FnOnce(&mut RowMarks)) {
+        if let Some(row) = self.grid_mut().current_row_mut() {
+            update(row.marks_mut());
+        }
+    }
+
+    /// Handle OSC (Operating System Command)
+    fn handle_osc(&mut self, osc: OperatingSystemCommand) {
+        match osc {
+            OperatingSystemCommand::SetWindowTitle(title)
+            | OperatingSystemCommand::SetWindowTitleSun(title) => {
+                self.title = title;
+            }
+            OperatingSystemCommand::SetIconName(name)
+            | OperatingSystemCommand::SetIconNameSun(name) => {
+                self.icon_name = name;
+            }
+            OperatingSystemCommand::SetIconNameAndWindowTitle(title) => {
+                self.title = title.clone();
+                self.icon_name = title;
+            }
+            OperatingSystemCommand::FinalTermSemanticPrompt(prompt) => {
+                self.handle_semantic_prompt(prompt);
+            }
+            OperatingSystemCommand::CurrentWorkingDirectory(uri) => {
+                if let Some(dir) = parse_cwd_uri(&uri) {
+                    self.current_dir = Some(dir);
+                }
+            }
+            OperatingSystemCommand::SetSelection(_, text)
+                if self.osc52_policy == Osc52Policy::AllowWrite =>
+            {
+                self.clipboard_requests.push(text);
+            }
+            OperatingSystemCommand::SetHyperlink(link) => {
+                self.hyperlink = link.as_ref().map(|link| Arc::new(Hyperlink::from(link)));
+            }
+            OperatingSystemCommand::ChangeDynamicColors(first, colors) => {
+                // Each additional parameter applies to the next dynamic color
+                for (offset, color) in colors.iter().enumerate() {
+                    let number = first as u8 + offset as u8;
+                    let rgb = match number {
+                        n if n == DynamicColorNumber::TextForegroundColor as u8 => {
+                            self.palette.foreground
+                        }
+                        n if n == DynamicColorNumber::TextBackgroundColor as u8 => {
+                            self.palette.background
+                        }
+                        n if n == DynamicColorNumber::TextCursorColor as u8 => self.palette.cursor,
+                        _ => continue,
+                    };
+                    if *color == ColorOrQuery::Query {
+                        let reply =
+                            format!("\x1b]{};{}\x1b\\", number, Palette::x11_color_spec(rgb));
+                        self.reply(reply);
+                    }
+                }
+            }
+            OperatingSystemCommand::ChangeColorNumber(pairs) => {
+                for pair in pairs {
+                    if pair.color == ColorOrQuery::Query {
+                        let rgb = self.palette.colors[pair.palette_index as usize];
+                        let reply = format!(
+                            "\x1b]4;{};{}\x1b\\",
+                            pair.palette_index,
+                            Palette::x11_color_spec(rgb)
+                        );
+                        self.reply(reply);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Get the text currently on screen (ignoring scrollback offset)
+    pub fn contents(&self) -> String {
+        self.grid().contents()
+    }
+
+    /// Export the primary scrollback and screen (see `export_grid`)
+    ///
+    /// The primary grid is used even while the alternate screen is shown,
+    /// since it holds the history.
+    pub fn export(&self, format: ExportFormat) -> String {
+        export_grid(&self.grid, format, &self.palette)
+    }
+
+    /// Get visible rows iterator (for rendering)
+    pub fn visible_rows(&self) -> impl Iterator<Item = &crate::termtui::row::Row> {
+        self.grid().visible_rows()
+    }
+
+    /// Get the link at a visible position
+    ///
+    /// OSC 8 hyperlinks take precedence over links detected in the text.
+    pub fn link_at(&self, row: u16, col: u16) -> Option<LinkTarget> {
+        let visible = self.grid().visible_row(row)?;
+        if let Some(link) = visible.get(col).and_then(|cell| cell.hyperlink()) {
+            return Some(LinkTarget::Hyperlink((**link).clone()));
+        }
+        detect_row_links(visible, row)
+            .into_iter()
+            .find(|link| link.contains(col))
+            .map(|link| link.target)
+    }
+
+    /// Detect URLs and `path:line[:col]` references in the visible rows
+    pub fn detect_links(&self) -> Vec<DetectedLink> {
+        self.visible_rows()
+            .enumerate()
+            .flat_map(|(row_idx, row)| detect_row_links(row, row_idx as u16))
+            .collect()
+    }
+
+    /// Find all matches in the active grid, scrollback included
+    ///
+    /// Positions use copy-mode coordinates (scrollback rows are negative).
+    pub fn search(&self, matcher: &Regex) -> Vec<SearchMatch> {
+        find_matches(self.grid().all_rows(), matcher)
+    }
+
+    /// Get the commands delimited by OSC 133 prompt marks
+    ///
+    /// Only the primary screen is considered; full-screen programs running on
+    /// the alternate screen don't emit prompts.
+    pub fn commands(&self) -> Vec<ShellCommand> {
+        collect_commands(self.grid.all_rows())
+    }
+
+    /// Get the exit code of the last finished shell command (OSC 133;D)
+    ///
+    /// Tracked as marks arrive, so it is cheap to call on every frame.
+    pub fn last_exit_code(&self) -> Option<i32> {
+        self.last_exit_code
+    }
+
+    /// Get the working directory last reported by the shell (OSC 7)
+    pub fn current_dir(&self) -> Option<&Path> {
+        self.current_dir.as_deref()
+    }
+
+    /// Check if in alternate screen mode
+    pub fn is_alternate_screen(&self) -> bool {
+        self.mode(MODE_ALTERNATE_SCREEN)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::termtui::attrs::UnderlineStyle;
+    use crate::termtui::parser::Parser;
+
+    #[test]
+    fn test_screen_new() {
+        let screen = Screen::new(24, 80, 1000);
+        assert_eq!(screen.size().rows, 24);
+        assert_eq!(screen.size().cols, 80);
+    }
+
+    #[test]
+    fn test_screen_text() {
+        let mut screen = Screen::new(24, 80, 1000);
 
         screen.text('H');
         screen.text('i');
@@ -575,4 +1421,734 @@ mod tests {
 
         assert!(screen.grid().scrollback_available() > 0);
     }
+
+    fn parse(bytes: &[u8]) -> Parser {
+        let mut parser = Parser::new(5, 20, 100);
+        parser.process(bytes);
+        parser
+    }
+
+    #[test]
+    fn test_alternate_screen_1049() {
+        let mut parser = parse(b"primary\x1b[2;4H");
+        let saved = parser.screen().cursor_pos();
+
+        parser.process(b"\x1b[?1049h");
+        assert!(parser.screen().is_alternate_screen());
+        assert_eq!(parser.screen().contents(), "");
+
+        parser.process(b"\x1b[Halternate");
+        assert_eq!(parser.screen().contents(), "alternate");
+
+        parser.process(b"\x1b[?1049l");
+        assert!(!parser.screen().is_alternate_screen());
+        assert_eq!(parser.screen().contents(), "primary");
+        assert_eq!(parser.screen().cursor_pos(), saved);
+    }
+
+    #[test]
+    fn test_alternate_screen_does_not_touch_scrollback() {
+        let mut parser = parse(b"\x1b[?1049h");
+        for _ in 0..20 {
+            parser.process(b"line\r\n");
+        }
+        assert_eq!(parser.screen().scrollback_available(), 0);
+
+        parser.process(b"\x1b[?1049l");
+        assert_eq!(parser.screen().primary_grid().scrollback_available(), 0);
+        assert_eq!(parser.screen().contents(), "");
+    }
+
+    #[test]
+    fn test_alternate_screen_47_keeps_contents() {
+        let mut parser = parse(b"\x1b[?47hfirst\x1b[?47l");
+        assert!(!parser.screen().is_alternate_screen());
+
+        parser.process(b"\x1b[?47h");
+        assert_eq!(parser.screen().contents(), "first");
+    }
+
+    #[test]
+    fn test_alternate_screen_1047_clears_on_exit() {
+        let mut parser = parse(b"\x1b[?1047hfirst\x1b[?1047l");
+        assert!(!parser.screen().is_alternate_screen());
+
+        parser.process(b"\x1b[?1047h");
+        assert_eq!(parser.screen().contents(), "");
+    }
+
+    #[test]
+    fn test_cursor_visibility_mode() {
+        let mut parser = parse(b"\x1b[?25l");
+        assert!(!parser.screen().cursor_visible());
+
+        parser.process(b"\x1b[?25h");
+        assert!(parser.screen().cursor_visible());
+    }
+
+    #[test]
+    fn test_cursor_blink_mode() {
+        let mut parser = parse(b"\x1b[?12h");
+        assert!(parser.screen().cursor_blinking());
+
+        parser.process(b"\x1b[?12l");
+        assert!(!parser.screen().cursor_blinking());
+    }
+
+    #[test]
+    fn test_osc52_clipboard_policy() {
+        // "hello" and "world" in base64
+        let mut parser = parse(b"\x1b]52;c;aGVsbG8=\x07");
+        assert!(parser.screen_mut().take_clipboard_requests().is_empty());
+
+        parser
+            .screen_mut()
+            .set_osc52_policy(Osc52Policy::AllowWrite);
+        parser.process(b"\x1b]52;c;aGVsbG8=\x07\x1b]52;p;d29ybGQ=\x1b\\");
+        // Queries are never answered
+        parser.process(b"\x1b]52;c;?\x07");
+        assert_eq!(
+            parser.screen_mut().take_clipboard_requests(),
+            ["hello", "world"]
+        );
+        assert!(parser.screen_mut().take_clipboard_requests().is_empty());
+        assert!(parser.take_replies().is_empty());
+    }
+
+    #[test]
+    fn test_bell_count() {
+        let mut parser = parse(b"a\x07b\x07");
+        assert_eq!(parser.screen().bell_count(), 2);
+        assert_eq!(parser.screen().contents(), "ab");
+
+        // The count survives a full reset so watchers never see it go back
+        parser.process(b"\x1bc\x07");
+        assert_eq!(parser.screen().bell_count(), 3);
+    }
+
+    #[test]
+    fn test_cursor_style_decscusr() {
+        let mut parser = parse(b"abc");
+        assert_eq!(parser.screen().cursor_shape(), CursorShape::Block);
+
+        parser.process(b"\x1b[6 q");
+        assert_eq!(parser.screen().cursor_shape(), CursorShape::Bar);
+        assert!(!parser.screen().cursor_blinking());
+        // The cursor does not move
+        assert_eq!(parser.screen().cursor_pos().col, 3);
+
+        parser.process(b"\x1b[3 q");
+        assert_eq!(parser.screen().cursor_shape(), CursorShape::Underline);
+        assert!(parser.screen().cursor_blinking());
+
+        parser.process(b"\x1b[0 q");
+        assert_eq!(parser.screen().cursor_shape(), CursorShape::Block);
+        assert!(!parser.screen().cursor_blinking());
+
+        parser.process(b"\x1b[5 q\x1bc");
+        assert_eq!(parser.screen().cursor_shape(), CursorShape::Block);
+    }
+
+    #[test]
+    fn test_bracketed_paste_mode() {
+        let mut parser = parse(b"");
+        assert!(!parser.screen().bracketed_paste());
+
+        parser.process(b"\x1b[?2004h");
+        assert!(parser.screen().bracketed_paste());
+
+        parser.process(b"\x1b[?2004l");
+        assert!(!parser.screen().bracketed_paste());
+    }
+
+    #[test]
+    fn test_osc8_hyperlink_cells() {
+        let parser = parse(b"a\x1b]8;id=x;https://example.com\x1b\\link\x1b]8;;\x1b\\b");
+        let screen = parser.screen();
+        let row = screen.grid().visible_row(0).unwrap();
+
+        assert!(row.get(0).unwrap().hyperlink().is_none());
+        let link = row.get(1).unwrap().hyperlink().unwrap();
+        assert_eq!(link.uri(), "https://example.com");
+        assert_eq!(link.id(), Some("x"));
+        assert!(row.get(4).unwrap().hyperlink().is_some());
+        assert!(row.get(5).unwrap().hyperlink().is_none());
+    }
+
+    #[test]
+    fn test_link_at_prefers_hyperlink_over_detection() {
+        let parser = parse(b"\x1b]8;;file:///tmp/a.rs\x1b\\a.rs:1\x1b]8;;\x1b\\ b.rs:2:3");
+        let screen = parser.screen();
+
+        assert_eq!(
+            screen.link_at(0, 0),
+            Some(LinkTarget::Hyperlink(Hyperlink::new("file:///tmp/a.rs")))
+        );
+        assert_eq!(
+            screen.link_at(0, 8),
+            Some(LinkTarget::FileLocation {
+                path: "b.rs".to_string(),
+                line: 2,
+                column: Some(3),
+            })
+        );
+        assert_eq!(screen.link_at(0, 6), None);
+        assert_eq!(screen.detect_links().len(), 2);
+    }
+
+    /// Bytes a shell with OSC 133 integration emits for one command
+    fn shell_command(cmd: &str, output: &str, status: i32) -> Vec<u8> {
+        format!(
+            "\x1b]133;A\x07$ \x1b]133;B\x07{cmd}\r\n\x1b]133;C\x07{output}\x1b]133;D;{status}\x07"
+        )
+        .into_bytes()
+    }
+
+    #[test]
+    fn test_semantic_prompt_marks() {
+        let mut bytes = shell_command("ls", "a\r\nb\r\n", 0);
+        bytes.extend(shell_command("false", "", 1));
+        bytes.extend(b"\x1b]133;A\x07$ ");
+        let parser = parse(&bytes);
+        let screen = parser.screen();
+
+        let row0 = screen.grid().drawing_row(0).unwrap().marks();
+        assert!(row0.prompt_start && row0.input_start);
+        assert!(screen.grid().drawing_row(1).unwrap().marks().output_start);
+
+        let commands = screen.commands();
+        assert_eq!(commands.len(), 3);
+        assert_eq!(commands[0].prompt_row, 0);
+        assert_eq!(commands[0].output_row, Some(1));
+        assert_eq!(commands[0].end_row, Some(3));
+        assert_eq!(commands[0].exit_code, Some(0));
+        assert_eq!(commands[0].output_rows(4), Some((1, 2)));
+        assert_eq!(commands[1].prompt_row, 3);
+        assert_eq!(commands[1].exit_code, Some(1));
+        assert_eq!(commands[2].prompt_row, 4);
+        assert!(!commands[2].is_finished());
+    }
+
+    #[test]
+    fn test_last_exit_code() {
+        let mut parser = parse(b"");
+        assert_eq!(parser.screen().last_exit_code(), None);
+
+        parser.process(&shell_command("false", "", 1));
+        assert_eq!(parser.screen().last_exit_code(), Some(1));
+        // A new prompt keeps the last finished command's code
+        parser.process(b"\x1b]133;A\x07$ ");
+        assert_eq!(parser.screen().last_exit_code(), Some(1));
+        parser.process(&shell_command("true", "", 0));
+        assert_eq!(parser.screen().last_exit_code(), Some(0));
+
+        // End marks without a running command are ignored
+        parser.process(b"\r\n\x1b]133;D;7\x07");
+        assert_eq!(parser.screen().last_exit_code(), Some(0));
+        let last_finished = parser
+            .screen()
+            .commands()
+            .iter()
+            .rev()
+            .find(|cmd| cmd.is_finished())
+            .and_then(|cmd| cmd.exit_code);
+        assert_eq!(last_finished, Some(0));
+    }
+
+    #[test]
+    fn test_semantic_prompt_fresh_line() {
+        // Output without a trailing newline: the next prompt starts on a new line
+        let parser = parse(b"partial\x1b]133;A\x07$ ");
+        let screen = parser.screen();
+
+        assert_eq!(screen.cursor_pos(), Pos::new(2, 1));
+        assert!(screen.grid().drawing_row(1).unwrap().marks().prompt_start);
+        assert!(!screen.grid().drawing_row(0).unwrap().marks().prompt_start);
+    }
+
+    #[test]
+    fn test_semantic_prompt_marks_scroll_into_scrollback() {
+        let mut bytes = shell_command("seq 6", "1\r\n2\r\n3\r\n4\r\n5\r\n6\r\n", 0);
+        bytes.extend(b"\x1b]133;A\x07$ ");
+        let parser = parse(&bytes);
+        let commands = parser.screen().commands();
+
+        // 5 visible rows; the first prompt scrolled off the top
+        assert_eq!(commands[0].prompt_row, -3);
+        assert_eq!(commands[0].output_row, Some(-2));
+        assert_eq!(commands[1].prompt_row, 4);
+    }
+
+    #[test]
+    fn test_osc7_current_dir() {
+        let mut parser = parse(b"");
+        assert!(parser.screen().current_dir().is_none());
+
+        parser.process(b"\x1b]7;file://host/home/user/src%20dir\x1b\\");
+        assert_eq!(
+            parser.screen().current_dir(),
+            Some(Path::new("/home/user/src dir"))
+        );
+    }
+
+    #[test]
+    fn test_resize_reflow_keeps_prompt_marks() {
+        let mut bytes = shell_command("echo 0123456789abcdef", "0123456789abcdef\r\n", 0);
+        bytes.extend(b"\x1b]133;A\x07$ ");
+        let mut parser = parse(&bytes);
+        assert_eq!(parser.screen().commands()[0].output_row, Some(2));
+
+        parser.resize(5, 40);
+        let screen = parser.screen();
+        assert_eq!(
+            screen.grid().drawing_row(0).unwrap().contents_trimmed(),
+            "$ echo 0123456789abcdef"
+        );
+        let commands = screen.commands();
+        assert_eq!(commands.len(), 2);
+        assert_eq!(commands[0].output_row, Some(1));
+        assert_eq!(commands[0].end_row, Some(2));
+        assert_eq!(commands[1].prompt_row, 2);
+        assert_eq!(screen.cursor_pos(), Pos::new(2, 2));
+    }
+
+    #[test]
+    fn test_combining_marks_attach_to_previous_cell() {
+        let parser = parse("cafe\u{301} x".as_bytes());
+        let row = parser.screen().grid().drawing_row(0).unwrap();
+        assert_eq!(row.get(3).unwrap().text(), "e\u{301}");
+        assert_eq!(row.get(4).unwrap().text(), " ");
+        assert_eq!(parser.screen().cursor_pos(), Pos::new(6, 0));
+        assert_eq!(
+            parser.screen().get_selected_text(0, 0, 6, 0),
+            "cafe\u{301} x"
+        );
+    }
+
+    #[test]
+    fn test_emoji_clusters_take_one_cell() {
+        let family = "\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}";
+        let flag = "\u{1F1FA}\u{1F1F8}";
+        let heart = "\u{2764}\u{FE0F}";
+        let parser = parse(format!("{family}{flag}{heart}|").as_bytes());
+        let row = parser.screen().grid().drawing_row(0).unwrap();
+
+        assert_eq!(row.get(0).unwrap().text(), family);
+        assert!(row.is_wide_continuation(1));
+        assert_eq!(row.get(2).unwrap().text(), flag);
+        assert!(row.is_wide_continuation(3));
+        // The variation selector widens the heart to two columns
+        assert_eq!(row.get(4).unwrap().text(), heart);
+        assert!(row.is_wide_continuation(5));
+        assert_eq!(row.get(6).unwrap().text(), "|");
+
+        let text = format!("{family}{flag}{heart}|");
+        assert_eq!(parser.screen().get_selected_text(0, 0, 7, 0), text);
+        assert_eq!(parser.screen().get_selected_text(3, 0, 4, 0), flag);
+    }
+
+    #[test]
+    fn test_combining_mark_after_wrap_pending() {
+        let mut parser = Parser::new(2, 4, 0);
+        parser.process("abce\u{301}".as_bytes());
+        let row = parser.screen().grid().drawing_row(0).unwrap();
+        assert_eq!(row.get(3).unwrap().text(), "e\u{301}");
+        assert_eq!(parser.screen().cursor_pos(), Pos::new(3, 0));
+
+        // A leading combining mark has nothing to attach to and is dropped
+        parser.process("\r\n\u{301}z".as_bytes());
+        assert_eq!(parser.screen().contents(), "abce\u{301}\nz");
+    }
+
+    #[test]
+    fn test_dec_line_drawing_g0() {
+        let parser = parse(b"\x1b(0lqqk\x1b(B lqqk");
+        assert_eq!(
+            parser.screen().contents(),
+            "\u{250c}\u{2500}\u{2500}\u{2510} lqqk"
+        );
+    }
+
+    #[test]
+    fn test_shift_out_selects_g1() {
+        let parser = parse(b"\x1b)0x\x0ex\x0fx\x1b)A\x0e#");
+        assert_eq!(parser.screen().contents(), "x\u{2502}x\u{a3}");
+    }
+
+    #[test]
+    fn test_default_tab_stops() {
+        let mut parser = parse(b"\t");
+        assert_eq!(parser.screen().cursor_pos().col, 8);
+        parser.process(b"\t\t\t");
+        // The last column stops the cursor
+        assert_eq!(parser.screen().cursor_pos().col, 19);
+    }
+
+    #[test]
+    fn test_set_and_clear_tab_stops() {
+        // HTS at column 3, then clear the stop at column 8 (TBC 0)
+        let mut parser = parse(b"\x1b[4G\x1bH\x1b[9G\x1b[g\r\t");
+        assert_eq!(parser.screen().cursor_pos().col, 3);
+        parser.process(b"\t");
+        assert_eq!(parser.screen().cursor_pos().col, 16);
+
+        // TBC 3 clears every stop
+        parser.process(b"\x1b[3g\r\t");
+        assert_eq!(parser.screen().cursor_pos().col, 19);
+    }
+
+    #[test]
+    fn test_tab_forward_and_backward() {
+        let mut parser = parse(b"\x1b[2I");
+        assert_eq!(parser.screen().cursor_pos().col, 16);
+        parser.process(b"\x1b[Z");
+        assert_eq!(parser.screen().cursor_pos().col, 8);
+        parser.process(b"\x1b[5Z");
+        assert_eq!(parser.screen().cursor_pos().col, 0);
+    }
+
+    #[test]
+    fn test_tab_stops_follow_resize() {
+        let mut parser = parse(b"\x1b[3g");
+        parser.resize(5, 40);
+        parser.process(b"\t");
+        // Cleared stops stay cleared; the new columns get default stops
+        assert_eq!(parser.screen().cursor_pos().col, 24);
+    }
+
+    #[test]
+    fn test_insert_mode() {
+        let mut parser = parse(b"abcdef\r\x1b[4hXY");
+        assert_eq!(parser.screen().contents(), "XYabcdef");
+        assert_eq!(parser.screen().cursor_pos().col, 2);
+
+        parser.process(b"\x1b[4lZ");
+        assert_eq!(parser.screen().contents(), "XYZbcdef");
+    }
+
+    #[test]
+    fn test_insert_character_inserts_blanks() {
+        let parser = parse(b"abc\r\x1b[2@");
+        assert_eq!(parser.screen().contents(), "  abc");
+    }
+
+    #[test]
+    fn test_screen_alignment() {
+        let parser = parse(b"hello\x1b[2;4r\x1b[3;3H\x1b#8");
+        let screen = parser.screen();
+        assert_eq!(screen.contents(), vec!["E".repeat(20); 5].join("\n"));
+        assert_eq!(screen.cursor_pos(), Pos::new(0, 0));
+        assert_eq!(screen.grid().scroll_region(), (0, 5));
+    }
+
+    #[test]
+    fn test_autowrap_mode() {
+        let mut parser = parse(b"\x1b[?7l");
+        assert!(!parser.screen().auto_wrap());
+
+        parser.process(b"abcdefghijklmnopqrstuvwxyz");
+        assert_eq!(parser.screen().cursor_pos().row, 0);
+        assert_eq!(parser.screen().contents(), "abcdefghijklmnopqrsz");
+
+        parser.process(b"\x1b[?7h\r\nabcdefghijklmnopqrstuvwxyz");
+        assert_eq!(parser.screen().cursor_pos().row, 2);
+    }
+
+    #[test]
+    fn test_origin_mode() {
+        let mut parser = parse(b"\x1b[?6h");
+        assert!(parser.screen().origin_mode());
+        assert_eq!(parser.screen().cursor_pos(), Pos::new(0, 0));
+
+        parser.process(b"\x1b[2;4r");
+        parser.process(b"\x1b[1;1H");
+        assert_eq!(parser.screen().cursor_pos(), Pos::new(0, 1));
+
+        // Positions are clamped to the scroll region
+        parser.process(b"\x1b[10;1H");
+        assert_eq!(parser.screen().cursor_pos(), Pos::new(0, 3));
+
+        parser.process(b"\x1b[?6l\x1b[1;1H");
+        assert_eq!(parser.screen().cursor_pos(), Pos::new(0, 0));
+    }
+
+    #[test]
+    fn test_mouse_tracking_modes() {
+        let mut parser = parse(b"\x1b[?1000h");
+        assert_eq!(
+            parser.screen().mouse_protocol_mode(),
+            MouseProtocolMode::PressRelease
+        );
+
+        parser.process(b"\x1b[?1002h");
+        assert_eq!(
+            parser.screen().mouse_protocol_mode(),
+            MouseProtocolMode::ButtonMotion
+        );
+
+        // Resetting a mode that is not active leaves the current one alone
+        parser.process(b"\x1b[?1000l");
+        assert_eq!(
+            parser.screen().mouse_protocol_mode(),
+            MouseProtocolMode::ButtonMotion
+        );
+
+        parser.process(b"\x1b[?1003h\x1b[?1003l");
+        assert_eq!(
+            parser.screen().mouse_protocol_mode(),
+            MouseProtocolMode::None
+        );
+
+        parser.process(b"\x1b[?9h");
+        assert_eq!(
+            parser.screen().mouse_protocol_mode(),
+            MouseProtocolMode::Press
+        );
+    }
+
+    #[test]
+    fn test_mouse_encodings() {
+        let mut parser = parse(b"\x1b[?1006h");
+        assert_eq!(
+            parser.screen().mouse_protocol_encoding(),
+            MouseProtocolEncoding::Sgr
+        );
+
+        parser.process(b"\x1b[?1005h");
+        assert_eq!(
+            parser.screen().mouse_protocol_encoding(),
+            MouseProtocolEncoding::Utf8
+        );
+
+        parser.process(b"\x1b[?1005l");
+        assert_eq!(
+            parser.screen().mouse_protocol_encoding(),
+            MouseProtocolEncoding::Default
+        );
+    }
+
+    /// Attributes of a character printed after `sgr` (e.g. `"1;4"`)
+    fn sgr_attrs(sgr: &str) -> Attrs {
+        let parser = parse(format!("\x1b[{sgr}mx").as_bytes());
+        *parser
+            .screen()
+            .grid()
+            .drawing_row(0)
+            .unwrap()
+            .get(0)
+            .unwrap()
+            .attrs()
+    }
+
+    #[test]
+    fn test_sgr_intensity() {
+        assert!(sgr_attrs("1").bold() && !sgr_attrs("1").dim());
+        assert!(sgr_attrs("2").dim() && !sgr_attrs("2").bold());
+        assert!(!sgr_attrs("1;2").bold());
+        let normal = sgr_attrs("1;22");
+        assert!(!normal.bold() && !normal.dim());
+        assert!(!sgr_attrs("2;22").dim());
+    }
+
+    #[test]
+    fn test_sgr_text_modes() {
+        assert!(sgr_attrs("3").italic());
+        assert!(!sgr_attrs("3;23").italic());
+        assert!(sgr_attrs("5").blink() && !sgr_attrs("5").rapid_blink());
+        assert!(sgr_attrs("6").rapid_blink());
+        assert!(!sgr_attrs("5;25").blink());
+        assert!(!sgr_attrs("6;25").blink());
+        assert!(sgr_attrs("7").inverse());
+        assert!(!sgr_attrs("7;27").inverse());
+        assert!(sgr_attrs("8").hidden());
+        assert!(!sgr_attrs("8;28").hidden());
+        assert!(sgr_attrs("9").strikethrough());
+        assert!(!sgr_attrs("9;29").strikethrough());
+        assert!(sgr_attrs("53").overline());
+        assert!(!sgr_attrs("53;55").overline());
+    }
+
+    #[test]
+    fn test_sgr_underline_styles() {
+        let cases = [
+            ("4", UnderlineStyle::Single),
+            ("21", UnderlineStyle::Double),
+            ("4:0", UnderlineStyle::None),
+            ("4:1", UnderlineStyle::Single),
+            ("4:2", UnderlineStyle::Double),
+            ("4:3", UnderlineStyle::Curly),
+            ("4:4", UnderlineStyle::Dotted),
+            ("4:5", UnderlineStyle::Dashed),
+            ("4:3;24", UnderlineStyle::None),
+        ];
+        for (sgr, style) in cases {
+            assert_eq!(sgr_attrs(sgr).underline_style(), style, "SGR {sgr}");
+        }
+    }
+
+    #[test]
+    fn test_sgr_colors() {
+        let cases = [
+            ("31", Color::Indexed(1), Color::Default),
+            ("97", Color::Indexed(15), Color::Default),
+            ("38;5;123", Color::Indexed(123), Color::Default),
+            ("38;2;10;20;30", Color::Rgb(10, 20, 30), Color::Default),
+            ("31;39", Color::Default, Color::Default),
+            ("42", Color::Default, Color::Indexed(2)),
+            ("104", Color::Default, Color::Indexed(12)),
+            ("48;5;200", Color::Default, Color::Indexed(200)),
+            ("48;2;1;2;3", Color::Default, Color::Rgb(1, 2, 3)),
+            ("42;49", Color::Default, Color::Default),
+        ];
+        for (sgr, fg, bg) in cases {
+            let attrs = sgr_attrs(sgr);
+            assert_eq!((attrs.fg, attrs.bg), (fg, bg), "SGR {sgr}");
+        }
+    }
+
+    #[test]
+    fn test_sgr_underline_color() {
+        assert_eq!(
+            sgr_attrs("4;58;2;255;0;0").underline_color,
+            Color::Rgb(255, 0, 0)
+        );
+        assert_eq!(sgr_attrs("58;5;9").underline_color, Color::Indexed(9));
+        assert_eq!(sgr_attrs("58;5;9;59").underline_color, Color::Default);
+    }
+
+    #[test]
+    fn test_sgr_reset() {
+        let attrs = sgr_attrs("1;2;3;4:3;5;7;8;9;53;31;42;58;5;1;0");
+        assert_eq!(attrs, Attrs::default());
+    }
+
+    #[test]
+    fn test_primary_device_attributes() {
+        let mut parser = parse(b"\x1b[c");
+        assert_eq!(parser.take_replies(), b"\x1b[?62;22c");
+        assert!(parser.take_replies().is_empty());
+    }
+
+    #[test]
+    fn test_secondary_device_attributes() {
+        let mut parser = parse(b"\x1b[>c");
+        assert_eq!(parser.take_replies(), b"\x1b[>1;10;0c");
+    }
+
+    #[test]
+    fn test_device_status_report() {
+        let mut parser = parse(b"\x1b[5n");
+        assert_eq!(parser.take_replies(), b"\x1b[0n");
+    }
+
+    #[test]
+    fn test_cursor_position_report() {
+        let mut parser = parse(b"\x1b[3;7H\x1b[6n");
+        assert_eq!(parser.take_replies(), b"\x1b[3;7R");
+
+        // Origin mode reports relative to the scroll region
+        parser.process(b"\x1b[2;5r\x1b[?6h\x1b[2;1H\x1b[6n");
+        assert_eq!(parser.take_replies(), b"\x1b[2;1R");
+
+        // A cursor restored above the scroll region must not underflow
+        let mut parser = parse(b"\x1b[1;1H\x1b7\x1b[5;10r\x1b[?6h\x1b8\x1b[6n");
+        assert_eq!(parser.take_replies(), b"\x1b[1;1R");
+    }
+
+    #[test]
+    fn test_xtversion() {
+        let mut parser = parse(b"\x1b[>q");
+        let expected = format!(
+            "\x1bP>|ratatui-toolkit({})\x1b\\",
+            env!("CARGO_PKG_VERSION")
+        );
+        assert_eq!(parser.take_replies(), expected.as_bytes());
+    }
+
+    #[test]
+    fn test_osc_dynamic_color_queries() {
+        let mut parser = Parser::new(5, 20, 100);
+        parser.set_palette(
+            Palette::default()
+                .with_foreground((0xc0, 0xc5, 0xce))
+                .with_background((0x2b, 0x30, 0x3b)),
+        );
+
+        parser.process(b"\x1b]10;?\x1b\\");
+        assert_eq!(parser.take_replies(), b"\x1b]10;rgb:c0c0/c5c5/cece\x1b\\");
+
+        parser.process(b"\x1b]11;?\x07");
+        assert_eq!(parser.take_replies(), b"\x1b]11;rgb:2b2b/3030/3b3b\x1b\\");
+    }
+
+    #[test]
+    fn test_osc_indexed_color_query() {
+        let mut parser = Parser::new(5, 20, 100);
+        parser.set_palette(Palette::default().with_color(1, (0xaa, 0x11, 0x22)));
+
+        parser.process(b"\x1b]4;1;?\x1b\\");
+        assert_eq!(parser.take_replies(), b"\x1b]4;1;rgb:aaaa/1111/2222\x1b\\");
+    }
+
+    #[test]
+    fn test_application_cursor_and_keypad_modes() {
+        let mut parser = parse(b"\x1b[?1h\x1b=");
+        let modes = parser.screen().keyboard_modes();
+        assert!(modes.application_cursor);
+        assert!(modes.application_keypad);
+
+        parser.process(b"\x1b[?1l\x1b>");
+        let modes = parser.screen().keyboard_modes();
+        assert!(!modes.application_cursor);
+        assert!(!modes.application_keypad);
+    }
+
+    #[test]
+    fn test_kitty_keyboard_disabled_by_default() {
+        let mut parser = parse(b"\x1b[>1u\x1b[?u");
+        assert_eq!(parser.screen().keyboard_modes().kitty_flags, 0);
+        assert!(parser.take_replies().is_empty());
+    }
+
+    #[test]
+    fn test_kitty_keyboard_push_pop_query() {
+        let mut parser = Parser::new(5, 20, 100);
+        parser.screen_mut().set_kitty_keyboard_enabled(true);
+
+        parser.process(b"\x1b[?u");
+        assert_eq!(parser.take_replies(), b"\x1b[?0u");
+
+        parser.process(b"\x1b[>1u\x1b[>9u");
+        assert_eq!(parser.screen().keyboard_modes().kitty_flags, 9);
+
+        parser.process(b"\x1b[?u");
+        assert_eq!(parser.take_replies(), b"\x1b[?9u");
+
+        parser.process(b"\x1b[<u");
+        assert_eq!(parser.screen().keyboard_modes().kitty_flags, 1);
+
+        parser.process(b"\x1b[<5u");
+        assert_eq!(parser.screen().keyboard_modes().kitty_flags, 0);
+    }
+
+    #[test]
+    fn test_kitty_keyboard_set_modes() {
+        let mut parser = Parser::new(5, 20, 100);
+        parser.screen_mut().set_kitty_keyboard_enabled(true);
+
+        parser.process(b"\x1b[=1u");
+        assert_eq!(parser.screen().keyboard_modes().kitty_flags, 1);
+
+        parser.process(b"\x1b[=8;2u");
+        assert_eq!(parser.screen().keyboard_modes().kitty_flags, 9);
+
+        parser.process(b"\x1b[=1;3u");
+        assert_eq!(parser.screen().keyboard_modes().kitty_flags, 8);
+    }
+
+    #[test]
+    fn test_full_reset_leaves_alternate_screen() {
+        let parser = parse(b"\x1b[?1049h\x1bc");
+        assert!(!parser.screen().is_alternate_screen());
+    }
 }
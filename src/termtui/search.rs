@@ -0,0 +1,200 @@
+//! Text search over terminal rows (used by copy mode)
+
+use crate::termtui::copy_mode::CopyPos;
+use crate::termtui::row::Row;
+use regex::{Regex, RegexBuilder};
+
+/// Search direction
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SearchDirection {
+    /// Towards the bottom of the screen
+    #[default]
+    Forward,
+    /// Towards the top of the scrollback
+    Backward,
+}
+
+impl SearchDirection {
+    /// Get the opposite direction
+    pub fn reverse(self) -> Self {
+        match self {
+            SearchDirection::Forward => SearchDirection::Backward,
+            SearchDirection::Backward => SearchDirection::Forward,
+        }
+    }
+}
+
+/// How the query is interpreted
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SearchOptions {
+    /// Treat the query as a regular expression
+    pub regex: bool,
+    /// Ignore case when matching
+    pub case_insensitive: bool,
+}
+
+/// A match, from the first to the last matched cell (inclusive)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SearchMatch {
+    /// First matched cell
+    pub start: CopyPos,
+    /// Last matched cell
+    pub end: CopyPos,
+}
+
+impl SearchMatch {
+    /// Check if the match covers a cell
+    pub fn contains(&self, x: i32, y: i32) -> bool {
+        let after_start = y > self.start.y || (y == self.start.y && x >= self.start.x);
+        let before_end = y < self.end.y || (y == self.end.y && x <= self.end.x);
+        after_start && before_end
+    }
+}
+
+/// Build the matcher for a query
+pub fn build_matcher(query: &str, options: SearchOptions) -> Result<Regex, regex::Error> {
+    let pattern = if options.regex {
+        query.to_string()
+    } else {
+        regex::escape(query)
+    };
+    RegexBuilder::new(&pattern)
+        .case_insensitive(options.case_insensitive)
+        .build()
+}
+
+/// Find all matches in the given rows
+///
+/// Rows are `(y, row)` pairs in copy-mode coordinates. Soft-wrapped rows are
+/// joined into one logical line, so matches can span wrap points.
+pub fn find_matches<'a>(
+    rows: impl Iterator<Item = (i32, &'a Row)>,
+    matcher: &Regex,
+) -> Vec<SearchMatch> {
+    let mut matches = Vec::new();
+    let mut line = String::new();
+    // Cell position for the start of each cell's text in `line`
+    let mut offsets: Vec<(usize, CopyPos)> = Vec::new();
+
+    for (y, row) in rows {
+        for (x, cell) in row.cells().enumerate() {
+            if cell.is_wide_continuation() {
+                continue;
+            }
+            offsets.push((line.len(), CopyPos::new(x as i32, y)));
+            line.push_str(cell.text());
+        }
+
+        if !row.wrapped() {
+            match_line(&line, &offsets, matcher, &mut matches);
+            line.clear();
+            offsets.clear();
+        }
+    }
+    match_line(&line, &offsets, matcher, &mut matches);
+
+    matches
+}
+
+/// Match one logical line, mapping byte ranges back to cells
+fn match_line(
+    line: &str,
+    offsets: &[(usize, CopyPos)],
+    matcher: &Regex,
+    matches: &mut Vec<SearchMatch>,
+) {
+    let pos_at = |byte: usize| -> CopyPos {
+        match offsets.binary_search_by_key(&byte, |&(offset, _)| offset) {
+            Ok(idx) => offsets[idx].1,
+            Err(idx) => offsets[idx.saturating_sub(1)].1,
+        }
+    };
+
+    for m in matcher.find_iter(line) {
+        if m.is_empty() {
+            continue;
+        }
+        matches.push(SearchMatch {
+            start: pos_at(m.start()),
+            end: pos_at(m.end() - 1),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(text: &str, width: u16, wrapped: bool) -> Row {
+        let mut row = Row::new(width);
+        for (x, ch) in text.chars().enumerate() {
+            row.get_mut(x as u16).unwrap().set_text(ch.to_string());
+        }
+        row.set_wrapped(wrapped);
+        row
+    }
+
+    fn search(rows: &[Row], query: &str, options: SearchOptions) -> Vec<SearchMatch> {
+        let matcher = build_matcher(query, options).unwrap();
+        find_matches(
+            rows.iter().enumerate().map(|(y, r)| (y as i32, r)),
+            &matcher,
+        )
+    }
+
+    #[test]
+    fn test_plain_search() {
+        let rows = [row("foo bar foo", 12, false), row("a.c abc", 12, false)];
+
+        let matches = search(&rows, "foo", SearchOptions::default());
+        assert_eq!(matches.len(), 2);
+        assert_eq!(matches[1].start, CopyPos::new(8, 0));
+        assert_eq!(matches[1].end, CopyPos::new(10, 0));
+
+        // The query is literal unless regex mode is on
+        assert_eq!(search(&rows, "a.c", SearchOptions::default()).len(), 1);
+    }
+
+    #[test]
+    fn test_regex_and_case_insensitive() {
+        let rows = [row("Error: 42 error: 7", 20, false)];
+
+        let regex = SearchOptions {
+            regex: true,
+            ..Default::default()
+        };
+        assert_eq!(search(&rows, r"\d+", regex).len(), 2);
+        assert_eq!(search(&rows, "error", SearchOptions::default()).len(), 1);
+
+        let nocase = SearchOptions {
+            case_insensitive: true,
+            ..Default::default()
+        };
+        assert_eq!(search(&rows, "error", nocase).len(), 2);
+
+        assert!(build_matcher("(", regex).is_err());
+    }
+
+    #[test]
+    fn test_match_across_wrapped_rows() {
+        let rows = [row("hello wo", 8, true), row("rld", 8, false)];
+
+        let matches = search(&rows, "world", SearchOptions::default());
+        assert_eq!(
+            matches,
+            vec![SearchMatch {
+                start: CopyPos::new(6, 0),
+                end: CopyPos::new(2, 1),
+            }]
+        );
+        assert!(matches[0].contains(7, 0));
+        assert!(matches[0].contains(0, 1));
+        assert!(!matches[0].contains(3, 1));
+    }
+
+    #[test]
+    fn test_no_match_across_hard_line_breaks() {
+        let rows = [row("hello wo", 8, false), row("rld", 8, false)];
+        assert!(search(&rows, "world", SearchOptions::default()).is_empty());
+    }
+}
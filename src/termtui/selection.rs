@@ -0,0 +1,298 @@
+//! Selection shapes and word/line expansion (used by copy mode)
+
+use crate::termtui::copy_mode::CopyPos;
+use crate::termtui::screen::Screen;
+
+/// Characters that end a word for double-click selection, besides whitespace
+pub const DEFAULT_WORD_SEPARATORS: &str = ",;()[]{}<>|'\"`";
+
+/// Shape of a copy-mode selection
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SelectionMode {
+    /// Characters from the anchor to the cursor, across lines (`v`)
+    #[default]
+    Stream,
+    /// Whole lines from the anchor to the cursor (`V`)
+    Line,
+    /// The rectangle between the anchor and the cursor (Ctrl+V)
+    Block,
+}
+
+/// A selection resolved against the screen, from the first to the last
+/// selected cell (inclusive)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Selection {
+    /// First selected cell (top-left corner for a block)
+    pub start: CopyPos,
+    /// Last selected cell (bottom-right corner for a block)
+    pub end: CopyPos,
+    /// Selection shape
+    pub mode: SelectionMode,
+}
+
+impl Selection {
+    /// Resolve the selection between an anchor and the cursor
+    ///
+    /// Line selections grow to whole logical lines, following soft wraps.
+    pub fn new(screen: &Screen, anchor: CopyPos, cursor: CopyPos, mode: SelectionMode) -> Self {
+        let (low, high) = CopyPos::to_low_high(&anchor, &cursor);
+        let (start, end) = match mode {
+            SelectionMode::Stream => (low, high),
+            SelectionMode::Line => (line_bounds(screen, low.y).0, line_bounds(screen, high.y).1),
+            SelectionMode::Block => (
+                CopyPos::new(anchor.x.min(cursor.x), low.y),
+                CopyPos::new(anchor.x.max(cursor.x), high.y),
+            ),
+        };
+        Self { start, end, mode }
+    }
+
+    /// Check if the selection covers a cell
+    pub fn contains(&self, x: i32, y: i32) -> bool {
+        if y < self.start.y || y > self.end.y {
+            return false;
+        }
+        match self.mode {
+            SelectionMode::Stream => {
+                (y > self.start.y || x >= self.start.x) && (y < self.end.y || x <= self.end.x)
+            }
+            SelectionMode::Line => true,
+            SelectionMode::Block => x >= self.start.x && x <= self.end.x,
+        }
+    }
+
+    /// Get the selected text
+    ///
+    /// Soft-wrapped rows are joined, except in a block selection.
+    pub fn text(&self, screen: &Screen) -> String {
+        let (start, end) = (self.start, self.end);
+        match self.mode {
+            SelectionMode::Block => screen.get_block_text(start.x, start.y, end.x + 1, end.y),
+            SelectionMode::Stream | SelectionMode::Line => {
+                screen.get_selected_text(start.x, start.y, end.x + 1, end.y)
+            }
+        }
+    }
+}
+
+/// Get the first and last cell of the logical line containing a row
+///
+/// Rows joined by soft wraps belong to the same line.
+pub fn line_bounds(screen: &Screen, y: i32) -> (CopyPos, CopyPos) {
+    let wrapped = |y: i32| screen.row_at(y).is_some_and(|row| row.wrapped());
+    let mut top = y;
+    while wrapped(top - 1) {
+        top -= 1;
+    }
+    let mut bottom = y;
+    while wrapped(bottom) && screen.row_at(bottom + 1).is_some() {
+        bottom += 1;
+    }
+    let width = screen.size().cols as i32;
+    (CopyPos::new(0, top), CopyPos::new(width - 1, bottom))
+}
+
+/// Get the first and last cell of the word under a position
+///
+/// Words end at whitespace and at any of `separators`, and continue across
+/// soft wraps. A position outside a word selects just that cell.
+pub fn word_bounds(screen: &Screen, pos: CopyPos, separators: &str) -> (CopyPos, CopyPos) {
+    let in_word = |pos: CopyPos| is_word_cell(screen, pos, separators);
+    if !in_word(pos) {
+        return (pos, pos);
+    }
+
+    let mut start = pos;
+    while let Some(prev) = prev_cell(screen, start).filter(|&p| in_word(p)) {
+        start = prev;
+    }
+    let mut end = pos;
+    while let Some(next) = next_cell(screen, end).filter(|&p| in_word(p)) {
+        end = next;
+    }
+    (start, end)
+}
+
+/// Check if a cell holds part of a word
+fn is_word_cell(screen: &Screen, pos: CopyPos, separators: &str) -> bool {
+    let Some(row) = screen.row_at(pos.y) else {
+        return false;
+    };
+    let Ok(mut col) = u16::try_from(pos.x) else {
+        return false;
+    };
+    // A wide continuation belongs to the character before it
+    if row.is_wide_continuation(col) {
+        col = col.saturating_sub(1);
+    }
+    row.get(col).is_some_and(|cell| {
+        !cell.text().is_empty()
+            && cell
+                .text()
+                .chars()
+                .all(|c| !c.is_whitespace() && !separators.contains(c))
+    })
+}
+
+/// Get the cell before a position, stepping back over a soft wrap
+fn prev_cell(screen: &Screen, pos: CopyPos) -> Option<CopyPos> {
+    if pos.x > 0 {
+        return Some(CopyPos::new(pos.x - 1, pos.y));
+    }
+    let width = screen.size().cols as i32;
+    screen
+        .row_at(pos.y - 1)
+        .filter(|row| row.wrapped())
+        .map(|_| CopyPos::new(width - 1, pos.y - 1))
+}
+
+/// Get the cell after a position, following a soft wrap
+fn next_cell(screen: &Screen, pos: CopyPos) -> Option<CopyPos> {
+    let width = screen.size().cols as i32;
+    if pos.x < width - 1 {
+        return Some(CopyPos::new(pos.x + 1, pos.y));
+    }
+    let wrapped = screen.row_at(pos.y).is_some_and(|row| row.wrapped());
+    screen
+        .row_at(pos.y + 1)
+        .filter(|_| wrapped)
+        .map(|_| CopyPos::new(0, pos.y + 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::termtui::parser::Parser;
+
+    fn screen(rows: usize, cols: usize, text: &[u8]) -> Screen {
+        let mut parser = Parser::new(rows, cols, 100);
+        parser.process(text);
+        parser.screen().clone()
+    }
+
+    #[test]
+    fn test_stream_contains_single_line() {
+        let screen = Screen::new(24, 80, 0);
+        let selection = Selection::new(
+            &screen,
+            CopyPos::new(5, 10),
+            CopyPos::new(15, 10),
+            SelectionMode::Stream,
+        );
+
+        assert!(selection.contains(5, 10));
+        assert!(selection.contains(10, 10));
+        assert!(selection.contains(15, 10));
+        assert!(!selection.contains(4, 10));
+        assert!(!selection.contains(16, 10));
+        assert!(!selection.contains(10, 9));
+    }
+
+    #[test]
+    fn test_stream_contains_multi_line() {
+        let screen = Screen::new(24, 80, 0);
+        let selection = Selection::new(
+            &screen,
+            CopyPos::new(5, 10),
+            CopyPos::new(15, 12),
+            SelectionMode::Stream,
+        );
+
+        assert!(selection.contains(5, 10));
+        assert!(selection.contains(50, 10)); // All of first line from start
+        assert!(!selection.contains(4, 10));
+
+        assert!(selection.contains(0, 11));
+        assert!(selection.contains(50, 11));
+
+        assert!(selection.contains(0, 12));
+        assert!(selection.contains(15, 12));
+        assert!(!selection.contains(16, 12));
+    }
+
+    #[test]
+    fn test_stream_contains_reversed() {
+        let screen = Screen::new(24, 80, 0);
+        let selection = Selection::new(
+            &screen,
+            CopyPos::new(15, 12),
+            CopyPos::new(5, 10),
+            SelectionMode::Stream,
+        );
+
+        // Should work the same due to to_low_high normalization
+        assert!(selection.contains(10, 11));
+        assert_eq!(selection.start, CopyPos::new(5, 10));
+    }
+
+    #[test]
+    fn test_block_selection() {
+        let screen = screen(4, 10, b"abcdef\r\nghijkl\r\nmnopqr");
+        let selection = Selection::new(
+            &screen,
+            CopyPos::new(4, 0),
+            CopyPos::new(1, 2),
+            SelectionMode::Block,
+        );
+
+        assert_eq!(selection.start, CopyPos::new(1, 0));
+        assert_eq!(selection.end, CopyPos::new(4, 2));
+        assert!(selection.contains(1, 1));
+        assert!(!selection.contains(5, 1));
+        assert!(!selection.contains(0, 2));
+        assert_eq!(selection.text(&screen), "bcde\nhijk\nnopq");
+    }
+
+    #[test]
+    fn test_line_selection_follows_wraps() {
+        // "0123456789abc" wraps onto a second row
+        let screen = screen(4, 10, b"first\r\n0123456789abc\r\nlast");
+        let selection = Selection::new(
+            &screen,
+            CopyPos::new(3, 2),
+            CopyPos::new(3, 2),
+            SelectionMode::Line,
+        );
+
+        assert_eq!(selection.start, CopyPos::new(0, 1));
+        assert_eq!(selection.end, CopyPos::new(9, 2));
+        assert!(selection.contains(9, 1));
+        assert!(!selection.contains(0, 3));
+        assert_eq!(selection.text(&screen), "0123456789abc");
+    }
+
+    #[test]
+    fn test_word_bounds() {
+        let screen = screen(4, 10, b"foo(bar) baz\r\nx");
+        let separators = DEFAULT_WORD_SEPARATORS;
+
+        assert_eq!(
+            word_bounds(&screen, CopyPos::new(5, 0), separators),
+            (CopyPos::new(4, 0), CopyPos::new(6, 0))
+        );
+        // A separator selects just itself
+        assert_eq!(
+            word_bounds(&screen, CopyPos::new(3, 0), separators),
+            (CopyPos::new(3, 0), CopyPos::new(3, 0))
+        );
+        // "baz" wraps after "ba"
+        assert_eq!(
+            word_bounds(&screen, CopyPos::new(0, 1), separators),
+            (CopyPos::new(9, 0), CopyPos::new(1, 1))
+        );
+        // Without separators, only whitespace ends a word
+        assert_eq!(
+            word_bounds(&screen, CopyPos::new(5, 0), ""),
+            (CopyPos::new(0, 0), CopyPos::new(7, 0))
+        );
+    }
+
+    #[test]
+    fn test_word_bounds_wide_characters() {
+        let screen = screen(2, 10, "a 日本語 b".as_bytes());
+        assert_eq!(
+            word_bounds(&screen, CopyPos::new(5, 0), DEFAULT_WORD_SEPARATORS),
+            (CopyPos::new(2, 0), CopyPos::new(7, 0))
+        );
+    }
+}
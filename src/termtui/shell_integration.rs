@@ -0,0 +1,216 @@
+//! Shell integration (OSC 133 semantic prompts, OSC 7 working directory)
+
+use crate::termtui::row::Row;
+use std::path::PathBuf;
+
+/// Shell integration markers recorded on a row
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RowMarks {
+    /// A prompt starts on this row (OSC 133;A)
+    pub prompt_start: bool,
+    /// Command input starts on this row (OSC 133;B)
+    pub input_start: bool,
+    /// Command output starts on this row (OSC 133;C)
+    pub output_start: bool,
+    /// A command finished on this row (OSC 133;D)
+    pub command_end: bool,
+    /// Exit code reported when the command finished
+    pub exit_code: Option<i32>,
+}
+
+impl RowMarks {
+    /// Check if the row carries no markers
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Combine with markers from another row
+    pub(crate) fn merge(&mut self, other: RowMarks) {
+        self.prompt_start |= other.prompt_start;
+        self.input_start |= other.input_start;
+        self.output_start |= other.output_start;
+        self.command_end |= other.command_end;
+        self.exit_code = other.exit_code.or(self.exit_code);
+    }
+}
+
+/// A shell command delimited by semantic prompt marks
+///
+/// Rows use copy-mode coordinates: row 0 is the top of the screen and
+/// scrollback rows are negative.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ShellCommand {
+    /// Row where the prompt starts
+    pub prompt_row: i32,
+    /// Row where the command output starts (`None` until the command runs)
+    pub output_row: Option<i32>,
+    /// Row where the command finished (`None` while it is running)
+    pub end_row: Option<i32>,
+    /// Exit code reported by the shell
+    pub exit_code: Option<i32>,
+}
+
+impl ShellCommand {
+    /// Check if the command has finished
+    pub fn is_finished(&self) -> bool {
+        self.end_row.is_some()
+    }
+
+    /// Get the first and last row of the command output, if it ran
+    ///
+    /// The finishing mark is printed on the row after the last output row,
+    /// so `last_row` is used as the end while the command is still running.
+    pub fn output_rows(&self, last_row: i32) -> Option<(i32, i32)> {
+        let start = self.output_row?;
+        let end = match self.end_row {
+            Some(end) if end > start => end - 1,
+            Some(_) => start,
+            None => last_row.max(start),
+        };
+        Some((start, end))
+    }
+}
+
+/// Collect commands from rows marked with OSC 133
+pub(crate) fn collect_commands<'a>(
+    rows: impl Iterator<Item = (i32, &'a Row)>,
+) -> Vec<ShellCommand> {
+    let mut commands: Vec<ShellCommand> = Vec::new();
+
+    for (y, row) in rows {
+        let marks = row.marks();
+
+        // A command that ends on the same row a new prompt starts belongs
+        // to the previous prompt, so handle the end first.
+        if marks.command_end {
+            if let Some(cmd) = commands.last_mut().filter(|cmd| cmd.end_row.is_none()) {
+                cmd.end_row = Some(y);
+                cmd.exit_code = marks.exit_code;
+            }
+        }
+        if marks.prompt_start {
+            commands.push(ShellCommand {
+                prompt_row: y,
+                output_row: None,
+                end_row: None,
+                exit_code: None,
+            });
+        }
+        if marks.output_start {
+            if let Some(cmd) = commands.last_mut().filter(|cmd| cmd.output_row.is_none()) {
+                cmd.output_row = Some(y);
+            }
+        }
+    }
+
+    commands
+}
+
+/// Parse an OSC 7 `file://host/path` report into a local path
+pub(crate) fn parse_cwd_uri(uri: &str) -> Option<PathBuf> {
+    let rest = uri.strip_prefix("file://")?;
+    // Skip the host part; the path starts at the next slash
+    let path = &rest[rest.find('/')?..];
+    Some(PathBuf::from(percent_decode(path)))
+}
+
+/// Decode `%XX` escapes, leaving malformed escapes untouched
+fn percent_decode(input: &str) -> String {
+    let bytes = input.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if bytes[i] == b'%' && i + 2 < bytes.len() {
+            let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).ok();
+            if let Some(byte) = hex.and_then(|h| u8::from_str_radix(h, 16).ok()) {
+                out.push(byte);
+                i += 3;
+                continue;
+            }
+        }
+        out.push(bytes[i]);
+        i += 1;
+    }
+
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn marked(marks: RowMarks) -> Row {
+        let mut row = Row::new(10);
+        *row.marks_mut() = marks;
+        row
+    }
+
+    #[test]
+    fn test_collect_commands() {
+        let rows = [
+            marked(RowMarks {
+                prompt_start: true,
+                input_start: true,
+                ..Default::default()
+            }),
+            marked(RowMarks {
+                output_start: true,
+                ..Default::default()
+            }),
+            Row::new(10),
+            marked(RowMarks {
+                command_end: true,
+                exit_code: Some(2),
+                prompt_start: true,
+                ..Default::default()
+            }),
+            marked(RowMarks {
+                output_start: true,
+                ..Default::default()
+            }),
+        ];
+
+        let commands = collect_commands(rows.iter().enumerate().map(|(i, r)| (i as i32 - 1, r)));
+        assert_eq!(
+            commands,
+            vec![
+                ShellCommand {
+                    prompt_row: -1,
+                    output_row: Some(0),
+                    end_row: Some(2),
+                    exit_code: Some(2),
+                },
+                ShellCommand {
+                    prompt_row: 2,
+                    output_row: Some(3),
+                    end_row: None,
+                    exit_code: None,
+                },
+            ]
+        );
+        assert_eq!(commands[0].output_rows(10), Some((0, 1)));
+        assert_eq!(commands[1].output_rows(5), Some((3, 5)));
+    }
+
+    #[test]
+    fn test_end_without_prompt_is_ignored() {
+        let rows = [marked(RowMarks {
+            command_end: true,
+            exit_code: Some(0),
+            ..Default::default()
+        })];
+        assert!(collect_commands(rows.iter().map(|r| (0, r))).is_empty());
+    }
+
+    #[test]
+    fn test_parse_cwd_uri() {
+        assert_eq!(
+            parse_cwd_uri("file://host/home/me/My%20Project"),
+            Some(PathBuf::from("/home/me/My Project"))
+        );
+        assert_eq!(parse_cwd_uri("file:///tmp"), Some(PathBuf::from("/tmp")));
+        assert_eq!(parse_cwd_uri("file:///a%zz"), Some(PathBuf::from("/a%zz")));
+        assert_eq!(parse_cwd_uri("https://example.com/"), None);
+    }
+}
@@ -0,0 +1,265 @@
+//! Options for spawning a process in a terminal
+
+use anyhow::Result;
+use portable_pty::CommandBuilder;
+use std::ffi::OsString;
+use std::path::PathBuf;
+
+/// A change to the environment inherited by the child
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum EnvChange {
+    Set(OsString, OsString),
+    Remove(OsString),
+}
+
+/// How to spawn a process in a `TermTui` (builder pattern)
+///
+/// Defaults to the user's shell in the current directory, a 24x80 screen,
+/// 10000 lines of scrollback and `TERM=xterm-256color`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TermTuiSpawnOptions {
+    /// Program to run (`None` for the user's shell)
+    command: Option<OsString>,
+    args: Vec<OsString>,
+    /// Working directory (`None` for the current directory)
+    cwd: Option<PathBuf>,
+    /// Start from an empty environment instead of inheriting it
+    env_clear: bool,
+    env: Vec<EnvChange>,
+    term: String,
+    login_shell: bool,
+    rows: u16,
+    cols: u16,
+    scrollback: usize,
+    /// ANSI export loaded into the screen before the process starts
+    preload: Option<String>,
+}
+
+impl Default for TermTuiSpawnOptions {
+    fn default() -> Self {
+        Self {
+            command: None,
+            args: Vec::new(),
+            cwd: None,
+            env_clear: false,
+            env: Vec::new(),
+            term: "xterm-256color".to_string(),
+            login_shell: false,
+            rows: 24,
+            cols: 80,
+            scrollback: 10000,
+            preload: None,
+        }
+    }
+}
+
+impl TermTuiSpawnOptions {
+    /// Create options that spawn the user's shell
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Run `command` instead of the user's shell (builder pattern)
+    pub fn with_command(mut self, command: impl Into<OsString>) -> Self {
+        self.command = Some(command.into());
+        self
+    }
+
+    /// Append an argument (builder pattern)
+    pub fn with_arg(mut self, arg: impl Into<OsString>) -> Self {
+        self.args.push(arg.into());
+        self
+    }
+
+    /// Append arguments (builder pattern)
+    pub fn with_args<I, S>(mut self, args: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<OsString>,
+    {
+        self.args.extend(args.into_iter().map(Into::into));
+        self
+    }
+
+    /// Set the working directory (builder pattern)
+    pub fn with_cwd(mut self, cwd: impl Into<PathBuf>) -> Self {
+        self.cwd = Some(cwd.into());
+        self
+    }
+
+    /// Set an environment variable (builder pattern)
+    pub fn with_env(mut self, key: impl Into<OsString>, value: impl Into<OsString>) -> Self {
+        self.env.push(EnvChange::Set(key.into(), value.into()));
+        self
+    }
+
+    /// Remove an inherited environment variable (builder pattern)
+    pub fn with_env_remove(mut self, key: impl Into<OsString>) -> Self {
+        self.env.push(EnvChange::Remove(key.into()));
+        self
+    }
+
+    /// Start from an empty environment (builder pattern)
+    ///
+    /// Variables set with `with_env` and `TERM` are still passed.
+    pub fn with_env_clear(mut self) -> Self {
+        self.env_clear = true;
+        self.env.clear();
+        self
+    }
+
+    /// Set the `TERM` value (builder pattern)
+    pub fn with_term(mut self, term: impl Into<String>) -> Self {
+        self.term = term.into();
+        self
+    }
+
+    /// Start the program as a login shell by passing `-l` (builder pattern)
+    pub fn with_login_shell(mut self, login_shell: bool) -> Self {
+        self.login_shell = login_shell;
+        self
+    }
+
+    /// Set the initial screen size (builder pattern)
+    pub fn with_size(mut self, rows: u16, cols: u16) -> Self {
+        self.rows = rows.max(1);
+        self.cols = cols.max(1);
+        self
+    }
+
+    /// Set the number of scrollback lines kept (builder pattern)
+    pub fn with_scrollback(mut self, scrollback: usize) -> Self {
+        self.scrollback = scrollback;
+        self
+    }
+
+    /// Restore history from an ANSI export before the process starts (builder pattern)
+    ///
+    /// See `ExportFormat::Ansi` and `TermTui::dump_scrollback`.
+    pub fn with_preload(mut self, ansi: impl Into<String>) -> Self {
+        self.preload = Some(ansi.into());
+        self
+    }
+
+    /// Get the initial screen size as `(rows, cols)`
+    pub fn size(&self) -> (u16, u16) {
+        (self.rows, self.cols)
+    }
+
+    /// Get the number of scrollback lines kept
+    pub fn scrollback(&self) -> usize {
+        self.scrollback
+    }
+
+    /// Get the ANSI export loaded before the process starts
+    pub fn preload(&self) -> Option<&str> {
+        self.preload.as_deref()
+    }
+
+    /// Build the command to spawn
+    ///
+    /// Fails only when no working directory was set and the current
+    /// directory cannot be read.
+    pub fn to_command(&self) -> Result<CommandBuilder> {
+        let mut cmd = CommandBuilder::new("");
+        if self.env_clear {
+            cmd.env_clear();
+        }
+        cmd.env("TERM", &self.term);
+        for change in &self.env {
+            match change {
+                EnvChange::Set(key, value) => cmd.env(key, value),
+                EnvChange::Remove(key) => cmd.env_remove(key),
+            }
+        }
+
+        // Resolved after the environment so an overridden $SHELL is honoured
+        let program = match &self.command {
+            Some(command) => command.clone(),
+            None => cmd.get_shell().into(),
+        };
+        let argv = cmd.get_argv_mut();
+        argv.clear();
+        argv.push(program);
+        if self.login_shell {
+            argv.push("-l".into());
+        }
+        argv.extend(self.args.iter().cloned());
+
+        let cwd = match &self.cwd {
+            Some(cwd) => cwd.clone(),
+            None => std::env::current_dir()?,
+        };
+        cmd.cwd(cwd);
+
+        Ok(cmd)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ffi::OsStr;
+
+    #[test]
+    fn test_defaults() {
+        let options = TermTuiSpawnOptions::new();
+        assert_eq!(options.size(), (24, 80));
+        assert_eq!(options.scrollback(), 10000);
+        assert_eq!(options.preload(), None);
+
+        let cmd = options.to_command().unwrap();
+        assert_eq!(cmd.get_env("TERM"), Some(OsStr::new("xterm-256color")));
+        assert_eq!(cmd.get_argv().len(), 1);
+        assert_eq!(
+            cmd.get_cwd().map(PathBuf::from),
+            Some(std::env::current_dir().unwrap())
+        );
+    }
+
+    #[test]
+    fn test_command_args_and_login_shell() {
+        let cmd = TermTuiSpawnOptions::new()
+            .with_command("bash")
+            .with_login_shell(true)
+            .with_arg("-c")
+            .with_args(["echo hi"])
+            .to_command()
+            .unwrap();
+        assert_eq!(cmd.get_argv(), &["bash", "-l", "-c", "echo hi"]);
+    }
+
+    #[test]
+    fn test_default_shell_follows_env() {
+        let cmd = TermTuiSpawnOptions::new()
+            .with_env("SHELL", "/bin/sh")
+            .to_command()
+            .unwrap();
+        assert_eq!(cmd.get_argv(), &["/bin/sh"]);
+    }
+
+    #[test]
+    fn test_env_and_cwd() {
+        let cmd = TermTuiSpawnOptions::new()
+            .with_env("FROM_OPTIONS", "1")
+            .with_env_remove("PATH")
+            .with_term("xterm")
+            .with_cwd("/tmp")
+            .to_command()
+            .unwrap();
+        assert_eq!(cmd.get_env("FROM_OPTIONS"), Some(OsStr::new("1")));
+        assert_eq!(cmd.get_env("PATH"), None);
+        assert_eq!(cmd.get_env("TERM"), Some(OsStr::new("xterm")));
+        assert_eq!(cmd.get_cwd(), Some(&OsString::from("/tmp")));
+
+        let cmd = TermTuiSpawnOptions::new()
+            .with_env("DROPPED", "1")
+            .with_env_clear()
+            .with_env("KEPT", "1")
+            .to_command()
+            .unwrap();
+        assert_eq!(cmd.get_env("DROPPED"), None);
+        assert_eq!(cmd.get_env("HOME"), None);
+        assert_eq!(cmd.get_env("KEPT"), Some(OsStr::new("1")));
+    }
+}
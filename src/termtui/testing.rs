@@ -0,0 +1,375 @@
+//! Headless snapshot testing for programs running in a terminal
+//!
+//! `TermTuiHarness` feeds program output (and user input) through a
+//! `Parser`, renders `TermTuiWidget` into a ratatui `TestBackend` and
+//! compares the result against a snapshot file:
+//!
+//! ```no_run
+//! use ratatui_toolkit::termtui::testing::TermTuiHarness;
+//!
+//! let mut harness = TermTuiHarness::new(5, 20).with_styles(true);
+//! harness.feed("hello \x1b[1;31mworld\x1b[0m");
+//! harness.assert_snapshot(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/snapshots/hello.snap"));
+//! ```
+//!
+//! Run the tests with `TERMTUI_UPDATE_SNAPSHOTS=1` to write new or changed
+//! snapshots instead of failing.
+
+use crate::termtui::key_encoder::{encode_key, encode_paste};
+use crate::termtui::parser::Parser;
+use crate::termtui::screen::Screen;
+use crate::termtui::widget::TermTuiWidget;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::backend::TestBackend;
+use ratatui::buffer::{Buffer, Cell as BufferCell};
+use ratatui::style::Color;
+use ratatui::Terminal;
+use std::path::Path;
+use unicode_width::UnicodeWidthStr;
+
+/// Environment variable that makes snapshot assertions write snapshots
+pub const UPDATE_SNAPSHOTS_ENV: &str = "TERMTUI_UPDATE_SNAPSHOTS";
+
+/// Line separating the screen text from the style annotations
+const STYLES_HEADER: &str = "--- styles ---";
+
+/// One step of a scripted session
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ScriptStep {
+    /// Output written by the program
+    Output(Vec<u8>),
+    /// Key pressed by the user, encoded with the modes the program set
+    Key(KeyEvent),
+    /// Text pasted by the user (bracketed if the program enabled it)
+    Paste(String),
+    /// Resize the screen
+    Resize { rows: u16, cols: u16 },
+    /// Scroll back this many lines into history (0 = latest output)
+    Scrollback(usize),
+}
+
+impl ScriptStep {
+    /// Create an output step
+    pub fn output(bytes: impl AsRef<[u8]>) -> Self {
+        ScriptStep::Output(bytes.as_ref().to_vec())
+    }
+
+    /// Create a key step without modifiers
+    pub fn key(code: KeyCode) -> Self {
+        ScriptStep::Key(KeyEvent::new(code, KeyModifiers::NONE))
+    }
+}
+
+/// A terminal screen without a process, for snapshot tests
+pub struct TermTuiHarness {
+    parser: Parser,
+    /// Bytes the program would have read from user input
+    input: Vec<u8>,
+    show_cursor: bool,
+    styles: bool,
+}
+
+impl TermTuiHarness {
+    /// Create a harness with a `rows` x `cols` screen
+    pub fn new(rows: u16, cols: u16) -> Self {
+        Self {
+            parser: Parser::new(rows.max(1) as usize, cols.max(1) as usize, 1000),
+            input: Vec::new(),
+            show_cursor: true,
+            styles: false,
+        }
+    }
+
+    /// Set whether the cursor is drawn in snapshots (builder pattern)
+    pub fn with_cursor(mut self, show: bool) -> Self {
+        self.show_cursor = show;
+        self
+    }
+
+    /// Set whether snapshots include style annotations (builder pattern)
+    pub fn with_styles(mut self, styles: bool) -> Self {
+        self.styles = styles;
+        self
+    }
+
+    /// Feed program output to the parser
+    pub fn feed(&mut self, bytes: impl AsRef<[u8]>) -> &mut Self {
+        self.parser.process(bytes.as_ref());
+        self
+    }
+
+    /// Run a script of steps in order
+    pub fn run(&mut self, steps: impl IntoIterator<Item = ScriptStep>) -> &mut Self {
+        for step in steps {
+            self.step(step);
+        }
+        self
+    }
+
+    /// Run a single step
+    pub fn step(&mut self, step: ScriptStep) -> &mut Self {
+        match step {
+            ScriptStep::Output(bytes) => self.parser.process(&bytes),
+            ScriptStep::Key(key) => {
+                let modes = self.parser.screen().keyboard_modes();
+                self.input
+                    .extend_from_slice(encode_key(&key, modes).as_bytes());
+            }
+            ScriptStep::Paste(text) => {
+                let bracketed = self.parser.screen().bracketed_paste();
+                self.input
+                    .extend_from_slice(encode_paste(&text, bracketed).as_bytes());
+            }
+            ScriptStep::Resize { rows, cols } => {
+                self.parser
+                    .resize(rows.max(1) as usize, cols.max(1) as usize);
+            }
+            ScriptStep::Scrollback(offset) => self.parser.set_scrollback(offset),
+        }
+        // Answers to terminal queries are input too
+        let replies = self.parser.take_replies();
+        self.input.extend_from_slice(&replies);
+        self
+    }
+
+    /// Take the bytes the program would have read since the last call
+    ///
+    /// This includes encoded keys and pastes, and replies to queries.
+    pub fn take_input(&mut self) -> Vec<u8> {
+        self.input.extend_from_slice(&self.parser.take_replies());
+        std::mem::take(&mut self.input)
+    }
+
+    /// Get the parser
+    pub fn parser(&self) -> &Parser {
+        &self.parser
+    }
+
+    /// Get the parser mutably
+    pub fn parser_mut(&mut self) -> &mut Parser {
+        &mut self.parser
+    }
+
+    /// Render the screen into a buffer the size of the screen
+    pub fn render(&self) -> Buffer {
+        render_screen(self.parser.screen(), self.show_cursor)
+    }
+
+    /// Get the snapshot text of the rendered screen
+    pub fn snapshot(&self) -> String {
+        buffer_snapshot(&self.render(), self.styles)
+    }
+
+    /// Compare the rendered screen against the snapshot file at `path`
+    ///
+    /// # Panics
+    ///
+    /// Panics when the snapshot differs or is missing, unless
+    /// `TERMTUI_UPDATE_SNAPSHOTS` is set.
+    pub fn assert_snapshot(&self, path: impl AsRef<Path>) {
+        assert_snapshot(path, &self.snapshot());
+    }
+}
+
+/// Render `screen` with `TermTuiWidget` into a `TestBackend` of its size
+///
+/// Scrolled-back screens are rendered at their scrollback offset.
+pub fn render_screen(screen: &Screen, show_cursor: bool) -> Buffer {
+    let size = screen.size();
+    let backend = TestBackend::new(size.cols, size.rows);
+    let mut terminal = Terminal::new(backend).expect("TestBackend cannot fail");
+    terminal
+        .draw(|frame| {
+            let widget = TermTuiWidget::new(screen)
+                .scroll_offset(screen.scrollback())
+                .show_cursor(show_cursor);
+            frame.render_widget(widget, frame.area());
+        })
+        .expect("TestBackend cannot fail");
+    terminal.backend().buffer().clone()
+}
+
+/// Format a buffer as snapshot text
+///
+/// Each buffer row becomes a line with trailing spaces removed. With
+/// `styles`, a `--- styles ---` section follows, listing each run of
+/// styled cells as `row:start..end` and its colors and modifiers.
+pub fn buffer_snapshot(buffer: &Buffer, styles: bool) -> String {
+    let area = buffer.area;
+    let mut output = String::new();
+    for y in area.top()..area.bottom() {
+        let start = output.len();
+        let mut x = area.left();
+        while x < area.right() {
+            let symbol = buffer[(x, y)].symbol();
+            output.push_str(symbol);
+            // The cells covered by a wide character are skipped
+            x += symbol.width().max(1) as u16;
+        }
+        output.truncate(start + output[start..].trim_end().len());
+        output.push('\n');
+    }
+
+    if styles {
+        output.push_str(STYLES_HEADER);
+        output.push('\n');
+        for y in area.top()..area.bottom() {
+            let row: Vec<String> = (area.left()..area.right())
+                .map(|x| describe_style(&buffer[(x, y)]))
+                .collect();
+            let mut start = 0;
+            while start < row.len() {
+                let end = start
+                    + row[start..]
+                        .iter()
+                        .take_while(|s| **s == row[start])
+                        .count();
+                if !row[start].is_empty() {
+                    output.push_str(&format!(
+                        "{}:{}..{} {}\n",
+                        y - area.top(),
+                        start,
+                        end,
+                        row[start]
+                    ));
+                }
+                start = end;
+            }
+        }
+    }
+    output
+}
+
+/// Describe a cell's style, or return an empty string for the default style
+fn describe_style(cell: &BufferCell) -> String {
+    let mut parts = Vec::new();
+    if cell.fg != Color::Reset {
+        parts.push(format!("fg={:?}", cell.fg));
+    }
+    if cell.bg != Color::Reset {
+        parts.push(format!("bg={:?}", cell.bg));
+    }
+    if cell.underline_color != Color::Reset {
+        parts.push(format!("ul={:?}", cell.underline_color));
+    }
+    for (name, _) in cell.modifier.iter_names() {
+        parts.push(format!("+{name}"));
+    }
+    parts.join(" ")
+}
+
+/// Compare `actual` against the snapshot file at `path`
+///
+/// Relative paths are resolved from the current directory, which cargo sets
+/// to the package root when running tests.
+///
+/// # Panics
+///
+/// Panics when the snapshot differs or is missing, unless
+/// `TERMTUI_UPDATE_SNAPSHOTS` is set (to anything but `0`), in which case
+/// the snapshot is written instead.
+pub fn assert_snapshot(path: impl AsRef<Path>, actual: &str) {
+    if let Err(message) = check_snapshot(path.as_ref(), actual, update_snapshots()) {
+        panic!("{message}");
+    }
+}
+
+fn update_snapshots() -> bool {
+    std::env::var_os(UPDATE_SNAPSHOTS_ENV).is_some_and(|value| !value.is_empty() && value != "0")
+}
+
+/// Compare or write a snapshot, describing any mismatch
+fn check_snapshot(path: &Path, actual: &str, update: bool) -> Result<(), String> {
+    if update {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("cannot create {}: {e}", parent.display()))?;
+        }
+        return std::fs::write(path, actual)
+            .map_err(|e| format!("cannot write snapshot {}: {e}", path.display()));
+    }
+
+    let expected = match std::fs::read_to_string(path) {
+        Ok(expected) => expected.replace("\r\n", "\n"),
+        Err(_) => {
+            return Err(format!(
+            "snapshot {} is missing; run with {UPDATE_SNAPSHOTS_ENV}=1 to create it\n\n{actual}",
+            path.display()
+        ))
+        }
+    };
+    if expected == actual {
+        return Ok(());
+    }
+
+    let mut message = format!(
+        "snapshot {} does not match; run with {UPDATE_SNAPSHOTS_ENV}=1 to update it\n",
+        path.display()
+    );
+    let expected_lines: Vec<&str> = expected.lines().collect();
+    let actual_lines: Vec<&str> = actual.lines().collect();
+    for i in 0..expected_lines.len().max(actual_lines.len()) {
+        let (old, new) = (expected_lines.get(i), actual_lines.get(i));
+        if old != new {
+            message.push_str(&format!("line {}:\n", i + 1));
+            if let Some(old) = old {
+                message.push_str(&format!("- {old}\n"));
+            }
+            if let Some(new) = new {
+                message.push_str(&format!("+ {new}\n"));
+            }
+        }
+    }
+    Err(message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snapshot_text_and_styles() {
+        let mut harness = TermTuiHarness::new(3, 10).with_styles(true);
+        harness.feed("ab \x1b[1;31mred\x1b[0m\r\n中文x");
+        assert_eq!(
+            harness.snapshot(),
+            "ab red\n中文x\n\n\
+             --- styles ---\n\
+             0:3..6 fg=Red +BOLD\n\
+             1:5..6 +REVERSED\n"
+        );
+
+        let harness = TermTuiHarness::new(2, 4).with_cursor(false);
+        assert_eq!(harness.snapshot(), "\n\n");
+    }
+
+    #[test]
+    fn test_script_collects_input() {
+        let mut harness = TermTuiHarness::new(3, 10);
+        harness.run([
+            ScriptStep::output("\x1b[?1h\x1b[?2004h"),
+            ScriptStep::key(KeyCode::Up),
+            ScriptStep::Paste("hi".to_string()),
+            ScriptStep::output("\x1b[6n"),
+            ScriptStep::Resize { rows: 2, cols: 5 },
+        ]);
+        assert_eq!(harness.take_input(), b"\x1bOA\x1b[200~hi\x1b[201~\x1b[1;1R");
+        assert!(harness.take_input().is_empty());
+        assert_eq!(harness.parser().size().cols, 5);
+    }
+
+    #[test]
+    fn test_check_snapshot() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested").join("screen.snap");
+
+        let missing = check_snapshot(&path, "one\n", false).unwrap_err();
+        assert!(missing.contains("is missing"));
+
+        check_snapshot(&path, "one\ntwo\n", true).unwrap();
+        assert_eq!(check_snapshot(&path, "one\ntwo\n", false), Ok(()));
+
+        let mismatch = check_snapshot(&path, "one\nthree\n", false).unwrap_err();
+        assert!(mismatch.ends_with("line 2:\n- two\n+ three\n"));
+    }
+}
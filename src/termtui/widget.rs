@@ -1,8 +1,10 @@
 //! Ratatui widget for rendering the terminal
 
-use crate::termtui::copy_mode::{CopyMode, CopyPos};
+use crate::termtui::copy_mode::CopyMode;
+use crate::termtui::cursor::CursorShape;
 use crate::termtui::screen::Screen;
-use ratatui::buffer::Buffer;
+use crate::termtui::search::SearchMatch;
+use ratatui::buffer::{Buffer, Cell as BufferCell};
 use ratatui::layout::Rect;
 use ratatui::style::{Color, Modifier, Style};
 use ratatui::widgets::Widget;
@@ -15,6 +17,14 @@ pub struct TermTuiWidget<'a> {
     scroll_offset: usize,
     /// Copy mode state (for rendering selection)
     copy_mode: Option<&'a CopyMode>,
+    /// Whether the pane has focus (an unfocused cursor is dimmed)
+    focused: bool,
+    /// Whether to draw the terminal cursor
+    show_cursor: bool,
+    /// Whether a blinking cursor is in its visible phase
+    cursor_blink_on: bool,
+    /// Whether a visual bell is flashing
+    visual_bell: bool,
 }
 
 impl<'a> TermTuiWidget<'a> {
@@ -24,6 +34,10 @@ impl<'a> TermTuiWidget<'a> {
             screen,
             scroll_offset: 0,
             copy_mode: None,
+            focused: true,
+            show_cursor: true,
+            cursor_blink_on: true,
+            visual_bell: false,
         }
     }
 
@@ -38,6 +52,32 @@ impl<'a> TermTuiWidget<'a> {
         self.copy_mode = Some(mode);
         self
     }
+
+    /// Set whether the pane has focus (default: true)
+    pub fn focused(mut self, focused: bool) -> Self {
+        self.focused = focused;
+        self
+    }
+
+    /// Set whether to draw the terminal cursor (default: true)
+    ///
+    /// Turn this off when the host places the real cursor instead.
+    pub fn show_cursor(mut self, show: bool) -> Self {
+        self.show_cursor = show;
+        self
+    }
+
+    /// Set the blink phase; a blinking cursor is hidden while `false`
+    pub fn cursor_blink_on(mut self, on: bool) -> Self {
+        self.cursor_blink_on = on;
+        self
+    }
+
+    /// Flash a visual bell by inverting the whole area (default: false)
+    pub fn visual_bell(mut self, flash: bool) -> Self {
+        self.visual_bell = flash;
+        self
+    }
 }
 
 impl Widget for TermTuiWidget<'_> {
@@ -47,9 +87,26 @@ impl Widget for TermTuiWidget<'_> {
         let screen_cols = size.cols as usize;
 
         // Get selection bounds if in copy mode
-        let selection = self.copy_mode.and_then(|m| m.get_selection());
+        let selection = self.copy_mode.and_then(|m| m.selection());
         let copy_cursor = self.copy_mode.and_then(|m| m.cursor());
 
+        // Search matches that intersect the visible rows
+        let top = -(self.scroll_offset as i32);
+        let bottom = top + area.height as i32 - 1;
+        let search = self.copy_mode.and_then(|m| m.search());
+        let current_match = search
+            .and_then(|s| s.current())
+            .and_then(|idx| search.map(|s| s.matches()[idx]));
+        let visible_matches: Vec<SearchMatch> = search
+            .map(|s| {
+                s.matches()
+                    .iter()
+                    .filter(|m| m.end.y >= top && m.start.y <= bottom)
+                    .copied()
+                    .collect()
+            })
+            .unwrap_or_default();
+
         // Render each visible row
         for (row_idx, row) in self.screen.visible_rows().enumerate() {
             if row_idx >= area.height as usize {
@@ -72,25 +129,28 @@ impl Widget for TermTuiWidget<'_> {
                 }
 
                 // Get cell style
-                let mut style = cell.attrs().to_ratatui();
+                let mut style = cell.style();
+
+                // Highlight search matches (the current match stands out)
+                let cell_y = row_idx as i32 - self.scroll_offset as i32;
+                let cell_x = col_idx as i32;
+                if current_match.is_some_and(|m| m.contains(cell_x, cell_y)) {
+                    style = Style::default()
+                        .bg(Color::Rgb(255, 140, 0))
+                        .fg(Color::Black);
+                } else if visible_matches.iter().any(|m| m.contains(cell_x, cell_y)) {
+                    style = Style::default().bg(Color::Yellow).fg(Color::Black);
+                }
 
                 // Check if this cell is in selection
-                if let Some((start, end)) = &selection {
-                    let cell_y = row_idx as i32 - self.scroll_offset as i32;
-                    let cell_x = col_idx as i32;
-
-                    if is_in_selection(cell_x, cell_y, start, end) {
-                        style = Style::default()
-                            .bg(Color::Rgb(70, 130, 180))
-                            .fg(Color::White);
-                    }
+                if selection.is_some_and(|s| s.contains(cell_x, cell_y)) {
+                    style = Style::default()
+                        .bg(Color::Rgb(70, 130, 180))
+                        .fg(Color::White);
                 }
 
-                // Get the character to render
-                let ch = cell.text().chars().next().unwrap_or(' ');
-
                 if let Some(buf_cell) = buf.cell_mut((x, y)) {
-                    buf_cell.set_char(ch).set_style(style);
+                    buf_cell.set_symbol(cell.text()).set_style(style);
                 }
             }
         }
@@ -115,99 +175,167 @@ impl Widget for TermTuiWidget<'_> {
                     cell.set_style(cursor_style);
                 }
             }
-        } else if self.screen.cursor_visible() && self.scroll_offset == 0 {
+        } else if self.show_cursor && self.screen.cursor_visible() && self.scroll_offset == 0 {
             // Normal cursor (only when not scrolled back)
-            let cursor_row = cursor_pos.row;
-            let cursor_col = cursor_pos.col;
-
-            if cursor_row < area.height && cursor_col < area.width {
-                let x = area.x + cursor_col;
-                let y = area.y + cursor_row;
+            let blinking = self.focused && self.screen.cursor_blinking();
+            if (cursor_pos.row < area.height && cursor_pos.col < area.width)
+                && (self.cursor_blink_on || !blinking)
+            {
+                let x = area.x + cursor_pos.col;
+                let y = area.y + cursor_pos.row;
 
                 if let Some(cell) = buf.cell_mut((x, y)) {
-                    let cursor_style = Style::default()
-                        .bg(Color::White)
-                        .fg(Color::Black)
-                        .add_modifier(Modifier::REVERSED);
+                    draw_cursor(cell, self.screen.cursor_shape(), self.focused);
+                }
+            }
+        }
 
-                    // Use block cursor if cell is empty
-                    if cell.symbol() == " " {
-                        cell.set_char('█');
+        if self.visual_bell {
+            for y in area.top()..area.bottom() {
+                for x in area.left()..area.right() {
+                    if let Some(cell) = buf.cell_mut((x, y)) {
+                        cell.modifier.toggle(Modifier::REVERSED);
                     }
-                    cell.set_style(cursor_style);
                 }
             }
         }
     }
 }
 
-/// Check if a cell position is within the selection range
-fn is_in_selection(x: i32, y: i32, start: &CopyPos, end: &CopyPos) -> bool {
-    let (low, high) = CopyPos::to_low_high(start, end);
-
-    if y < low.y || y > high.y {
-        return false;
+/// Draw the terminal cursor over a buffer cell
+///
+/// An unfocused pane always shows a dim block.
+fn draw_cursor(cell: &mut BufferCell, shape: CursorShape, focused: bool) {
+    if !focused {
+        cell.set_bg(Color::DarkGray);
+        return;
     }
 
-    if y == low.y && y == high.y {
-        // Single line selection
-        x >= low.x && x <= high.x
-    } else if y == low.y {
-        // First line of multi-line selection
-        x >= low.x
-    } else if y == high.y {
-        // Last line of multi-line selection
-        x <= high.x
-    } else {
-        // Middle lines - entire line selected
-        true
+    let blank = cell.symbol() == " ";
+    match shape {
+        CursorShape::Underline => {
+            cell.modifier.insert(Modifier::UNDERLINED);
+        }
+        // A bar is only drawn in empty cells so text under it stays readable
+        CursorShape::Bar if blank => {
+            cell.set_symbol("▏");
+        }
+        CursorShape::Block | CursorShape::Bar => {
+            cell.modifier.insert(Modifier::REVERSED);
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::termtui::copy_mode::CopyPos;
 
-    #[test]
-    fn test_is_in_selection_single_line() {
-        let start = CopyPos::new(5, 10);
-        let end = CopyPos::new(15, 10);
+    fn render_cursor(bytes: &[u8], widget: impl Fn(TermTuiWidget) -> TermTuiWidget) -> Buffer {
+        use crate::termtui::parser::Parser;
 
-        assert!(is_in_selection(5, 10, &start, &end));
-        assert!(is_in_selection(10, 10, &start, &end));
-        assert!(is_in_selection(15, 10, &start, &end));
-        assert!(!is_in_selection(4, 10, &start, &end));
-        assert!(!is_in_selection(16, 10, &start, &end));
-        assert!(!is_in_selection(10, 9, &start, &end));
+        let mut parser = Parser::new(2, 6, 0);
+        parser.process(bytes);
+        let area = Rect::new(0, 0, 6, 2);
+        let mut buf = Buffer::empty(area);
+        widget(TermTuiWidget::new(parser.screen())).render(area, &mut buf);
+        buf
     }
 
     #[test]
-    fn test_is_in_selection_multi_line() {
-        let start = CopyPos::new(5, 10);
-        let end = CopyPos::new(15, 12);
+    fn test_cursor_shapes() {
+        let buf = render_cursor(b"ab", |w| w);
+        assert!(buf[(2, 0)].modifier.contains(Modifier::REVERSED));
+        assert!(!buf[(1, 0)].modifier.contains(Modifier::REVERSED));
+
+        let buf = render_cursor(b"ab\x1b[4 q", |w| w);
+        assert!(buf[(2, 0)].modifier.contains(Modifier::UNDERLINED));
+        assert!(!buf[(2, 0)].modifier.contains(Modifier::REVERSED));
+
+        let buf = render_cursor(b"ab\x1b[6 q", |w| w);
+        assert_eq!(buf[(2, 0)].symbol(), "▏");
+
+        // A bar over text falls back to a block
+        let buf = render_cursor(b"ab\x1b[6 q\x1b[D", |w| w);
+        assert_eq!(buf[(1, 0)].symbol(), "b");
+        assert!(buf[(1, 0)].modifier.contains(Modifier::REVERSED));
+    }
 
-        // First line
-        assert!(is_in_selection(5, 10, &start, &end));
-        assert!(is_in_selection(50, 10, &start, &end)); // All of first line from start
-        assert!(!is_in_selection(4, 10, &start, &end));
+    #[test]
+    fn test_cursor_focus_blink_and_visibility() {
+        let buf = render_cursor(b"ab", |w| w.focused(false));
+        assert_eq!(buf[(2, 0)].bg, Color::DarkGray);
+        assert!(!buf[(2, 0)].modifier.contains(Modifier::REVERSED));
+
+        // A blinking cursor is hidden in the off phase, a steady one is not
+        let buf = render_cursor(b"ab\x1b[1 q", |w| w.cursor_blink_on(false));
+        assert!(!buf[(2, 0)].modifier.contains(Modifier::REVERSED));
+        let buf = render_cursor(b"ab\x1b[2 q", |w| w.cursor_blink_on(false));
+        assert!(buf[(2, 0)].modifier.contains(Modifier::REVERSED));
+
+        let buf = render_cursor(b"ab", |w| w.show_cursor(false));
+        assert!(!buf[(2, 0)].modifier.contains(Modifier::REVERSED));
+        let buf = render_cursor(b"ab\x1b[?25l", |w| w);
+        assert!(!buf[(2, 0)].modifier.contains(Modifier::REVERSED));
+    }
 
-        // Middle line
-        assert!(is_in_selection(0, 11, &start, &end));
-        assert!(is_in_selection(50, 11, &start, &end));
+    #[test]
+    fn test_search_matches_highlighted() {
+        use crate::termtui::parser::Parser;
+        use crate::termtui::search::SearchDirection;
+
+        let mut parser = Parser::new(3, 10, 0);
+        parser.process(b"foo bar\r\nbar foo");
+        let mut mode = CopyMode::enter(parser.screen().clone(), CopyPos::new(0, 0));
+        mode.start_search(SearchDirection::Forward);
+        for c in "foo".chars() {
+            mode.search_push(c);
+        }
 
-        // Last line
-        assert!(is_in_selection(0, 12, &start, &end));
-        assert!(is_in_selection(15, 12, &start, &end));
-        assert!(!is_in_selection(16, 12, &start, &end));
+        let area = Rect::new(0, 0, 10, 3);
+        let mut buf = Buffer::empty(area);
+        TermTuiWidget::new(mode.frozen_screen().unwrap())
+            .copy_mode(&mode)
+            .render(area, &mut buf);
+
+        // The match at the cursor is the current one
+        assert_eq!(buf[(1, 0)].bg, Color::Rgb(255, 140, 0));
+        assert_eq!(buf[(4, 1)].bg, Color::Yellow);
+        assert_eq!(buf[(6, 1)].bg, Color::Yellow);
+        assert_ne!(buf[(3, 1)].bg, Color::Yellow);
     }
 
     #[test]
-    fn test_is_in_selection_reversed() {
-        // Selection from bottom-right to top-left
-        let start = CopyPos::new(15, 12);
-        let end = CopyPos::new(5, 10);
+    fn test_block_selection_highlighted() {
+        use crate::termtui::parser::Parser;
+        use crate::termtui::selection::SelectionMode;
+
+        let mut parser = Parser::new(3, 10, 0);
+        parser.process(b"abcdef\r\nghijkl");
+        let mut mode = CopyMode::enter(parser.screen().clone(), CopyPos::new(1, 0));
+        mode.start_selection(SelectionMode::Block);
+        mode.move_cursor(2, 1);
+
+        let area = Rect::new(0, 0, 10, 3);
+        let mut buf = Buffer::empty(area);
+        TermTuiWidget::new(mode.frozen_screen().unwrap())
+            .copy_mode(&mode)
+            .render(area, &mut buf);
+
+        let selected = Color::Rgb(70, 130, 180);
+        assert_eq!(buf[(1, 0)].bg, selected);
+        assert_eq!(buf[(2, 1)].bg, selected);
+        // Unlike a stream selection, columns outside the block are not selected
+        assert_ne!(buf[(5, 0)].bg, selected);
+        assert_ne!(buf[(0, 1)].bg, selected);
+    }
 
-        // Should work the same due to to_low_high normalization
-        assert!(is_in_selection(10, 11, &start, &end));
+    #[test]
+    fn test_visual_bell_inverts_area() {
+        let buf = render_cursor(b"ab", |w| w.visual_bell(true));
+        assert!(buf[(0, 0)].modifier.contains(Modifier::REVERSED));
+        assert!(buf[(5, 1)].modifier.contains(Modifier::REVERSED));
+        // The block cursor flips back, so it still stands out
+        assert!(!buf[(2, 0)].modifier.contains(Modifier::REVERSED));
     }
 }
@@ -2,7 +2,13 @@
 
 #![cfg(feature = "terminal")]
 
-use ratatui_toolkit::termtui::TermTui;
+use ratatui_toolkit::termtui::{
+    AsciicastEventKind, AsciicastRecording, ChildBackend, ExportFormat, ProcessStatus,
+    StreamBackend, TermTui, TermTuiEvent, TermTuiSpawnOptions,
+};
+use std::io::{Cursor, Write};
+use std::process::{Command, Stdio};
+use std::sync::{Arc, Mutex};
 
 #[tokio::test]
 async fn test_spawn_echo() {
@@ -48,3 +54,386 @@ async fn test_copy_mode_on_spawned_terminal() {
     let cursor = term.copy_mode.cursor();
     assert!(cursor.is_some());
 }
+
+#[tokio::test]
+async fn test_resize_propagates_to_child_pty() {
+    let mut term =
+        TermTui::spawn_with_command("Resize", "sh", &["-c", "read _line; stty size"]).unwrap();
+
+    // Wait for the shell to block on `read`
+    tokio::time::sleep(tokio::time::Duration::from_millis(200)).await;
+
+    term.resize(30, 100);
+    term.send_input("\n");
+
+    wait_for_contents(&term, "30 100").await;
+}
+
+#[tokio::test]
+async fn test_paste_uses_bracketed_paste_when_enabled() {
+    let script = "printf '\\033[?2004h'; stty -icanon -echo min 1; \
+                  dd bs=1 count=14 2>/dev/null | od -An -tx1";
+    let term = TermTui::spawn_with_command("Paste", "sh", &["-c", script]).unwrap();
+
+    let parser = term.parser();
+    wait_until("bracketed paste", || {
+        parser.lock().unwrap().screen().bracketed_paste()
+    })
+    .await;
+    // Give `stty` a moment to switch off canonical mode
+    tokio::time::sleep(tokio::time::Duration::from_millis(200)).await;
+
+    term.paste("ab");
+
+    wait_for_contents(&term, "1b 5b 32 30 30 7e 61 62 1b 5b 32 30 31 7e").await;
+}
+
+/// Poll until the child process is no longer running
+async fn wait_for_exit(term: &mut TermTui) -> Option<ProcessStatus> {
+    for _ in 0..100 {
+        match term.status() {
+            Some(ProcessStatus::Running) => {
+                tokio::time::sleep(tokio::time::Duration::from_millis(20)).await
+            }
+            status => return status,
+        }
+    }
+    term.status()
+}
+
+/// Wait until the screen shows `needle`, failing the test on timeout
+async fn wait_for_contents(term: &TermTui, needle: &str) -> String {
+    let parser = term.parser();
+    let mut contents = String::new();
+    for _ in 0..100 {
+        contents = parser.lock().unwrap().screen().contents();
+        if contents.contains(needle) {
+            return contents;
+        }
+        tokio::time::sleep(tokio::time::Duration::from_millis(20)).await;
+    }
+    panic!("timed out waiting for {needle:?}, got: {contents:?}");
+}
+
+/// Wait until `condition` holds, failing the test on timeout
+async fn wait_until(what: &str, mut condition: impl FnMut() -> bool) {
+    for _ in 0..100 {
+        if condition() {
+            return;
+        }
+        tokio::time::sleep(tokio::time::Duration::from_millis(20)).await;
+    }
+    panic!("timed out waiting for {what}");
+}
+
+#[tokio::test]
+async fn test_exit_status_and_event() {
+    let mut term = TermTui::spawn_with_command("Exit", "sh", &["-c", "exit 3"]).unwrap();
+
+    assert_eq!(
+        wait_for_exit(&mut term).await,
+        Some(ProcessStatus::Exited(3))
+    );
+    assert_eq!(
+        term.take_events(),
+        vec![TermTuiEvent::ProcessExited {
+            status: ProcessStatus::Exited(3)
+        }]
+    );
+    // The exit is reported once
+    assert!(term.take_events().is_empty());
+}
+
+#[tokio::test]
+async fn test_status_without_process() {
+    let mut term = TermTui::new("Empty");
+    assert_eq!(term.status(), None);
+    assert!(term.restart().is_err());
+}
+
+#[tokio::test]
+async fn test_kill_running_process() {
+    let mut term = TermTui::spawn_with_command("Sleep", "sleep", &["10"]).unwrap();
+    assert_eq!(term.status(), Some(ProcessStatus::Running));
+
+    term.kill().unwrap();
+    assert!(matches!(term.status(), Some(ProcessStatus::Signaled(_))));
+    // Killing an exited process is a no-op
+    term.kill().unwrap();
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_send_signal() {
+    let mut term = TermTui::spawn_with_command("Sleep", "sleep", &["10"]).unwrap();
+    term.send_signal(libc::SIGTERM).unwrap();
+
+    let status = wait_for_exit(&mut term).await;
+    assert!(
+        matches!(status, Some(ProcessStatus::Signaled(_))),
+        "expected a signal, got {status:?}"
+    );
+    assert!(term.send_signal(libc::SIGTERM).is_err());
+}
+
+#[tokio::test]
+async fn test_restart_reuses_command() {
+    let mut term =
+        TermTui::spawn_with_command("Restart", "sh", &["-c", "echo started; exit 3"]).unwrap();
+    assert_eq!(
+        wait_for_exit(&mut term).await,
+        Some(ProcessStatus::Exited(3))
+    );
+    term.take_events();
+
+    term.restart().unwrap();
+    assert_eq!(
+        wait_for_exit(&mut term).await,
+        Some(ProcessStatus::Exited(3))
+    );
+    assert_eq!(term.take_events().len(), 1);
+
+    wait_for_contents(&term, "started").await;
+}
+
+#[tokio::test]
+async fn test_record_and_play_back_session() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("session.cast");
+
+    let mut term = TermTui::spawn_with_command(
+        "Record",
+        "sh",
+        &["-c", "sleep 0.3; printf 'recorded \\033[32mgreen\\033[0m'"],
+    )
+    .unwrap();
+    term.start_recording(&path).unwrap();
+    assert!(term.is_recording());
+    term.resize(30, 100);
+
+    wait_for_exit(&mut term).await;
+    wait_for_contents(&term, "green").await;
+    term.stop_recording().unwrap();
+    assert!(!term.is_recording());
+
+    let cast = std::fs::read_to_string(&path).unwrap();
+    assert!(cast
+        .lines()
+        .next()
+        .unwrap()
+        .contains(r#""env":{"TERM":"xterm-256color"}"#));
+    let recording = AsciicastRecording::load(&path).unwrap();
+    assert_eq!((recording.width, recording.height), (80, 24));
+    assert_eq!(recording.events[0].kind, AsciicastEventKind::Resize);
+    assert_eq!(recording.events[0].resize_size(), Some((100, 30)));
+    let output: String = recording
+        .events
+        .iter()
+        .filter(|event| event.kind == AsciicastEventKind::Output)
+        .map(|event| event.data.as_str())
+        .collect();
+    assert!(output.contains("recorded \x1b[32mgreen\x1b[0m"));
+
+    let mut replay = TermTui::from_asciicast("Replay", &path).unwrap();
+    let duration = replay.player().unwrap().duration();
+    replay.seek_playback(duration);
+    let parser = replay.parser();
+    let parser = parser.lock().unwrap();
+    assert_eq!(parser.size().cols, 100);
+    assert!(parser.screen().contents().contains("recorded green"));
+}
+
+#[test]
+fn test_demo_recording_plays_back() {
+    let recording = AsciicastRecording::load("demo/ratatui-toolkit-demo.cast").unwrap();
+    assert!(!recording.events.is_empty());
+
+    let mut replay = TermTui::from_recording("Demo", recording);
+    let duration = replay.player().unwrap().duration();
+    replay.seek_playback(duration);
+    assert!(replay.player().unwrap().is_finished());
+}
+
+/// Writer whose output stays readable after the terminal takes it
+#[derive(Clone, Default)]
+struct SharedBuf(Arc<Mutex<Vec<u8>>>);
+
+impl Write for SharedBuf {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn test_stream_backend_in_memory() {
+    let input = SharedBuf::default();
+    let output = Cursor::new(b"\x1b[1mbold\x1b[0m text\r\n\x1b[6n".to_vec());
+    let mut term =
+        TermTui::from_backend("Stream", StreamBackend::new(output, input.clone())).unwrap();
+
+    assert_eq!(
+        wait_for_exit(&mut term).await,
+        Some(ProcessStatus::Exited(0))
+    );
+    let contents = term.parser().lock().unwrap().screen().contents();
+    assert!(contents.starts_with("bold text"));
+
+    // Query replies and input both go to the writer
+    term.send_input("ls\r");
+    let written = input.0.lock().unwrap().clone();
+    assert_eq!(written, b"\x1b[2;1Rls\r");
+
+    // There is no command to restart
+    assert!(term.restart().is_err());
+}
+
+#[tokio::test]
+async fn test_child_backend_with_piped_stdio() {
+    let child = Command::new("sh")
+        .args(["-c", "read line; echo \"got $line\"; echo oops >&2; exit 4"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let mut term = TermTui::from_backend("Child", ChildBackend::new(child)).unwrap();
+    assert_eq!(term.status(), Some(ProcessStatus::Running));
+
+    term.send_input("hello\n");
+    assert_eq!(
+        wait_for_exit(&mut term).await,
+        Some(ProcessStatus::Exited(4))
+    );
+
+    let contents = wait_for_contents(&term, "oops").await;
+    assert!(contents.contains("got hello"), "got: {contents:?}");
+}
+
+#[tokio::test]
+async fn test_child_backend_kill() {
+    let child = Command::new("sleep")
+        .arg("30")
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let mut term = TermTui::from_backend("Sleep", ChildBackend::new(child)).unwrap();
+    term.kill().unwrap();
+    assert_eq!(
+        term.status(),
+        Some(ProcessStatus::Signaled("Killed".to_string()))
+    );
+}
+
+#[tokio::test]
+async fn test_spawn_with_options() {
+    let dir = tempfile::tempdir().unwrap();
+    let options = TermTuiSpawnOptions::new()
+        .with_command("sh")
+        .with_args(["-c", "echo \"$GREETING $TERM $HOME\"; pwd; stty size"])
+        .with_cwd(dir.path())
+        .with_env("GREETING", "hi")
+        .with_env_remove("HOME")
+        .with_term("xterm")
+        .with_size(10, 50)
+        .with_scrollback(5);
+    let mut term = TermTui::spawn_with_options("Options", options).unwrap();
+    assert_eq!(
+        wait_for_exit(&mut term).await,
+        Some(ProcessStatus::Exited(0))
+    );
+
+    let contents = wait_for_contents(&term, "10 50").await;
+    let lines: Vec<&str> = contents.lines().collect();
+    assert_eq!(lines[0], "hi xterm");
+    assert_eq!(
+        std::path::Path::new(lines[1]).canonicalize().unwrap(),
+        dir.path().canonicalize().unwrap()
+    );
+    assert_eq!(lines[2], "10 50");
+    let parser = term.parser();
+    assert_eq!(parser.lock().unwrap().size().cols, 50);
+
+    // The scrollback limit applies
+    parser.lock().unwrap().process("\r\n".repeat(30).as_bytes());
+    assert_eq!(parser.lock().unwrap().screen().scrollback_available(), 5);
+}
+
+#[tokio::test]
+async fn test_dump_and_preload_scrollback() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("history.ansi");
+
+    let mut first = TermTui::spawn_with_command(
+        "Build",
+        "sh",
+        &["-c", "printf 'line 1\\n\\033[31mline 2\\033[0m\\n'"],
+    )
+    .unwrap();
+    assert_eq!(
+        wait_for_exit(&mut first).await,
+        Some(ProcessStatus::Exited(0))
+    );
+    wait_for_contents(&first, "line 2").await;
+    first.dump_scrollback(&path, ExportFormat::Ansi).unwrap();
+    assert!(std::fs::read_to_string(&path)
+        .unwrap()
+        .contains("\x1b[0;31mline 2\x1b[0m"));
+
+    let options = TermTuiSpawnOptions::new()
+        .with_command("sh")
+        .with_args(["-c", "echo restored"])
+        .with_preload(std::fs::read_to_string(&path).unwrap());
+    let mut second = TermTui::spawn_with_options("Build", options).unwrap();
+    assert_eq!(
+        wait_for_exit(&mut second).await,
+        Some(ProcessStatus::Exited(0))
+    );
+    wait_for_contents(&second, "restored").await;
+    assert_eq!(
+        second.export_scrollback(ExportFormat::Text),
+        "line 1\nline 2\nrestored\n"
+    );
+}
+
+#[tokio::test]
+async fn test_activity_and_silence_monitoring() {
+    let output = Cursor::new(b"building...\r\n".to_vec());
+    let mut term = TermTui::from_backend("Job", StreamBackend::read_only(output))
+        .unwrap()
+        .with_monitor_activity(true)
+        .with_monitor_silence(Some(std::time::Duration::from_millis(200)));
+
+    let notifier = term.dirty_notifier();
+    tokio::time::timeout(std::time::Duration::from_secs(2), notifier.notified())
+        .await
+        .unwrap();
+    let alerts = term.alerts();
+    assert!(alerts.activity && !alerts.bell);
+
+    // Going quiet raises the silence alert and one event
+    let mut silence_events = 0;
+    wait_until("the silence alert", || {
+        silence_events += term
+            .take_events()
+            .iter()
+            .filter(|event| **event == TermTuiEvent::Silence)
+            .count();
+        term.alerts().silence
+    })
+    .await;
+    assert_eq!(term.alerts().badge().as_deref(), Some("#~"));
+    silence_events += term
+        .take_events()
+        .iter()
+        .filter(|event| **event == TermTuiEvent::Silence)
+        .count();
+    assert_eq!(silence_events, 1);
+
+    term.mark_viewed();
+    assert!(term.alerts().is_empty());
+}
@@ -0,0 +1,132 @@
+//! Property-style tests for reflowing wrapped lines on resize
+
+#![cfg(feature = "terminal")]
+
+use ratatui_toolkit::termtui::Parser;
+
+/// Small deterministic generator so failures are reproducible
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn range(&mut self, low: u64, high: u64) -> u64 {
+        low + self.next() % (high - low)
+    }
+}
+
+/// Generate lines of random text, some longer than the screen is wide
+fn random_lines(rng: &mut Rng, count: usize) -> Vec<String> {
+    const ALPHABET: [char; 8] = ['a', 'b', 'x', 'y', ' ', '-', '你', '界'];
+    (0..count)
+        .map(|_| {
+            let len = rng.range(0, 50);
+            let line: String = (0..len)
+                .map(|_| ALPHABET[rng.range(0, ALPHABET.len() as u64) as usize])
+                .collect();
+            line.trim_end().to_string()
+        })
+        .collect()
+}
+
+/// Join soft-wrapped rows of the primary grid into logical lines
+fn logical_lines(parser: &Parser) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    for (_, row) in parser.screen().primary_grid().all_rows() {
+        for cell in row.cells().filter(|cell| !cell.is_wide_continuation()) {
+            line.push_str(cell.text());
+        }
+        if !row.wrapped() {
+            lines.push(line.trim_end().to_string());
+            line.clear();
+        }
+    }
+    lines.push(line.trim_end().to_string());
+
+    while lines.last().is_some_and(|line| line.is_empty()) {
+        lines.pop();
+    }
+    lines
+}
+
+fn print_lines(parser: &mut Parser, lines: &[String]) {
+    parser.process(lines.join("\r\n").as_bytes());
+}
+
+#[test]
+fn test_reflow_preserves_logical_lines() {
+    for seed in 1..=20u64 {
+        let mut rng = Rng(seed.wrapping_mul(0x9E37_79B9_7F4A_7C15));
+        let lines = random_lines(&mut rng, 30);
+
+        let mut parser = Parser::new(8, rng.range(4, 40) as usize, 1000);
+        print_lines(&mut parser, &lines);
+        assert_eq!(logical_lines(&parser), lines, "seed {seed}: initial print");
+
+        for step in 0..15 {
+            let rows = rng.range(2, 12) as usize;
+            let cols = rng.range(2, 60) as usize;
+            parser.resize(rows, cols);
+            assert_eq!(
+                logical_lines(&parser),
+                lines,
+                "seed {seed}, step {step}: resize to {rows}x{cols}"
+            );
+        }
+    }
+}
+
+#[test]
+fn test_reflow_keeps_cursor_at_logical_position() {
+    for seed in 1..=20u64 {
+        let mut rng = Rng(seed.wrapping_mul(0x2545_F491_4F6C_DD1D));
+        let mut lines = random_lines(&mut rng, 10);
+
+        let mut parser = Parser::new(6, rng.range(4, 30) as usize, 1000);
+        print_lines(&mut parser, &lines);
+
+        for _ in 0..10 {
+            parser.resize(rng.range(2, 10) as usize, rng.range(2, 50) as usize);
+        }
+
+        // Whatever the width, the next character continues the last line
+        parser.process("#".as_bytes());
+        lines.last_mut().unwrap().push('#');
+        assert_eq!(logical_lines(&parser), lines, "seed {seed}");
+    }
+}
+
+#[test]
+fn test_narrow_then_widen_restores_rows() {
+    let mut parser = Parser::new(4, 20, 100);
+    parser.process(b"$ echo hello world\r\nhello world\r\n$ ");
+
+    parser.resize(4, 6);
+    parser.resize(4, 20);
+
+    let rows: Vec<String> = parser
+        .screen()
+        .primary_grid()
+        .drawing_rows()
+        .map(|row| row.contents_trimmed())
+        .collect();
+    assert_eq!(rows, ["$ echo hello world", "hello world", "$", ""]);
+    assert_eq!(parser.screen().cursor_pos().row, 2);
+    assert_eq!(parser.screen().cursor_pos().col, 2);
+    assert_eq!(parser.screen().scrollback_available(), 0);
+}
+
+#[test]
+fn test_alternate_screen_is_not_reflowed() {
+    let mut parser = Parser::new(4, 10, 100);
+    parser.process(b"\x1b[?1049habcdefghij");
+    parser.resize(4, 5);
+    parser.resize(4, 10);
+    assert_eq!(parser.screen().contents().lines().next(), Some("abcde"));
+}
@@ -0,0 +1,164 @@
+//! Conformance tests for scroll regions (DECSTBM) in the TermTui grid
+
+#![cfg(feature = "terminal")]
+
+use ratatui_toolkit::termtui::{Parser, Pos};
+
+/// Create a 6x10 terminal with rows labelled "0".."5"
+fn labelled_screen() -> Parser {
+    let mut parser = Parser::new(6, 10, 100);
+    parser.process(b"0\r\n1\r\n2\r\n3\r\n4\r\n5");
+    parser
+}
+
+fn lines(parser: &Parser) -> Vec<String> {
+    parser
+        .screen()
+        .primary_grid()
+        .drawing_rows()
+        .map(|row| row.contents_trimmed())
+        .collect()
+}
+
+#[test]
+fn test_decstbm_moves_cursor_home() {
+    let mut parser = labelled_screen();
+    parser.process(b"\x1b[2;5r");
+    assert_eq!(parser.screen().cursor_pos(), Pos::new(0, 0));
+    assert_eq!(parser.screen().primary_grid().scroll_region(), (1, 5));
+}
+
+#[test]
+fn test_decstbm_reset_without_params() {
+    let mut parser = labelled_screen();
+    parser.process(b"\x1b[2;5r\x1b[r");
+    assert_eq!(parser.screen().primary_grid().scroll_region(), (0, 6));
+}
+
+#[test]
+fn test_decstbm_ignores_single_line_region() {
+    let mut parser = labelled_screen();
+    parser.process(b"\x1b[3;3r");
+    assert_eq!(parser.screen().primary_grid().scroll_region(), (0, 6));
+}
+
+#[test]
+fn test_linefeed_scrolls_only_region() {
+    let mut parser = labelled_screen();
+    parser.process(b"\x1b[2;4r\x1b[4;1H\nX");
+    assert_eq!(lines(&parser), ["0", "2", "3", "X", "4", "5"]);
+}
+
+#[test]
+fn test_partial_region_does_not_feed_scrollback() {
+    let mut parser = labelled_screen();
+    parser.process(b"\x1b[2;4r\x1b[4;1H");
+    for _ in 0..10 {
+        parser.process(b"\n");
+    }
+    assert_eq!(parser.screen().scrollback_available(), 0);
+    assert_eq!(lines(&parser), ["0", "", "", "", "4", "5"]);
+}
+
+#[test]
+fn test_full_region_feeds_scrollback() {
+    let mut parser = labelled_screen();
+    parser.process(b"\n\n");
+    assert_eq!(parser.screen().scrollback_available(), 2);
+}
+
+#[test]
+fn test_linefeed_below_region_does_not_scroll() {
+    let mut parser = labelled_screen();
+    parser.process(b"\x1b[2;4r\x1b[6;1H\n\n");
+    assert_eq!(parser.screen().cursor_pos().row, 5);
+    assert_eq!(lines(&parser), ["0", "1", "2", "3", "4", "5"]);
+}
+
+#[test]
+fn test_reverse_index_scrolls_region_down() {
+    let mut parser = labelled_screen();
+    parser.process(b"\x1b[2;4r\x1b[2;1H\x1bMX");
+    assert_eq!(lines(&parser), ["0", "X", "1", "2", "4", "5"]);
+}
+
+#[test]
+fn test_reverse_index_outside_region_moves_up() {
+    let mut parser = labelled_screen();
+    parser.process(b"\x1b[3;5r\x1b[2;1H\x1bM");
+    assert_eq!(parser.screen().cursor_pos().row, 0);
+    assert_eq!(lines(&parser), ["0", "1", "2", "3", "4", "5"]);
+}
+
+#[test]
+fn test_insert_line_confined_to_region() {
+    let mut parser = labelled_screen();
+    parser.process(b"\x1b[2;5r\x1b[3;3H\x1b[2L");
+    assert_eq!(parser.screen().cursor_pos(), Pos::new(0, 2));
+    assert_eq!(lines(&parser), ["0", "1", "", "", "2", "5"]);
+}
+
+#[test]
+fn test_delete_line_confined_to_region() {
+    let mut parser = labelled_screen();
+    parser.process(b"\x1b[2;5r\x1b[2;1H\x1b[2M");
+    assert_eq!(lines(&parser), ["0", "3", "4", "", "", "5"]);
+}
+
+#[test]
+fn test_insert_delete_line_outside_region_ignored() {
+    let mut parser = labelled_screen();
+    parser.process(b"\x1b[2;4r\x1b[6;1H\x1b[L\x1b[1;1H\x1b[M");
+    assert_eq!(lines(&parser), ["0", "1", "2", "3", "4", "5"]);
+}
+
+#[test]
+fn test_scroll_up_and_down_confined_to_region() {
+    let mut parser = labelled_screen();
+    parser.process(b"\x1b[2;5r\x1b[S");
+    assert_eq!(lines(&parser), ["0", "2", "3", "4", "", "5"]);
+
+    parser.process(b"\x1b[2T");
+    assert_eq!(lines(&parser), ["0", "", "", "2", "3", "5"]);
+}
+
+#[test]
+fn test_autowrap_scrolls_region() {
+    let mut parser = labelled_screen();
+    parser.process(b"\x1b[1;3r\x1b[3;1H0123456789A");
+    assert_eq!(lines(&parser), ["1", "0123456789", "A", "3", "4", "5"]);
+}
+
+#[test]
+fn test_cursor_up_down_stop_at_margins() {
+    let mut parser = labelled_screen();
+    parser.process(b"\x1b[2;4r\x1b[3;1H\x1b[10A");
+    assert_eq!(parser.screen().cursor_pos().row, 1);
+
+    parser.process(b"\x1b[10B");
+    assert_eq!(parser.screen().cursor_pos().row, 3);
+
+    // Above the region the top of the screen is reachable
+    parser.process(b"\x1b[3;5r\x1b[2;1H\x1b[10A");
+    assert_eq!(parser.screen().cursor_pos().row, 0);
+
+    // Below the region, moving up stops at the top margin (xterm behavior)
+    parser.process(b"\x1b[6;1H\x1b[10A");
+    assert_eq!(parser.screen().cursor_pos().row, 2);
+}
+
+#[test]
+fn test_resize_resets_region() {
+    let mut parser = labelled_screen();
+    parser.process(b"\x1b[2;4r");
+    parser.resize(8, 10);
+    assert_eq!(parser.screen().primary_grid().scroll_region(), (0, 8));
+}
+
+#[test]
+fn test_alternate_screen_region_scrolls() {
+    let mut parser = Parser::new(4, 10, 100);
+    parser.process(b"\x1b[?1049ha\r\nb\r\nc\r\nd\x1b[1;3r\x1b[3;1H\n");
+    assert_eq!(parser.screen().contents(), "b\nc\n\nd");
+    assert_eq!(parser.screen().scrollback_available(), 0);
+}
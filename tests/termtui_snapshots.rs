@@ -0,0 +1,91 @@
+//! Snapshot tests for TermTui rendering
+
+#![cfg(feature = "terminal")]
+
+use crossterm::event::KeyCode;
+use ratatui_toolkit::termtui::testing::{
+    assert_snapshot, buffer_snapshot, render_screen, ScriptStep, TermTuiHarness,
+};
+use ratatui_toolkit::termtui::{ProcessStatus, TermTui, TermTuiSpawnOptions};
+
+fn snapshot_path(name: &str) -> String {
+    format!("{}/tests/snapshots/{name}.snap", env!("CARGO_MANIFEST_DIR"))
+}
+
+/// Wait until the screen shows `needle`, failing the test on timeout
+async fn wait_for_contents(term: &TermTui, needle: &str) {
+    let parser = term.parser();
+    for _ in 0..100 {
+        if parser.lock().unwrap().screen().contents().contains(needle) {
+            return;
+        }
+        tokio::time::sleep(tokio::time::Duration::from_millis(20)).await;
+    }
+    panic!(
+        "timed out waiting for {needle:?}, got: {:?}",
+        parser.lock().unwrap().screen().contents()
+    );
+}
+
+#[test]
+fn test_colored_prompt() {
+    let mut harness = TermTuiHarness::new(4, 30).with_styles(true);
+    harness.feed(
+        "\x1b[1;32muser@host\x1b[0m:\x1b[34m~/src\x1b[0m$ ls\r\n\
+         Cargo.toml  \x1b[1;34msrc\x1b[0m  \x1b[4mREADME.md\x1b[0m\r\n\
+         \x1b[1;32muser@host\x1b[0m:\x1b[34m~/src\x1b[0m$ ",
+    );
+    harness.assert_snapshot(snapshot_path("colored_prompt"));
+}
+
+#[test]
+fn test_full_screen_app_script() {
+    let mut harness = TermTuiHarness::new(5, 24).with_styles(true);
+    harness.run([
+        ScriptStep::output("shell history\r\n$ editor"),
+        // Alternate screen with application cursor keys
+        ScriptStep::output("\x1b[?1049h\x1b[?1h\x1b[H\x1b[2J"),
+        ScriptStep::output("\x1b[30;47m file.txt \x1b[0m\r\n~\r\n~\x1b[5;1H-- INSERT --\x1b[2;1H"),
+        ScriptStep::key(KeyCode::Down),
+        ScriptStep::Resize { rows: 5, cols: 20 },
+    ]);
+    assert_eq!(harness.take_input(), b"\x1bOB");
+    harness.assert_snapshot(snapshot_path("full_screen_app"));
+
+    // Leaving the alternate screen restores the shell
+    harness.feed("\x1b[?1049l\x1b[?1l\r\n$ ");
+    harness.assert_snapshot(snapshot_path("full_screen_app_exit"));
+}
+
+#[test]
+fn test_scrolled_back_view() {
+    let mut harness = TermTuiHarness::new(3, 12).with_cursor(false);
+    for i in 1..=8 {
+        harness.feed(format!("line {i}\r\n"));
+    }
+    harness.step(ScriptStep::Scrollback(4));
+    harness.assert_snapshot(snapshot_path("scrolled_back"));
+}
+
+#[tokio::test]
+async fn test_pty_program_snapshot() {
+    let options = TermTuiSpawnOptions::new()
+        .with_command("printf")
+        .with_arg("\\033[31merror\\033[0m: build failed\\n")
+        .with_size(3, 30);
+    let mut term = TermTui::spawn_with_options("Build", options).unwrap();
+    for _ in 0..200 {
+        if matches!(term.status(), Some(ProcessStatus::Exited(_))) {
+            break;
+        }
+        tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+    }
+
+    wait_for_contents(&term, "failed").await;
+    let parser = term.parser();
+    let buffer = render_screen(parser.lock().unwrap().screen(), false);
+    assert_snapshot(
+        snapshot_path("pty_program"),
+        &buffer_snapshot(&buffer, true),
+    );
+}
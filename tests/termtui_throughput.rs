@@ -0,0 +1,69 @@
+//! Throughput tests for the TermTui parser and PTY reader
+
+#![cfg(feature = "terminal")]
+
+use ratatui_toolkit::termtui::{Parser, TermTui};
+use std::time::{Duration, Instant};
+
+/// Build `lines` numbered lines of colored output
+fn colored_output(lines: usize) -> Vec<u8> {
+    let mut output = Vec::new();
+    for i in 0..lines {
+        output.extend(
+            format!(
+                "\x1b[3{}mline {i:07}\x1b[0m the quick brown fox jumps over the lazy dog\r\n",
+                i % 8
+            )
+            .as_bytes(),
+        );
+    }
+    output
+}
+
+#[test]
+fn test_parser_handles_megabytes_of_output() {
+    let output = colored_output(100_000);
+    assert!(output.len() > 6_000_000);
+
+    let mut parser = Parser::new(24, 80, 1000);
+    for chunk in output.chunks(64 * 1024) {
+        parser.process(chunk);
+    }
+
+    let screen = parser.screen();
+    assert_eq!(screen.scrollback_available(), 1000);
+    let contents = screen.contents();
+    let last_line = contents.lines().rev().find(|line| !line.is_empty());
+    assert_eq!(
+        last_line,
+        Some("line 0099999 the quick brown fox jumps over the lazy dog")
+    );
+}
+
+#[tokio::test]
+async fn test_pty_output_wakes_dirty_notifier() {
+    // About 4 MB of output, followed by a marker
+    let script = "i=0; while [ $i -lt 4 ]; do head -c 1000000 /dev/zero | tr '\\0' x; \
+                  i=$((i+1)); done; echo; echo DONE";
+    let term = TermTui::spawn_with_command("Throughput", "sh", &["-c", script]).unwrap();
+    let notify = term.dirty_notifier();
+    let parser = term.parser();
+
+    let start = Instant::now();
+    let mut wakeups = 0;
+    while !parser.lock().unwrap().screen().contents().contains("DONE") {
+        tokio::time::timeout(Duration::from_secs(20), notify.notified())
+            .await
+            .expect("no output notification within 20s");
+        wakeups += 1;
+    }
+
+    assert!(wakeups > 0);
+    assert!(term.take_dirty());
+    assert!(!term.take_dirty());
+    assert!(
+        start.elapsed() < Duration::from_secs(20),
+        "reading 4 MB took {:?}",
+        start.elapsed()
+    );
+}